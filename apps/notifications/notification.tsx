@@ -1,5 +1,16 @@
 import { makeTimer } from "@solid-primitives/timer";
-import { BiRegularQrScan, BiSolidCopy, BiSolidEyedropper, BiSolidRuler } from "solid-icons/bi";
+import {
+  BiRegularQrScan,
+  BiRegularRepeat,
+  BiRegularStopwatch,
+  BiRegularTable,
+  BiRegularWorld,
+  BiSolidCamera,
+  BiSolidCopy,
+  BiSolidError,
+  BiSolidEyedropper,
+  BiSolidRuler,
+} from "solid-icons/bi";
 import {
   Accessor,
   Component,
@@ -24,6 +35,10 @@ const ICON_MAP: Record<SnappitOverlayTarget, Component<{ size?: number }>> = {
   digital_ruler: BiSolidRuler,
   color_dropper: BiSolidEyedropper,
   qr_scanner: BiRegularQrScan,
+  table_scanner: BiRegularTable,
+  translator: BiRegularWorld,
+  screenshot: BiSolidCamera,
+  repeat_region: BiRegularRepeat,
   none: BiSolidCopy,
 };
 
@@ -37,8 +52,19 @@ type NotificationProps = {
 const ANIMATION_DURATION_MS = 200;
 
 export function NotificationItem(props: NotificationProps) {
-  const IconComponent = createMemo(() => ICON_MAP[props.target() ?? "none"]);
+  const isLowConfidence = createMemo(
+    () => props.target() === "capture" && props.data() === "low_confidence",
+  );
+  const isCountdown = createMemo(
+    () => props.target() === "capture" && props.data() === "countdown",
+  );
+  const IconComponent = createMemo(() => {
+    if (isCountdown()) return BiRegularStopwatch;
+    return isLowConfidence() ? BiSolidError : ICON_MAP[props.target() ?? "none"];
+  });
   const [progress, setProgress] = createSignal(100);
+  // The countdown payload is its length in seconds
+  const secondsLeft = createMemo(() => Math.ceil((progress() / 100) * Number(props.payload())));
 
   createEffect(
     on(props.notificationId, (currentId) => {
@@ -55,7 +81,9 @@ export function NotificationItem(props: NotificationProps) {
 
       (async () => {
         await SnappitStore.sync();
-        const durationMs = await NotificationDurationSettings.getDurationMs();
+        const durationMs = isCountdown()
+          ? Number(props.payload()) * 1000
+          : await NotificationDurationSettings.getDurationMs();
 
         const startTime = Date.now();
         const clearProgress = makeTimer(
@@ -110,7 +138,12 @@ export function NotificationItem(props: NotificationProps) {
         `pointer-events-none opacity-85 flex h-full w-full flex-col items-center justify-center gap-5 rounded-4xl text-center text-foreground backdrop-blur-xl`,
       )}
     >
-      <div class="flex h-16 w-16 items-center justify-center rounded-full bg-primary/15 text-primary">
+      <div
+        class={cn(
+          "flex h-16 w-16 items-center justify-center rounded-full bg-primary/15 text-primary",
+          isLowConfidence() && "bg-warning/15 text-warning",
+        )}
+      >
         <Dynamic component={IconComponent()} size={38} />
       </div>
 
@@ -121,11 +154,17 @@ export function NotificationItem(props: NotificationProps) {
           <Match when={props.target() === "qr_scanner"}>
             {props.data() === "on_url" ? "Link opened" : "Content copied"}
           </Match>
+          <Match when={props.target() === "table_scanner"}>Table copied</Match>
+          <Match when={props.target() === "translator"}>Translation copied</Match>
+          <Match when={props.target() === "screenshot"}>Screenshot saved</Match>
+          <Match when={isLowConfidence()}>Check the copied text</Match>
+          <Match when={isCountdown()}>Capturing in {secondsLeft()}</Match>
         </Switch>
       </div>
 
       <div class="text-sm w-[240px] text-foreground opacity-90 truncate">
         <Switch fallback={<>{props.payload()}</>}>
+          <Match when={isCountdown()}>Open the menu or tooltip to capture</Match>
           <Match when={props.target() === "color_dropper"}>
             <div class="flex items-center justify-center flex-wrap gap-2">
               <div
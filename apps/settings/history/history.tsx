@@ -1,5 +1,8 @@
+import { debounce } from "@solid-primitives/scheduled";
 import { listen, UnlistenFn } from "@tauri-apps/api/event";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
 import {
+  BiRegularExport,
   BiRegularQr,
   BiRegularRuler,
   BiRegularSearch,
@@ -7,56 +10,83 @@ import {
   BiRegularTrash,
   BiSolidPalette,
 } from "solid-icons/bi";
-import { createMemo, createSignal, For, onCleanup, onMount, Show } from "solid-js";
+import {
+  createEffect,
+  createMemo,
+  createSignal,
+  For,
+  on,
+  onCleanup,
+  onMount,
+  Show,
+} from "solid-js";
 
-import { CaptureHistory, CaptureHistoryItem, HISTORY_UPDATED_EVENT } from "@/shared/history";
+import {
+  CaptureHistory,
+  CaptureHistoryItem,
+  CaptureHistoryQuery,
+  ExportFormat,
+  HISTORY_UPDATED_EVENT,
+} from "@/shared/history";
 import { cn } from "@/shared/libs/cn";
-import { SnappitStore } from "@/shared/store";
 import { Button } from "@/shared/ui/button";
 
 import { HistoryItem } from "./history_item";
 
 type ToolType = CaptureHistoryItem["type"];
 
-function getSearchableContent(item: CaptureHistoryItem): string {
-  switch (item.type) {
-    case "ocr":
-      return item.payload.text;
-    case "qr":
-      return item.payload.content;
-    case "dropper":
-      return `${item.payload.hex} ${item.payload.formattedColor}`;
-    case "ruler":
-      return item.payload.value;
-  }
-}
+const ALL_TOOL_TYPES: ToolType[] = ["ocr", "qr", "dropper", "ruler"];
+const SEARCH_DEBOUNCE_MS = 200;
 
 export function History() {
-  const [history] = CaptureHistory.create();
+  const [items, setItems] = createSignal<CaptureHistoryItem[]>([]);
+  const [total, setTotal] = createSignal(0);
+  const [hasAny, setHasAny] = createSignal(false);
   const [searchQuery, setSearchQuery] = createSignal("");
   const [enabledFilters, setEnabledFilters] = createSignal<Set<ToolType>>(
-    new Set(["ocr", "qr", "dropper", "ruler"]),
+    new Set(ALL_TOOL_TYPES),
   );
   let unlisten: UnlistenFn | undefined;
+  let requestId = 0;
 
-  const items = createMemo(() => history() ?? []);
-  const isEmpty = createMemo(() => items().length === 0);
+  const isEmpty = createMemo(() => !hasAny());
+  const hasMore = createMemo(() => items().length < total());
 
-  const filteredItems = createMemo(() => {
-    const query = searchQuery().toLowerCase().trim();
-    const filters = enabledFilters();
+  const currentQuery = (offset: number): CaptureHistoryQuery => ({
+    search: searchQuery().trim() || undefined,
+    kinds: [...enabledFilters()],
+    offset,
+  });
 
-    return items().filter((item) => {
-      if (!filters.has(item.type)) return false;
+  const reload = async () => {
+    const id = ++requestId;
+    const [page, all] = await Promise.all([
+      CaptureHistory.query(currentQuery(0)),
+      CaptureHistory.query({ limit: 1 }),
+    ]);
+    if (id !== requestId) return;
+
+    setItems(page.items);
+    setTotal(page.total);
+    setHasAny(all.total > 0);
+  };
 
-      if (query) {
-        const content = getSearchableContent(item).toLowerCase();
-        return content.includes(query);
-      }
+  const loadMore = async () => {
+    const id = requestId;
+    const page = await CaptureHistory.query(currentQuery(items().length));
+    if (id !== requestId) return;
 
-      return true;
-    });
-  });
+    setItems((prev) => [...prev, ...page.items]);
+    setTotal(page.total);
+  };
+
+  const reloadDebounced = debounce(reload, SEARCH_DEBOUNCE_MS);
+
+  createEffect(
+    on([searchQuery, enabledFilters], () => reloadDebounced(), {
+      defer: true,
+    }),
+  );
 
   const toggleFilter = (type: ToolType) => {
     setEnabledFilters((prev) => {
@@ -76,11 +106,20 @@ export function History() {
     await CaptureHistory.clear();
   };
 
+  const handleExport = async (format: ExportFormat) => {
+    try {
+      const path = await CaptureHistory.export(format);
+      await revealItemInDir(path);
+    } catch (err) {
+      console.error("Failed to export history", err);
+    }
+  };
+
   onMount(async () => {
-    await SnappitStore.sync();
+    await reload();
 
     unlisten = await listen(HISTORY_UPDATED_EVENT, async () => {
-      await SnappitStore.sync();
+      await reload();
     });
   });
 
@@ -94,6 +133,14 @@ export function History() {
 
       <Show when={!isEmpty()}>
         <div class="flex justify-end gap-2 mb-3 shrink-0">
+          <Button variant="outline" size="sm" onClick={() => handleExport("json")}>
+            <BiRegularExport class="w-4 h-4 mr-2" />
+            JSON
+          </Button>
+          <Button variant="outline" size="sm" onClick={() => handleExport("csv")}>
+            <BiRegularExport class="w-4 h-4 mr-2" />
+            CSV
+          </Button>
           <Button variant="destructive" size="sm" onClick={handleClearAll}>
             <BiRegularTrash class="w-4 h-4 mr-2" />
             Clear All
@@ -163,7 +210,7 @@ export function History() {
         }
       >
         <Show
-          when={filteredItems().length > 0}
+          when={items().length > 0}
           fallback={
             <div class="flex flex-col items-center justify-center h-64 text-muted-foreground">
               <p class="text-sm">No results found</p>
@@ -172,7 +219,12 @@ export function History() {
           }
         >
           <div class="border-t pt-3 flex flex-col gap-2 pb-3 -ml-3 pl-3 -mr-3 pr-3 flex-1 overflow-y-auto [scrollbar-gutter:stable]">
-            <For each={filteredItems()}>{(item) => <HistoryItem item={item} />}</For>
+            <For each={items()}>{(item) => <HistoryItem item={item} />}</For>
+            <Show when={hasMore()}>
+              <Button variant="ghost" size="sm" class="self-center" onClick={loadMore}>
+                Load more
+              </Button>
+            </Show>
           </div>
         </Show>
       </Show>
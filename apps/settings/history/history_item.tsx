@@ -9,10 +9,13 @@ import {
   BiRegularTrash,
   BiSolidPalette,
 } from "solid-icons/bi";
-import { Component, createMemo, createSignal, Match, Show, Switch } from "solid-js";
+import { Component, createMemo, createResource, createSignal, Match, Show, Switch } from "solid-js";
 
+import { SNAPPIT_CONSTS } from "@/shared/constants";
 import { CaptureHistory, CaptureHistoryItem } from "@/shared/history";
+import { ColorFormat, DEFAULT_COLOR_FORMAT, formatColor } from "@/shared/libs/color_format";
 import { normalizeHttpUrl } from "@/shared/libs/normalize_url";
+import { SnappitStore } from "@/shared/store";
 import { Button } from "@/shared/ui/button";
 
 interface HistoryItemProps {
@@ -37,25 +40,32 @@ function formatTime(timestamp: number): string {
   return date.toLocaleDateString([], { month: "short", day: "numeric" }) + ` ${time}`;
 }
 
-function getDisplayValue(item: CaptureHistoryItem): string {
+function getDisplayValue(item: CaptureHistoryItem, colorFormat: ColorFormat): string {
   switch (item.type) {
     case "ocr":
       return item.payload.text;
     case "qr":
       return item.payload.content;
     case "dropper":
-      return item.payload.formattedColor;
+      return formatColor(item.payload.rgb, item.payload.hex, colorFormat);
     case "ruler":
       return item.payload.value;
   }
 }
 
-function getCopyValue(item: CaptureHistoryItem): string {
-  return getDisplayValue(item);
-}
-
 export const HistoryItem: Component<HistoryItemProps> = (props) => {
   const [copied, setCopied] = createSignal(false);
+  const [colorFormat] = SnappitStore.createValue<ColorFormat>(
+    SNAPPIT_CONSTS.store.keys.preferred_color_format,
+  );
+  const [thumbnail] = createResource(
+    () => (props.item.has_thumbnail ? props.item.id : false),
+    (id) => CaptureHistory.getThumbnail(id),
+  );
+
+  const displayValue = createMemo(() =>
+    getDisplayValue(props.item, colorFormat() ?? DEFAULT_COLOR_FORMAT),
+  );
 
   const qrUrl = createMemo(() => {
     if (props.item.type === "qr") {
@@ -65,8 +75,7 @@ export const HistoryItem: Component<HistoryItemProps> = (props) => {
   });
 
   const handleCopy = async () => {
-    const value = getCopyValue(props.item);
-    await writeText(value);
+    await writeText(displayValue());
     setCopied(true);
     setTimeout(() => setCopied(false), COPY_FEEDBACK_DURATION);
   };
@@ -101,6 +110,16 @@ export const HistoryItem: Component<HistoryItemProps> = (props) => {
         </Switch>
       </div>
 
+      <Show when={thumbnail()}>
+        {(src) => (
+          <img
+            src={src()}
+            alt=""
+            class="flex-shrink-0 h-8 max-w-16 rounded-sm object-cover border border-border"
+          />
+        )}
+      </Show>
+
       <div class="flex-1 min-w-0 overflow-hidden">
         <Switch>
           <Match when={qrUrl()}>
@@ -108,7 +127,7 @@ export const HistoryItem: Component<HistoryItemProps> = (props) => {
               class="text-sm truncate text-blue-500 hover:underline cursor-pointer text-left w-full"
               onClick={handleOpenUrl}
             >
-              {getDisplayValue(props.item)}
+              {displayValue()}
             </button>
           </Match>
           <Match when={!qrUrl()}>
@@ -121,7 +140,7 @@ export const HistoryItem: Component<HistoryItemProps> = (props) => {
                   }}
                 />
               </Show>
-              <p class="text-sm truncate">{getDisplayValue(props.item)}</p>
+              <p class="text-sm truncate">{displayValue()}</p>
             </div>
           </Match>
         </Switch>
@@ -3,6 +3,8 @@ import { createMemo, onMount, Show } from "solid-js";
 
 import { refreshSystemLanguagesInfo, systemLanguagesInfo } from "@/shared/ocr/installed_languages";
 import { DEFAULT_VALUE, RECOGNITION_LANGUAGE_OPTIONS } from "@/shared/ocr/recognition_language";
+import { TessdataImport } from "@/shared/ocr/tessdata_import";
+import { TessdataSourceSettings } from "@/shared/ocr/tessdata_source";
 import { TesseractLanguageList } from "@/shared/ocr/tesseract_language_list";
 import { createRecognitionLanguages } from "@/shared/ocr/use_recognition_languages";
 import { RadioGroup, RadioGroupItemCard } from "@/shared/ui/radio_group";
@@ -13,6 +15,8 @@ export function Languages() {
     tesseractLanguageSet,
     installedLanguages,
     downloading,
+    downloadProgress,
+    cancelDownload,
     isSystemLanguage,
     isAutoLanguageSelected,
     setRecognitionLanguage,
@@ -129,17 +133,21 @@ export function Languages() {
 
           <div class="flex flex-col min-h-0 -m-3 border-t pt-3 p-3 pb-0 overflow-y-auto [scrollbar-gutter:stable]">
             <div class="text-muted-foreground text-sm">Tesseract languages</div>
+            <TessdataSourceSettings />
+            <TessdataImport />
             <div class="">
               <TesseractLanguageList
                 options={sortedOptions}
                 installedLanguages={installedLanguages}
                 downloading={downloading}
+                downloadProgress={downloadProgress}
                 selectedLanguages={tesseractLanguageSet}
                 isSystemLanguage={isSystemLanguage}
                 canDeleteLanguage={canDeleteLanguage}
                 onToggle={toggleRecognitionLanguage}
                 onDownload={handleDownload}
                 onDelete={deleteLanguage}
+                onCancelDownload={cancelDownload}
               />
             </div>
           </div>
@@ -1,6 +1,16 @@
 import {
+  BiRegularCode,
+  BiRegularCodeBlock,
+  BiRegularError,
+  BiRegularHistory,
+  BiRegularImage,
   BiRegularLinkExternal,
+  BiRegularMath,
+  BiRegularRepeat,
+  BiRegularRevision,
+  BiRegularStopwatch,
   BiRegularSun,
+  BiRegularTable,
   BiRegularText,
   BiRegularTimer,
   BiSolidBell,
@@ -9,10 +19,11 @@ import {
   BiSolidPalette,
   BiSolidUser,
 } from "solid-icons/bi";
-import { createMemo, onMount, Show } from "solid-js";
+import { createMemo, For, onMount, Show } from "solid-js";
 
 import { AutostartSettings } from "@/shared/autostart";
 import { SNAPPIT_CONSTS } from "@/shared/constants";
+import { CAPTURE_DELAY_OPTIONS, DEFAULT_CAPTURE_DELAY } from "@/shared/libs/capture_delay";
 import {
   COLOR_FORMAT_OPTIONS,
   ColorFormat,
@@ -25,12 +36,33 @@ import {
   NotificationDurationSettings,
 } from "@/shared/notifications";
 import { NotificationSettings } from "@/shared/notifications";
+import {
+  DEFAULT_MATH_MODE,
+  DEFAULT_MIN_CONFIDENCE,
+  DEFAULT_PREPROCESS_STEPS,
+  DEFAULT_TABLE_FORMAT,
+  LINE_BREAK_MODE_OPTIONS,
+  LineBreakMode,
+  MATH_MODE_OPTIONS,
+  MathMode,
+  MIN_CONFIDENCE_OPTIONS,
+  PREPROCESS_STEP_OPTIONS,
+  PreprocessStep,
+  resolveLineBreakMode,
+  TABLE_FORMAT_OPTIONS,
+  TableFormat,
+  togglePreprocessStep,
+} from "@/shared/ocr";
 import { SnappitStore } from "@/shared/store";
 import { Theme } from "@/shared/theme";
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/shared/ui/select";
 import { Switch, SwitchControl, SwitchLabel, SwitchThumb } from "@/shared/ui/switch";
 import { ToggleGroup, ToggleGroupItem } from "@/shared/ui/toggle_group";
 
+import { ScreenshotSettings } from "./screenshot_settings";
+import { TesseractSettings } from "./tesseract_settings";
+import { TranslationSettings } from "./translation_settings";
+
 export function Preferences() {
   const [theme, setTheme, isThemeReady] = Theme.create();
   const [notificationsEnabled, setNotificationsEnabled] = NotificationSettings.create();
@@ -45,12 +77,44 @@ export function Preferences() {
   const [soundEnabled, setSoundEnabled, , soundReady] = SnappitStore.createValue<boolean>(
     SNAPPIT_CONSTS.store.keys.sound_enabled,
   );
-  const [ocrKeepLineBreaks, setOcrKeepLineBreaks, , ocrReady] = SnappitStore.createValue<boolean>(
+  const [ocrKeepLineBreaks, , , ocrReady] = SnappitStore.createValue<boolean>(
     SNAPPIT_CONSTS.store.keys.ocr_keep_line_breaks,
   );
+  const [ocrLineBreaks, setOcrLineBreaks, , ocrLineBreaksReady] =
+    SnappitStore.createValue<LineBreakMode>(SNAPPIT_CONSTS.store.keys.ocr_line_breaks);
+  const [ocrCodeMode, setOcrCodeMode, , ocrCodeModeReady] = SnappitStore.createValue<boolean>(
+    SNAPPIT_CONSTS.store.keys.ocr_code_mode,
+  );
+  const [ocrCodeFence, setOcrCodeFence, , ocrCodeFenceReady] = SnappitStore.createValue<boolean>(
+    SNAPPIT_CONSTS.store.keys.ocr_code_fence,
+  );
+  const [ocrMathMode, setOcrMathMode, , ocrMathModeReady] = SnappitStore.createValue<MathMode>(
+    SNAPPIT_CONSTS.store.keys.ocr_math_mode,
+  );
+  const [ocrMinConfidence, setOcrMinConfidence, , ocrMinConfidenceReady] =
+    SnappitStore.createValue<number>(SNAPPIT_CONSTS.store.keys.ocr_min_confidence);
+  const [ocrRetryLowConfidence, setOcrRetryLowConfidence, , ocrRetryLowConfidenceReady] =
+    SnappitStore.createValue<boolean>(SNAPPIT_CONSTS.store.keys.ocr_retry_low_confidence);
+  const [tableFormat, setTableFormat, , tableFormatReady] = SnappitStore.createValue<TableFormat>(
+    SNAPPIT_CONSTS.store.keys.table_format,
+  );
+  const [captureDelay, setCaptureDelay, , captureDelayReady] = SnappitStore.createValue<number>(
+    SNAPPIT_CONSTS.store.keys.capture_delay,
+  );
+  const [captureDelayRepeatRegion, setCaptureDelayRepeatRegion, , captureDelayRepeatRegionReady] =
+    SnappitStore.createValue<boolean>(SNAPPIT_CONSTS.store.keys.capture_delay_repeat_region);
   const [qrAutoOpenUrls, setQrAutoOpenUrls, , qrReady] = SnappitStore.createValue<boolean>(
     SNAPPIT_CONSTS.store.keys.qr_auto_open_urls,
   );
+  const [historyThumbnails, setHistoryThumbnails, , historyReady] =
+    SnappitStore.createValue<boolean>(SNAPPIT_CONSTS.store.keys.history_thumbnails);
+  const [ocrPreprocess, setOcrPreprocess, , ocrPreprocessReady] = SnappitStore.createValue<
+    PreprocessStep[]
+  >(SNAPPIT_CONSTS.store.keys.ocr_preprocess);
+
+  const preprocessSteps = () => ocrPreprocess() ?? DEFAULT_PREPROCESS_STEPS;
+  const minConfidence = () => ocrMinConfidence() ?? DEFAULT_MIN_CONFIDENCE;
+  const delay = () => captureDelay() ?? DEFAULT_CAPTURE_DELAY;
 
   const isReady = createMemo(
     () =>
@@ -59,6 +123,17 @@ export function Preferences() {
       colorFormatReady() &&
       soundReady() &&
       ocrReady() &&
+      ocrLineBreaksReady() &&
+      ocrCodeModeReady() &&
+      ocrCodeFenceReady() &&
+      ocrMathModeReady() &&
+      ocrMinConfidenceReady() &&
+      ocrRetryLowConfidenceReady() &&
+      tableFormatReady() &&
+      captureDelayReady() &&
+      captureDelayRepeatRegionReady() &&
+      ocrPreprocessReady() &&
+      historyReady() &&
       qrReady(),
   );
 
@@ -122,20 +197,146 @@ export function Preferences() {
             </SwitchControl>
           </Switch>
 
+          <div class="flex justify-between items-center h-[30px]">
+            <div class="text-sm font-light flex gap-2 items-center">
+              <BiRegularText />
+              Line breaks
+            </div>
+            <ToggleGroup
+              size={"sm"}
+              color={"product"}
+              value={resolveLineBreakMode(ocrLineBreaks(), ocrKeepLineBreaks())}
+            >
+              <For each={LINE_BREAK_MODE_OPTIONS}>
+                {(option) => (
+                  <ToggleGroupItem
+                    onClick={() => setOcrLineBreaks(option.value)}
+                    value={option.value}
+                  >
+                    {option.label}
+                  </ToggleGroupItem>
+                )}
+              </For>
+            </ToggleGroup>
+          </div>
+
           <Switch
             class="flex justify-between items-center h-[30px]"
-            checked={ocrKeepLineBreaks() ?? true}
-            onChange={(value) => setOcrKeepLineBreaks(value)}
+            checked={ocrCodeMode() ?? false}
+            onChange={(value) => setOcrCodeMode(value)}
           >
             <SwitchLabel class="text-sm font-light flex gap-2 items-center">
-              <BiRegularText />
-              Keep line breaks
+              <BiRegularCode />
+              Code mode
             </SwitchLabel>
             <SwitchControl variant={"product"}>
               <SwitchThumb />
             </SwitchControl>
           </Switch>
 
+          <Show when={ocrCodeMode()}>
+            <Switch
+              class="flex justify-between items-center h-[30px]"
+              checked={ocrCodeFence() ?? false}
+              onChange={(value) => setOcrCodeFence(value)}
+            >
+              <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+                <BiRegularCodeBlock />
+                Wrap code in a fenced block
+              </SwitchLabel>
+              <SwitchControl variant={"product"}>
+                <SwitchThumb />
+              </SwitchControl>
+            </Switch>
+          </Show>
+
+          {/* Code mode already reads symbols as they are and takes precedence */}
+          <Show when={!ocrCodeMode()}>
+            <div class="flex justify-between items-center h-[30px]">
+              <div class="text-sm font-light flex gap-2 items-center">
+                <BiRegularMath />
+                Math
+              </div>
+              <ToggleGroup size={"sm"} color={"product"} value={ocrMathMode() ?? DEFAULT_MATH_MODE}>
+                <For each={MATH_MODE_OPTIONS}>
+                  {(option) => (
+                    <ToggleGroupItem
+                      onClick={() => setOcrMathMode(option.value)}
+                      value={option.value}
+                    >
+                      {option.label}
+                    </ToggleGroupItem>
+                  )}
+                </For>
+              </ToggleGroup>
+            </div>
+          </Show>
+
+          <div class="flex justify-between items-center h-[30px]">
+            <div class="text-sm font-light flex gap-2 items-center">
+              <BiRegularTable />
+              Table format
+            </div>
+            <ToggleGroup
+              size={"sm"}
+              color={"product"}
+              value={tableFormat() ?? DEFAULT_TABLE_FORMAT}
+            >
+              <For each={TABLE_FORMAT_OPTIONS}>
+                {(option) => (
+                  <ToggleGroupItem
+                    onClick={() => setTableFormat(option.value)}
+                    value={option.value}
+                  >
+                    {option.label}
+                  </ToggleGroupItem>
+                )}
+              </For>
+            </ToggleGroup>
+          </div>
+
+          <div class="flex justify-between items-center h-[30px]">
+            <div class="text-sm font-light flex gap-2 items-center">
+              <BiRegularError />
+              Low confidence warning
+            </div>
+            <Select
+              value={minConfidence()}
+              onChange={(value) => value !== null && setOcrMinConfidence(value)}
+              options={MIN_CONFIDENCE_OPTIONS.map((o) => o.value)}
+              itemComponent={(props) => (
+                <SelectItem item={props.item}>
+                  {MIN_CONFIDENCE_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+                </SelectItem>
+              )}
+            >
+              <SelectTrigger class="w-[130px]">
+                <SelectValue<number>>
+                  {(state) =>
+                    MIN_CONFIDENCE_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+                  }
+                </SelectValue>
+              </SelectTrigger>
+              <SelectContent />
+            </Select>
+          </div>
+
+          <Show when={minConfidence() > 0}>
+            <Switch
+              class="flex justify-between items-center h-[30px]"
+              checked={ocrRetryLowConfidence() ?? false}
+              onChange={(value) => setOcrRetryLowConfidence(value)}
+            >
+              <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+                <BiRegularRevision />
+                Retry low confidence captures
+              </SwitchLabel>
+              <SwitchControl variant={"product"}>
+                <SwitchThumb />
+              </SwitchControl>
+            </Switch>
+          </Show>
+
           <Switch
             class="flex justify-between items-center h-[30px]"
             checked={toolsEnabled() ?? true}
@@ -162,8 +363,96 @@ export function Preferences() {
               <SwitchThumb />
             </SwitchControl>
           </Switch>
+
+          <Switch
+            class="flex justify-between items-center h-[30px]"
+            checked={historyThumbnails() ?? false}
+            onChange={(value) => setHistoryThumbnails(value)}
+          >
+            <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+              <BiRegularHistory />
+              Save thumbnails in history
+            </SwitchLabel>
+            <SwitchControl variant={"product"}>
+              <SwitchThumb />
+            </SwitchControl>
+          </Switch>
+        </div>
+
+        <div class="rounded-lg p-3 bg-card mb-3">
+          <div class="flex justify-between items-center h-[30px]">
+            <div class="text-sm font-light flex gap-2 items-center">
+              <BiRegularStopwatch />
+              Capture delay
+            </div>
+            <Select
+              value={delay()}
+              onChange={(value) => value !== null && setCaptureDelay(value)}
+              options={CAPTURE_DELAY_OPTIONS.map((o) => o.value)}
+              itemComponent={(props) => (
+                <SelectItem item={props.item}>
+                  {CAPTURE_DELAY_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+                </SelectItem>
+              )}
+            >
+              <SelectTrigger class="w-[130px]">
+                <SelectValue<number>>
+                  {(state) =>
+                    CAPTURE_DELAY_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+                  }
+                </SelectValue>
+              </SelectTrigger>
+              <SelectContent />
+            </Select>
+          </div>
+
+          <Show when={delay() > 0}>
+            <Switch
+              class="flex justify-between items-center h-[30px]"
+              checked={captureDelayRepeatRegion() ?? false}
+              onChange={(value) => setCaptureDelayRepeatRegion(value)}
+            >
+              <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+                <BiRegularRepeat />
+                Repeat last region after the delay
+              </SwitchLabel>
+              <SwitchControl variant={"product"}>
+                <SwitchThumb />
+              </SwitchControl>
+            </Switch>
+          </Show>
         </div>
 
+        <div class="rounded-lg p-3 bg-card mb-3">
+          <div class="text-sm font-light flex gap-2 items-center h-[30px]">
+            <BiRegularImage />
+            Text recognition preprocessing
+          </div>
+
+          <For each={PREPROCESS_STEP_OPTIONS}>
+            {(option) => (
+              <Switch
+                class="flex justify-between items-center h-[30px] pl-6"
+                checked={preprocessSteps().includes(option.value)}
+                onChange={(value) =>
+                  setOcrPreprocess(togglePreprocessStep(preprocessSteps(), option.value, value))
+                }
+              >
+                <SwitchLabel class="text-sm font-light">{option.label}</SwitchLabel>
+                <SwitchControl variant={"product"}>
+                  <SwitchThumb />
+                </SwitchControl>
+              </Switch>
+            )}
+          </For>
+        </div>
+
+        <TesseractSettings />
+
+        <TranslationSettings />
+
+        <ScreenshotSettings />
+
         <div class="rounded-lg p-3 bg-card mb-3">
           <Switch
             class="flex justify-between items-center h-[30px]"
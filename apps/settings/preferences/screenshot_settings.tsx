@@ -0,0 +1,151 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import {
+  BiRegularCopy,
+  BiRegularFolder,
+  BiRegularImageAlt,
+  BiRegularRename,
+  BiRegularSliderAlt,
+} from "solid-icons/bi";
+import { createMemo, For, Show } from "solid-js";
+
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+import {
+  DEFAULT_SCREENSHOT_FORMAT,
+  SCREENSHOT_FORMAT_OPTIONS,
+  SCREENSHOT_QUALITY_OPTIONS,
+  ScreenshotFormat,
+} from "@/shared/libs/screenshot_format";
+import { SnappitStore } from "@/shared/store";
+import { Button } from "@/shared/ui/button";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/shared/ui/select";
+import { Switch, SwitchControl, SwitchLabel, SwitchThumb } from "@/shared/ui/switch";
+import { ToggleGroup, ToggleGroupItem } from "@/shared/ui/toggle_group";
+
+const INPUT_CLASS =
+  "w-[180px] h-7 px-2 text-sm rounded-md border border-input bg-background focus:outline-none focus:ring-1 focus:ring-ring";
+
+export function ScreenshotSettings() {
+  const keys = SNAPPIT_CONSTS.store.keys;
+  const defaults = SNAPPIT_CONSTS.defaults;
+  const [folder, setFolder, , folderReady] = SnappitStore.createValue<string>(
+    keys.screenshot_folder,
+  );
+  const [filename, setFilename, , filenameReady] = SnappitStore.createValue<string>(
+    keys.screenshot_filename,
+  );
+  const [format, setFormat, , formatReady] = SnappitStore.createValue<ScreenshotFormat>(
+    keys.screenshot_format,
+  );
+  const [quality, setQuality, , qualityReady] = SnappitStore.createValue<number>(
+    keys.screenshot_quality,
+  );
+  const [copyImage, setCopyImage, , copyImageReady] = SnappitStore.createValue<boolean>(
+    keys.screenshot_copy_image,
+  );
+
+  const isReady = createMemo(
+    () => folderReady() && filenameReady() && formatReady() && qualityReady() && copyImageReady(),
+  );
+  const selectedFormat = () => format() ?? DEFAULT_SCREENSHOT_FORMAT;
+
+  const chooseFolder = async () => {
+    const selected = await open({ directory: true });
+    if (selected) setFolder(selected);
+  };
+
+  return (
+    <div class="rounded-lg p-3 bg-card mb-3" classList={{ "opacity-50": !isReady() }}>
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularFolder /> Screenshot folder
+        </div>
+        <div class="flex gap-2">
+          <input
+            type="text"
+            placeholder="Pictures/Snappit"
+            value={folder() ?? ""}
+            onChange={(e) => setFolder(e.currentTarget.value.trim())}
+            class={INPUT_CLASS}
+          />
+          <Button variant={"muted"} size={"sm"} onClick={chooseFolder}>
+            Choose
+          </Button>
+        </div>
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularRename /> File name
+        </div>
+        <input
+          type="text"
+          placeholder={defaults.screenshot_filename}
+          value={filename() ?? ""}
+          onChange={(e) => setFilename(e.currentTarget.value.trim())}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularImageAlt /> Format
+        </div>
+        <ToggleGroup size={"sm"} color={"product"} value={selectedFormat()}>
+          <For each={SCREENSHOT_FORMAT_OPTIONS}>
+            {(option) => (
+              <ToggleGroupItem onClick={() => setFormat(option.value)} value={option.value}>
+                {option.label}
+              </ToggleGroupItem>
+            )}
+          </For>
+        </ToggleGroup>
+      </div>
+
+      <Show when={selectedFormat() !== "png"}>
+        <div class="flex justify-between items-center h-[30px]">
+          <div class="text-sm font-light flex gap-2 items-center">
+            <BiRegularSliderAlt /> Quality
+          </div>
+          <Select
+            value={quality() ?? defaults.screenshot_quality}
+            onChange={(value) => value !== null && setQuality(value)}
+            options={SCREENSHOT_QUALITY_OPTIONS.map((o) => o.value)}
+            itemComponent={(props) => (
+              <SelectItem item={props.item}>
+                {SCREENSHOT_QUALITY_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+              </SelectItem>
+            )}
+          >
+            <SelectTrigger class="w-[130px]">
+              <SelectValue<number>>
+                {(state) =>
+                  SCREENSHOT_QUALITY_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+                }
+              </SelectValue>
+            </SelectTrigger>
+            <SelectContent />
+          </Select>
+        </div>
+      </Show>
+
+      <Switch
+        class="flex justify-between items-center h-[30px]"
+        checked={copyImage() ?? false}
+        onChange={(value) => setCopyImage(value)}
+      >
+        <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+          <BiRegularCopy />
+          Copy image to clipboard
+        </SwitchLabel>
+        <SwitchControl variant={"product"}>
+          <SwitchThumb />
+        </SwitchControl>
+      </Switch>
+
+      <p class="text-xs text-muted-foreground mt-1">
+        The file name can use {"{date}"}, {"{time}"}, {"{app}"} and {"{monitor}"}. Quality applies
+        to JPEG and WebP, PNG is saved without loss.
+      </p>
+    </div>
+  );
+}
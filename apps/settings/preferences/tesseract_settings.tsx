@@ -0,0 +1,140 @@
+import { BiRegularCog, BiRegularDetail, BiRegularFilter, BiRegularSpaceBar } from "solid-icons/bi";
+import { createMemo } from "solid-js";
+
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+import {
+  DEFAULT_OCR_ENGINE_MODE,
+  DEFAULT_PAGE_SEG_MODE,
+  OCR_ENGINE_MODE_OPTIONS,
+  OcrEngineMode,
+  PAGE_SEG_MODE_OPTIONS,
+  PageSegMode,
+} from "@/shared/ocr";
+import { SnappitStore } from "@/shared/store";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/shared/ui/select";
+import { Switch, SwitchControl, SwitchLabel, SwitchThumb } from "@/shared/ui/switch";
+
+const INPUT_CLASS =
+  "w-[130px] h-7 px-2 text-sm rounded-md border border-input bg-background focus:outline-none focus:ring-1 focus:ring-ring";
+
+export function TesseractSettings() {
+  const keys = SNAPPIT_CONSTS.store.keys;
+  const [pageSegMode, setPageSegMode, , psmReady] = SnappitStore.createValue<PageSegMode>(
+    keys.ocr_page_seg_mode,
+  );
+  const [engineMode, setEngineMode, , oemReady] = SnappitStore.createValue<OcrEngineMode>(
+    keys.ocr_engine_mode,
+  );
+  const [whitelist, setWhitelist, , whitelistReady] = SnappitStore.createValue<string>(
+    keys.ocr_char_whitelist,
+  );
+  const [blacklist, setBlacklist, , blacklistReady] = SnappitStore.createValue<string>(
+    keys.ocr_char_blacklist,
+  );
+  const [preserveSpaces, setPreserveSpaces, , spacesReady] = SnappitStore.createValue<boolean>(
+    keys.ocr_preserve_interword_spaces,
+  );
+
+  const isReady = createMemo(
+    () => psmReady() && oemReady() && whitelistReady() && blacklistReady() && spacesReady(),
+  );
+
+  return (
+    <div class="rounded-lg p-3 bg-card mb-3" classList={{ "opacity-50": !isReady() }}>
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularDetail /> Page layout
+        </div>
+        <Select
+          value={pageSegMode() ?? DEFAULT_PAGE_SEG_MODE}
+          onChange={(value) => value && setPageSegMode(value)}
+          options={PAGE_SEG_MODE_OPTIONS.map((o) => o.value)}
+          itemComponent={(props) => (
+            <SelectItem item={props.item}>
+              {PAGE_SEG_MODE_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+            </SelectItem>
+          )}
+        >
+          <SelectTrigger class="w-[130px]">
+            <SelectValue<PageSegMode>>
+              {(state) =>
+                PAGE_SEG_MODE_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+              }
+            </SelectValue>
+          </SelectTrigger>
+          <SelectContent />
+        </Select>
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularCog /> Tesseract engine
+        </div>
+        <Select
+          value={engineMode() ?? DEFAULT_OCR_ENGINE_MODE}
+          onChange={(value) => value && setEngineMode(value)}
+          options={OCR_ENGINE_MODE_OPTIONS.map((o) => o.value)}
+          itemComponent={(props) => (
+            <SelectItem item={props.item}>
+              {OCR_ENGINE_MODE_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+            </SelectItem>
+          )}
+        >
+          <SelectTrigger class="w-[130px]">
+            <SelectValue<OcrEngineMode>>
+              {(state) =>
+                OCR_ENGINE_MODE_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+              }
+            </SelectValue>
+          </SelectTrigger>
+          <SelectContent />
+        </Select>
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularFilter /> Only characters
+        </div>
+        <input
+          type="text"
+          placeholder="Any"
+          value={whitelist() ?? ""}
+          onChange={(e) => setWhitelist(e.currentTarget.value)}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularFilter /> Never characters
+        </div>
+        <input
+          type="text"
+          placeholder="None"
+          value={blacklist() ?? ""}
+          onChange={(e) => setBlacklist(e.currentTarget.value)}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <Switch
+        class="flex justify-between items-center h-[30px]"
+        checked={preserveSpaces() ?? false}
+        onChange={(value) => setPreserveSpaces(value)}
+      >
+        <SwitchLabel class="text-sm font-light flex gap-2 items-center">
+          <BiRegularSpaceBar />
+          Preserve spaces between words
+        </SwitchLabel>
+        <SwitchControl variant={"product"}>
+          <SwitchThumb />
+        </SwitchControl>
+      </Switch>
+
+      <p class="text-xs text-muted-foreground mt-1">
+        Applies to Tesseract. Character filters always use Tesseract, automatic layout reads short,
+        wide selections as a single line.
+      </p>
+    </div>
+  );
+}
@@ -0,0 +1,88 @@
+import { BiRegularGlobe, BiRegularKey, BiRegularServer, BiRegularWorld } from "solid-icons/bi";
+import { createMemo } from "solid-js";
+
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+import { SnappitStore } from "@/shared/store";
+
+const INPUT_CLASS =
+  "w-[180px] h-7 px-2 text-sm rounded-md border border-input bg-background focus:outline-none focus:ring-1 focus:ring-ring";
+
+export function TranslationSettings() {
+  const keys = SNAPPIT_CONSTS.store.keys;
+  const defaults = SNAPPIT_CONSTS.defaults;
+  const [url, setUrl, , urlReady] = SnappitStore.createValue<string>(keys.translate_url);
+  const [sourceLang, setSourceLang, , sourceLangReady] = SnappitStore.createValue<string>(
+    keys.translate_source_lang,
+  );
+  const [targetLang, setTargetLang, , targetLangReady] = SnappitStore.createValue<string>(
+    keys.translate_target_lang,
+  );
+  const [apiKey, setApiKey, , apiKeyReady] = SnappitStore.createValue<string>(
+    keys.translate_api_key,
+  );
+
+  const isReady = createMemo(
+    () => urlReady() && sourceLangReady() && targetLangReady() && apiKeyReady(),
+  );
+
+  return (
+    <div class="rounded-lg p-3 bg-card mb-3" classList={{ "opacity-50": !isReady() }}>
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularServer /> Translation server
+        </div>
+        <input
+          type="url"
+          placeholder={defaults.translate_url}
+          value={url() ?? ""}
+          onChange={(e) => setUrl(e.currentTarget.value.trim())}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularGlobe /> Translate from
+        </div>
+        <input
+          type="text"
+          placeholder="auto"
+          value={sourceLang() ?? ""}
+          onChange={(e) => setSourceLang(e.currentTarget.value.trim())}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularWorld /> Translate into
+        </div>
+        <input
+          type="text"
+          placeholder={defaults.translate_target_lang}
+          value={targetLang() ?? ""}
+          onChange={(e) => setTargetLang(e.currentTarget.value.trim())}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light flex gap-2 items-center">
+          <BiRegularKey /> API key
+        </div>
+        <input
+          type="password"
+          placeholder="None"
+          value={apiKey() ?? ""}
+          onChange={(e) => setApiKey(e.currentTarget.value.trim())}
+          class={INPUT_CLASS}
+        />
+      </div>
+
+      <p class="text-xs text-muted-foreground mt-1">
+        Any LibreTranslate-compatible server. Languages are codes like en, ja or ko. Without a
+        source language the server detects it, unless automatic recognition found the script.
+      </p>
+    </div>
+  );
+}
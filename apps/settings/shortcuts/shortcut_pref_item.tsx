@@ -1,7 +1,11 @@
 import {
   BiRegularQrScan,
   BiRegularRadioCircleMarked,
+  BiRegularRepeat,
   BiRegularReset,
+  BiRegularTable,
+  BiRegularWorld,
+  BiSolidCamera,
   BiSolidCopy,
   BiSolidEyedropper,
   BiSolidRuler,
@@ -19,8 +23,12 @@ import {
   DEFAULT_SHORTCUTS,
   DIGITAL_RULER_SHORTCUT_KEY,
   QR_SHORTCUT_KEY,
+  REPEAT_REGION_SHORTCUT_KEY,
+  SCREENSHOT_SHORTCUT_KEY,
   ShortcutKeys,
   ShortcutsApi,
+  TABLE_SHORTCUT_KEY,
+  TRANSLATOR_SHORTCUT_KEY,
 } from "@/shared/tauri/shortcuts_api";
 import { SnappitOverlayTarget } from "@/shared/tauri/snap_overlay_target";
 import { Button } from "@/shared/ui/button";
@@ -62,6 +70,30 @@ export const TOOL_SHORTCUTS: ShortcutPreferenceItem[] = [
     target: "qr_scanner",
     Icon: BiRegularQrScan,
   },
+  {
+    label: "Table Scanner",
+    storeKey: TABLE_SHORTCUT_KEY,
+    target: "table_scanner",
+    Icon: BiRegularTable,
+  },
+  {
+    label: "Translator",
+    storeKey: TRANSLATOR_SHORTCUT_KEY,
+    target: "translator",
+    Icon: BiRegularWorld,
+  },
+  {
+    label: "Screenshot",
+    storeKey: SCREENSHOT_SHORTCUT_KEY,
+    target: "screenshot",
+    Icon: BiSolidCamera,
+  },
+  {
+    label: "Repeat Last Region",
+    storeKey: REPEAT_REGION_SHORTCUT_KEY,
+    target: "repeat_region",
+    Icon: BiRegularRepeat,
+  },
 ];
 
 type ShortcutPreferenceItemProps = {
@@ -0,0 +1,232 @@
+import { createEventListener } from "@solid-primitives/event-listener";
+import {
+  BiRegularCheck,
+  BiRegularHighlight,
+  BiRegularRectangle,
+  BiRegularRightArrowAlt,
+  BiRegularUndo,
+} from "solid-icons/bi";
+import { createMemo, createSignal, For, Match, Show, Switch } from "solid-js";
+
+import { cn } from "@/shared/libs/cn";
+import {
+  RegionCaptureParams,
+  SnappitAnnotation,
+  SnappitAnnotationKind,
+} from "@/shared/tauri/region_capture_api";
+import { Button } from "@/shared/ui/button";
+import { OverlayPortal } from "@/shared/ui/overlay";
+import { ToggleGroup, ToggleGroupItem } from "@/shared/ui/toggle_group";
+import { tooltip } from "@/shared/ui/tooltip";
+
+void tooltip;
+
+type Point = { x: number; y: number };
+
+// Kept in sync with the colors and sizes in snappit_annotation.rs
+const STROKE_COLOR = "#ff3b30";
+const HIGHLIGHT_COLOR = "#ffd60a";
+const HIGHLIGHT_OPACITY = 0.4;
+const STROKE_WIDTH = 3;
+const ARROW_HEAD_LENGTH = 14;
+const ARROW_HEAD_ANGLE = (28 * Math.PI) / 180;
+
+/** Shapes smaller than this in both directions are dropped as stray clicks */
+const MIN_SIZE = 3;
+
+const TOOLBAR_GAP = 8;
+const TOOLBAR_HEIGHT = 36;
+
+function annotationFrom(kind: SnappitAnnotationKind, from: Point, to: Point): SnappitAnnotation {
+  if (kind === "arrow") {
+    return { kind, x1: from.x, y1: from.y, x2: to.x, y2: to.y };
+  }
+
+  return {
+    kind,
+    x: Math.min(from.x, to.x),
+    y: Math.min(from.y, to.y),
+    width: Math.abs(to.x - from.x),
+    height: Math.abs(to.y - from.y),
+  };
+}
+
+function arrowHead(annotation: Extract<SnappitAnnotation, { kind: "arrow" }>) {
+  const angle = Math.atan2(annotation.y1 - annotation.y2, annotation.x1 - annotation.x2);
+
+  const [left, right] = [angle + ARROW_HEAD_ANGLE, angle - ARROW_HEAD_ANGLE].map(
+    (side) =>
+      `${annotation.x2 + ARROW_HEAD_LENGTH * Math.cos(side)},` +
+      `${annotation.y2 + ARROW_HEAD_LENGTH * Math.sin(side)}`,
+  );
+
+  return `${left} ${annotation.x2},${annotation.y2} ${right}`;
+}
+
+function AnnotationShape(props: { annotation: SnappitAnnotation }) {
+  return (
+    <Switch>
+      <Match when={props.annotation.kind === "rect" && props.annotation}>
+        {(rect) => (
+          <rect
+            x={rect().x}
+            y={rect().y}
+            width={rect().width}
+            height={rect().height}
+            fill="none"
+            stroke={STROKE_COLOR}
+            stroke-width={STROKE_WIDTH}
+            stroke-linejoin="round"
+          />
+        )}
+      </Match>
+      <Match when={props.annotation.kind === "highlight" && props.annotation}>
+        {(highlight) => (
+          <rect
+            x={highlight().x}
+            y={highlight().y}
+            width={highlight().width}
+            height={highlight().height}
+            fill={HIGHLIGHT_COLOR}
+            fill-opacity={HIGHLIGHT_OPACITY}
+          />
+        )}
+      </Match>
+      <Match when={props.annotation.kind === "arrow" && props.annotation}>
+        {(arrow) => (
+          <g stroke={STROKE_COLOR} stroke-width={STROKE_WIDTH} stroke-linecap="round" fill="none">
+            <line x1={arrow().x1} y1={arrow().y1} x2={arrow().x2} y2={arrow().y2} />
+            <polyline points={arrowHead(arrow())} stroke-linejoin="round" />
+          </g>
+        )}
+      </Match>
+    </Switch>
+  );
+}
+
+export type annotationEditorProps = {
+  selection: RegionCaptureParams;
+  onSave: (annotations: SnappitAnnotation[]) => unknown;
+};
+
+/** Draws rectangles, arrows and highlights over a selected region before it is saved */
+export function AnnotationEditor(props: annotationEditorProps) {
+  const [kind, setKind] = createSignal<SnappitAnnotationKind>("arrow");
+  const [annotations, setAnnotations] = createSignal<SnappitAnnotation[]>([]);
+  const [dragStart, setDragStart] = createSignal<Point | null>(null);
+  const [draft, setDraft] = createSignal<SnappitAnnotation | null>(null);
+
+  const toolbarPos = createMemo(() => {
+    const below = props.selection.y + props.selection.height + TOOLBAR_GAP;
+
+    return {
+      left: `${props.selection.x}px`,
+      top: `${Math.min(below, window.innerHeight - TOOLBAR_HEIGHT - TOOLBAR_GAP)}px`,
+    };
+  });
+
+  const pointAt = (event: MouseEvent): Point => ({
+    x: event.clientX - props.selection.x,
+    y: event.clientY - props.selection.y,
+  });
+
+  const undo = () => setAnnotations((current) => current.slice(0, -1));
+
+  const save = () => props.onSave(annotations());
+
+  const onMouseDown = (event: MouseEvent) => {
+    if (event.button !== 0) return;
+
+    event.preventDefault();
+    const point = pointAt(event);
+    setDragStart(point);
+    setDraft(annotationFrom(kind(), point, point));
+  };
+
+  createEventListener(window, "mousemove", (event: MouseEvent) => {
+    const start = dragStart();
+    if (!start) return;
+
+    setDraft(annotationFrom(kind(), start, pointAt(event)));
+  });
+
+  createEventListener(window, "mouseup", (event: MouseEvent) => {
+    const start = dragStart();
+    if (!start || event.button !== 0) return;
+
+    const end = pointAt(event);
+    if (Math.abs(end.x - start.x) >= MIN_SIZE || Math.abs(end.y - start.y) >= MIN_SIZE) {
+      setAnnotations((current) => [...current, annotationFrom(kind(), start, end)]);
+    }
+    setDragStart(null);
+    setDraft(null);
+  });
+
+  createEventListener(window, "keydown", (event: KeyboardEvent) => {
+    if (event.key === "Enter") {
+      event.preventDefault();
+      save();
+    } else if ((event.metaKey || event.ctrlKey) && event.key.toLowerCase() === "z") {
+      event.preventDefault();
+      undo();
+    }
+  });
+
+  return (
+    <div class="absolute inset-0 cursor-crosshair" onMouseDown={onMouseDown}>
+      <OverlayPortal pos={() => props.selection} innerClass="border-1 border-white">
+        <svg class="absolute inset-0 w-full h-full overflow-visible">
+          <For each={annotations()}>
+            {(annotation) => <AnnotationShape annotation={annotation} />}
+          </For>
+          <Show when={draft()}>
+            {(annotation) => <AnnotationShape annotation={annotation()} />}
+          </Show>
+        </svg>
+      </OverlayPortal>
+
+      <div
+        class={cn(
+          "absolute z-10 bg-card/85 rounded-full flex items-center gap-1 pr-1",
+          "select-none cursor-default",
+        )}
+        style={toolbarPos()}
+        onMouseDown={(event) => event.stopPropagation()}
+      >
+        <ToggleGroup value={kind()} color={"product"} variant={"ghost"}>
+          <div use:tooltip={"Rectangle"}>
+            <ToggleGroupItem value="rect" onClick={() => setKind("rect")}>
+              <BiRegularRectangle />
+            </ToggleGroupItem>
+          </div>
+          <div use:tooltip={"Arrow"}>
+            <ToggleGroupItem value="arrow" onClick={() => setKind("arrow")}>
+              <BiRegularRightArrowAlt />
+            </ToggleGroupItem>
+          </div>
+          <div use:tooltip={"Highlight"}>
+            <ToggleGroupItem value="highlight" onClick={() => setKind("highlight")}>
+              <BiRegularHighlight />
+            </ToggleGroupItem>
+          </div>
+        </ToggleGroup>
+        <div use:tooltip={"Undo"}>
+          <Button
+            variant="ghost"
+            size="icon"
+            class="rounded-full"
+            disabled={annotations().length === 0}
+            onClick={undo}
+          >
+            <BiRegularUndo />
+          </Button>
+        </div>
+        <div use:tooltip={"Save"}>
+          <Button variant="product" size="icon" class="rounded-full" onClick={save}>
+            <BiRegularCheck />
+          </Button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1 @@
+export * from "./annotation_editor";
@@ -0,0 +1,20 @@
+import { SnappitCaptureMode } from "@/shared/tauri/region_capture_api";
+
+/**
+ * Keys that capture without a selection while the capture tool is active:
+ * F for the screen, A for all screens, W for the window under the cursor
+ * and Enter for the window that was focused before the overlay opened.
+ */
+const CAPTURE_MODE_KEYS: Record<string, SnappitCaptureMode> = {
+  f: "monitor",
+  a: "all_monitors",
+  w: "hovered_window",
+  Enter: "focused_window",
+};
+
+export function captureModeForKey(event: KeyboardEvent): SnappitCaptureMode | undefined {
+  if (event.altKey || event.ctrlKey || event.metaKey || event.shiftKey) return;
+
+  const key = event.key.length === 1 ? event.key.toLowerCase() : event.key;
+  return CAPTURE_MODE_KEYS[key];
+}
@@ -2,6 +2,7 @@ import { createEventListener } from "@solid-primitives/event-listener";
 import { createMemo, createSignal } from "solid-js";
 
 import { RegionCaptureParams } from "@/shared/tauri/region_capture_api";
+import { SnapOverlayApi } from "@/shared/tauri/snap_overlay_api";
 
 const DEFAULT_POS = { x: 0, y: 0 };
 
@@ -20,6 +21,7 @@ export function createSelection(onSelected?: (selection: RegionCaptureParams) =>
   const onSelectionStart = (e: MouseEvent) => {
     if (e.button === 0) {
       setIsSelecting(true);
+      void SnapOverlayApi.setSelecting(true);
       setStartPos({ x: e.clientX, y: e.clientY });
       setCurrentPos({ x: e.clientX, y: e.clientY });
     }
@@ -37,6 +39,7 @@ export function createSelection(onSelected?: (selection: RegionCaptureParams) =>
 
     if (e.button === 0) {
       setIsSelecting(false);
+      void SnapOverlayApi.setSelecting(false);
       if (onSelected) {
         onSelected(selection());
       }
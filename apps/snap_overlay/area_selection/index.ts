@@ -1,3 +1,4 @@
 export * from "./area_selection";
 export * from "./create_selection";
 export * from "./on_selected";
+export * from "./capture_mode_keys";
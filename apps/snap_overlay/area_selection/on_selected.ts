@@ -4,18 +4,32 @@ import { load } from "@tauri-apps/plugin-store";
 import { onColorRecognized } from "@/apps/snap_overlay/color_dropper/on_recognized";
 import { onScanSuccess } from "@/apps/snap_overlay/qr-scan";
 import { SNAPPIT_CONSTS } from "@/shared/constants";
-import { CaptureHistory } from "@/shared/history";
 import { ColorFormat, DEFAULT_COLOR_FORMAT, formatColor } from "@/shared/libs/color_format";
 import { NotificationCenter } from "@/shared/notifications";
-import { RegionCaptureApi, RegionCaptureParams } from "@/shared/tauri/region_capture_api";
+import {
+  RegionCaptureApi,
+  RegionCaptureParams,
+  SnappitAnnotation,
+  SnappitCaptureMode,
+} from "@/shared/tauri/region_capture_api";
 import { ColorInfo } from "@/shared/tauri/screen_capture_api";
 import { SnapOverlayApi } from "@/shared/tauri/snap_overlay_api";
+import {
+  joinBarcodeValues,
+  SnappitOcrPayload,
+  SnappitResponse,
+  SnappitTranslationPayload,
+} from "@/shared/tauri/snappit_res";
 
-export async function onTextRecognized(text: string, engine: "vision" | "tesseract") {
-  if (text) {
-    await writeText(text);
+export async function onTextRecognized(result: SnappitOcrPayload) {
+  const text = result.value;
+  if (!text) return;
+
+  await writeText(text);
+  if (result.low_confidence) {
+    await NotificationCenter.notifyOcrLowConfidence(text);
+  } else {
     await NotificationCenter.notifyOcr(text);
-    await CaptureHistory.addOcr({ text, engine });
   }
 }
 
@@ -27,16 +41,54 @@ async function getFormattedColor(color: ColorInfo): Promise<string> {
   return formatColor(color.rgb, color.hex, format);
 }
 
-export async function onAreaSelected(selection: RegionCaptureParams) {
+export async function onTableRecognized(table: string) {
+  if (table) {
+    await writeText(table);
+    await NotificationCenter.notifyTable(table);
+  }
+}
+
+export async function onTableAreaSelected(selection: RegionCaptureParams) {
   SnapOverlayApi.hide();
 
-  const res = await RegionCaptureApi.onCapture(selection);
+  const res = await RegionCaptureApi.scanRegionTable(selection);
+  return onTableRecognized(res.payload.value);
+}
+
+export async function onTranslationRecognized(translation: SnappitTranslationPayload) {
+  if (translation.translated_text) {
+    await writeText(translation.translated_text);
+    await NotificationCenter.notifyTranslation(translation.translated_text);
+  }
+}
+
+export async function onTranslateAreaSelected(selection: RegionCaptureParams) {
+  SnapOverlayApi.hide();
+
+  const res = await RegionCaptureApi.translateRegion(selection);
+  return onTranslationRecognized(res.payload);
+}
+
+export async function onScreenshotAreaSelected(
+  selection: RegionCaptureParams,
+  annotations: SnappitAnnotation[],
+) {
+  SnapOverlayApi.hide();
+
+  const path = await RegionCaptureApi.saveScreenshot(selection, annotations);
+  return NotificationCenter.notifyScreenshot(path);
+}
 
+async function onCaptureResponse(res: SnappitResponse) {
   switch (res.kind) {
     case "qr":
-      return onScanSuccess(res.payload);
+      return onScanSuccess(joinBarcodeValues(res.payload));
     case "ocr":
-      return onTextRecognized(res.payload.value, res.payload.ocr);
+      return onTextRecognized(res.payload);
+    case "table":
+      return onTableRecognized(res.payload.value);
+    case "translation":
+      return onTranslationRecognized(res.payload);
     case "dropper": {
       const formattedColor = await getFormattedColor(res.payload);
       return onColorRecognized(res.payload, formattedColor);
@@ -45,3 +97,23 @@ export async function onAreaSelected(selection: RegionCaptureParams) {
       return;
   }
 }
+
+export async function onAreaSelected(selection: RegionCaptureParams) {
+  SnapOverlayApi.hide();
+
+  const res = await RegionCaptureApi.onCapture(selection);
+  return onCaptureResponse(res);
+}
+
+/** Captures the last region again, without the overlay being shown */
+export async function onRegionRepeated() {
+  const res = await RegionCaptureApi.repeatLastRegion();
+  return onCaptureResponse(res);
+}
+
+export async function onCaptureModeSelected(mode: SnappitCaptureMode) {
+  SnapOverlayApi.hide();
+
+  const res = await RegionCaptureApi.onCaptureMode(mode);
+  return onCaptureResponse(res);
+}
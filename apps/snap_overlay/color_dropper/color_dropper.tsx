@@ -36,11 +36,19 @@ export function ColorDropper() {
     event.preventDefault();
     event.stopPropagation();
 
-    if (colorInfo() && formattedColor()) {
+    if (colorInfo()) {
       try {
         await consumeToolUse();
         await updateTrayLicenseStatus();
-        onColorRecognized(colorInfo()!, formattedColor()!);
+        const color = await ColorDropperApi.captureColorAtCursor(
+          event.clientX,
+          event.clientY,
+          true,
+        );
+        onColorRecognized(
+          color,
+          formatColor(color.rgb, color.hex, colorFormat() ?? DEFAULT_COLOR_FORMAT),
+        );
         await SnapOverlayApi.hide();
       } catch (err) {
         console.error(err);
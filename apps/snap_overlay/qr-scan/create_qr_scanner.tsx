@@ -5,6 +5,7 @@ import { createEffect, createMemo, createSignal, untrack } from "solid-js";
 
 import { clamp } from "@/shared/libs/clamp";
 import { RegionCaptureApi, RegionCaptureParams } from "@/shared/tauri/region_capture_api";
+import { joinBarcodeValues } from "@/shared/tauri/snappit_res";
 
 import {
   DEFAULT_QR_SIZE,
@@ -74,17 +75,19 @@ export function createQrScanner(options: createQrScannerOptions): qrScannerInsta
 
       const result = await RegionCaptureApi.scanRegionQr(params);
 
-      if (!result.payload) {
+      if (!result.payload.length) {
         await options.onScanFailure?.();
         return;
       }
 
-      if (payloadCache.isRecent(result.payload, now)) {
+      const content = joinBarcodeValues(result.payload);
+
+      if (payloadCache.isRecent(content, now)) {
         return;
       }
 
-      payloadCache.remember(result.payload, now);
-      await options.onScanSuccess(result.payload);
+      payloadCache.remember(content, now);
+      await options.onScanSuccess(content);
       return result;
     } finally {
       setIsScanning(false);
@@ -7,6 +7,6 @@ export async function onRulerSuccess(body: string) {
   if (body) {
     await writeText(body);
     await NotificationCenter.notifyRuler(body);
-    await CaptureHistory.addRuler({ value: body });
+    await CaptureHistory.addRuler(body);
   }
 }
@@ -1,4 +1,5 @@
 import { SnapOverlayApi } from "@shared/tauri/snap_overlay_api";
+import { createEventListener } from "@solid-primitives/event-listener";
 import { UnlistenFn } from "@tauri-apps/api/event";
 import {
   Accessor,
@@ -10,14 +11,28 @@ import {
   Show,
 } from "solid-js";
 
-import { AreaSelection, createSelection, onAreaSelected } from "@/apps/snap_overlay/area_selection";
+import { AnnotationEditor } from "@/apps/snap_overlay/annotation";
+import {
+  AreaSelection,
+  captureModeForKey,
+  createSelection,
+  onAreaSelected,
+  onCaptureModeSelected,
+  onScreenshotAreaSelected,
+  onTableAreaSelected,
+  onTranslateAreaSelected,
+} from "@/apps/snap_overlay/area_selection";
 import { ColorDropper } from "@/apps/snap_overlay/color_dropper";
 import { createQrScanner, onScanSuccess, QrScanner } from "@/apps/snap_overlay/qr-scan";
 import { Ruler } from "@/apps/snap_overlay/ruler";
 import { SNAPPIT_CONSTS } from "@/shared/constants";
 import { cn } from "@/shared/libs/cn";
 import { SnappitStore } from "@/shared/store";
-import { RegionCaptureParams } from "@/shared/tauri/region_capture_api";
+import {
+  RegionCaptureApi,
+  RegionCaptureParams,
+  SnappitAnnotation,
+} from "@/shared/tauri/region_capture_api";
 import { SnappitOverlayTarget } from "@/shared/tauri/snap_overlay_target";
 import { Overlay, StaticBackdrop } from "@/shared/ui/overlay/overlay";
 
@@ -32,11 +47,20 @@ function SnapOverlay(props: snapOverlayProps) {
   const [cursorStyle, setCursorStyle] = createSignal("cursor-default");
   const [activeTool, setActiveTool] = createSignal<SnappitOverlayTarget>("capture");
   const [mouseOnTools, setMouseOnTools] = createSignal<boolean>(false);
+  const [frozenFrame, setFrozenFrame] = createSignal<string>();
+  // Screenshot selection being annotated before it's saved
+  const [annotating, setAnnotating] = createSignal<RegionCaptureParams>();
   const [toolsEnabled] = SnappitStore.createValue<boolean>(SNAPPIT_CONSTS.store.keys.tools_panel);
   const isCaptureTool = createMemo(() => activeTool() === "capture");
   const isRulerTool = createMemo(() => activeTool() === "digital_ruler");
   const isQrTool = createMemo(() => activeTool() === "qr_scanner");
   const isColorDropperTool = createMemo(() => activeTool() === "color_dropper");
+  const isTableTool = createMemo(() => activeTool() === "table_scanner");
+  const isTranslatorTool = createMemo(() => activeTool() === "translator");
+  const isScreenshotTool = createMemo(() => activeTool() === "screenshot");
+  const isSelectionTool = createMemo(
+    () => isCaptureTool() || isTableTool() || isTranslatorTool() || isScreenshotTool(),
+  );
 
   const showQrScanner = createMemo(() => isQrTool() && !mouseOnTools() && qrScanner.frame());
   const showColorDropper = createMemo(() => isColorDropperTool() && !mouseOnTools());
@@ -44,18 +68,39 @@ function SnapOverlay(props: snapOverlayProps) {
 
   const [selection, isSelecting, onSelectionStart] = createSelection(
     async (selection: RegionCaptureParams) => {
+      if (isScreenshotTool()) {
+        setAnnotating(selection);
+        return;
+      }
+
       setCursorStyle("cursor-none");
-      await onAreaSelected(selection);
+      if (isTableTool()) {
+        await onTableAreaSelected(selection);
+      } else if (isTranslatorTool()) {
+        await onTranslateAreaSelected(selection);
+      } else {
+        await onAreaSelected(selection);
+      }
       setCursorStyle("cursor-default");
     },
   );
 
+  const onAnnotationsSaved = async (annotations: SnappitAnnotation[]) => {
+    const selection = annotating();
+    if (!selection) return;
+
+    setCursorStyle("cursor-none");
+    await onScreenshotAreaSelected(selection, annotations);
+    setAnnotating(undefined);
+    setCursorStyle("cursor-crosshair");
+  };
+
   const showBackdrop = createMemo(() => {
-    if (isColorDropperTool() || isRulerTool()) {
+    if (isColorDropperTool() || isRulerTool() || annotating()) {
       return false;
     }
 
-    if (isCaptureTool()) {
+    if (isSelectionTool()) {
       return !isSelecting();
     }
 
@@ -67,8 +112,20 @@ function SnapOverlay(props: snapOverlayProps) {
     onScanSuccess,
   });
 
+  createEventListener(window, "keydown", async (event: KeyboardEvent) => {
+    if (!isCaptureTool() || isSelecting()) return;
+
+    const mode = captureModeForKey(event);
+    if (!mode) return;
+
+    event.preventDefault();
+    setCursorStyle("cursor-none");
+    await onCaptureModeSelected(mode);
+    setCursorStyle("cursor-default");
+  });
+
   const onOverlayMouseDown = (event: MouseEvent) => {
-    if (activeTool() === "capture") {
+    if (isSelectionTool() && !annotating()) {
       onSelectionStart(event);
     }
   };
@@ -77,6 +134,21 @@ function SnapOverlay(props: snapOverlayProps) {
     await SnappitStore.sync();
   });
 
+  // After a delayed capture countdown the overlay selects on the frozen screen
+  onMount(async () => {
+    const frame = await RegionCaptureApi.getFrozenFrame();
+    if (frame) {
+      setFrozenFrame(URL.createObjectURL(frame));
+    }
+  });
+
+  onCleanup(() => {
+    const frame = frozenFrame();
+    if (frame) {
+      URL.revokeObjectURL(frame);
+    }
+  });
+
   onMount(async () => {
     const overlay = await SnapOverlayApi.get();
     unregisterFocus = await overlay?.onFocusChanged((e) => {
@@ -94,12 +166,13 @@ function SnapOverlay(props: snapOverlayProps) {
 
   createEffect(() => {
     if (props?.target && props.target() !== "none") {
+      setAnnotating(undefined);
       setActiveTool(props.target());
     }
   });
 
   createEffect(() => {
-    if (isCaptureTool()) {
+    if (isSelectionTool()) {
       setCursorStyle("cursor-crosshair");
     } else {
       setCursorStyle("cursor-default");
@@ -115,6 +188,16 @@ function SnapOverlay(props: snapOverlayProps) {
   return (
     <Overlay>
       <div onMouseDown={onOverlayMouseDown} class={cn("absolute inset-0 ", cursorStyle())}>
+        <Show when={frozenFrame()}>
+          {(frame) => (
+            <img
+              src={frame()}
+              class="absolute inset-0 h-full w-full pointer-events-none select-none"
+              draggable={false}
+            />
+          )}
+        </Show>
+
         <Show when={showBackdrop()}>
           <StaticBackdrop />
         </Show>
@@ -132,9 +215,13 @@ function SnapOverlay(props: snapOverlayProps) {
         <Show when={showRuler()}>
           <Ruler />
         </Show>
+
+        <Show when={annotating()}>
+          {(selection) => <AnnotationEditor selection={selection()} onSave={onAnnotationsSaved} />}
+        </Show>
       </div>
 
-      <Show when={toolsEnabled() ?? true}>
+      <Show when={(toolsEnabled() ?? true) && !annotating()}>
         <Tools
           class={cn(
             "transition-[opacity,transform] duration-200 ease-in-out pointer-events-auto",
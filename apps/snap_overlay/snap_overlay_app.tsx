@@ -1,14 +1,29 @@
-import { Show } from "solid-js";
+import { UnlistenFn } from "@tauri-apps/api/event";
+import { onCleanup, onMount, Show } from "solid-js";
 
+import { onRegionRepeated } from "@/apps/snap_overlay/area_selection";
 import { createOverlayVisible } from "@/shared/libs/overlay_visible";
+import { SnapOverlayApi } from "@/shared/tauri/snap_overlay_api";
 import { Theme } from "@/shared/theme";
 
 import SnapOverlay from "./snap_overlay";
 
 function SnapOverlayApp() {
+  let unlistenRepeat: UnlistenFn | undefined;
   Theme.create();
   const [visible, target] = createOverlayVisible();
 
+  // The last region is captured again while the overlay stays hidden
+  onMount(async () => {
+    unlistenRepeat = await SnapOverlayApi.onRepeatRegion(() => onRegionRepeated());
+  });
+
+  onCleanup(() => {
+    if (unlistenRepeat) {
+      unlistenRepeat();
+    }
+  });
+
   return (
     <Show when={visible()}>
       <SnapOverlay target={target} />
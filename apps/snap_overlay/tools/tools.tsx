@@ -1,5 +1,13 @@
 import { UnlistenFn } from "@tauri-apps/api/event";
-import { BiRegularQrScan, BiSolidCopy, BiSolidEyedropper, BiSolidRuler } from "solid-icons/bi";
+import {
+  BiRegularQrScan,
+  BiRegularTable,
+  BiRegularWorld,
+  BiSolidCamera,
+  BiSolidCopy,
+  BiSolidEyedropper,
+  BiSolidRuler,
+} from "solid-icons/bi";
 import { JSX, onCleanup, splitProps } from "solid-js";
 
 import { cn } from "@/shared/libs/cn";
@@ -64,6 +72,24 @@ export function Tools(props: toolsProps) {
             <BiRegularQrScan />
           </ToggleGroupItem>
         </div>
+        <div use:tooltip={"Table Scanner"}>
+          <ToggleGroupItem
+            value="table_scanner"
+            onClick={() => local.onValueChange("table_scanner")}
+          >
+            <BiRegularTable />
+          </ToggleGroupItem>
+        </div>
+        <div use:tooltip={"Translator"}>
+          <ToggleGroupItem value="translator" onClick={() => local.onValueChange("translator")}>
+            <BiRegularWorld />
+          </ToggleGroupItem>
+        </div>
+        <div use:tooltip={"Screenshot"}>
+          <ToggleGroupItem value="screenshot" onClick={() => local.onValueChange("screenshot")}>
+            <BiSolidCamera />
+          </ToggleGroupItem>
+        </div>
       </ToggleGroup>
     </div>
   );
@@ -1,7 +1,8 @@
 // Base interface for all history items
 interface BaseCaptureHistoryItem {
-  id: string;
+  id: number;
   timestamp: number;
+  has_thumbnail: boolean;
 }
 
 // OCR payload
@@ -20,11 +21,10 @@ export interface QrPayload {
   content: string;
 }
 
-// Dropper payload
+// Dropper payload, formatted with the preferred color format when displayed
 export interface DropperPayload {
   hex: string;
   rgb: [number, number, number];
-  formattedColor: string;
 }
 
 // Typed history items
@@ -60,3 +60,17 @@ export type PayloadByType<T extends CaptureHistoryItem["type"]> = Extract<
   CaptureHistoryItem,
   { type: T }
 >["payload"];
+
+export interface CaptureHistoryQuery {
+  search?: string;
+  kinds?: CaptureHistoryItem["type"][];
+  offset?: number;
+  limit?: number;
+}
+
+export interface CaptureHistoryPage {
+  items: CaptureHistoryItem[];
+  total: number;
+}
+
+export type ExportFormat = "json" | "csv";
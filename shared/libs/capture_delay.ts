@@ -0,0 +1,9 @@
+// A delay of 0 shows the overlay as soon as the hotkey is pressed
+export const CAPTURE_DELAY_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: "Off" },
+  { value: 3, label: "3 seconds" },
+  { value: 5, label: "5 seconds" },
+  { value: 10, label: "10 seconds" },
+];
+
+export const DEFAULT_CAPTURE_DELAY = 0;
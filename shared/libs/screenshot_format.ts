@@ -0,0 +1,17 @@
+export type ScreenshotFormat = "png" | "jpeg" | "webp";
+
+export const DEFAULT_SCREENSHOT_FORMAT: ScreenshotFormat = "png";
+
+export const SCREENSHOT_FORMAT_OPTIONS: { value: ScreenshotFormat; label: string }[] = [
+  { value: "png", label: "PNG" },
+  { value: "jpeg", label: "JPEG" },
+  { value: "webp", label: "WebP" },
+];
+
+// Quality only applies to JPEG, PNG and WebP screenshots are saved lossless
+export const SCREENSHOT_QUALITY_OPTIONS: { value: number; label: string }[] = [
+  { value: 60, label: "Low" },
+  { value: 75, label: "Medium" },
+  { value: 90, label: "High" },
+  { value: 100, label: "Maximum" },
+];
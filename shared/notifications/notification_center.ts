@@ -13,6 +13,22 @@ export abstract class NotificationCenter {
     return await NotificationApi.show({ value: body, target: "capture" });
   }
 
+  static async notifyOcrLowConfidence(body: string) {
+    return await NotificationApi.show({ value: body, data: "low_confidence", target: "capture" });
+  }
+
+  static async notifyTable(body: string) {
+    return await NotificationApi.show({ value: body, target: "table_scanner" });
+  }
+
+  static async notifyTranslation(body: string) {
+    return await NotificationApi.show({ value: body, target: "translator" });
+  }
+
+  static async notifyScreenshot(path: string) {
+    return await NotificationApi.show({ value: path, target: "screenshot" });
+  }
+
   static async notifyDropper(body: string, hexColor: string) {
     return await NotificationApi.show({ value: body, data: hexColor, target: "color_dropper" });
   }
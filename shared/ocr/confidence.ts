@@ -0,0 +1,12 @@
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+
+// Off until the user picks a minimum
+export const DEFAULT_MIN_CONFIDENCE: number = SNAPPIT_CONSTS.defaults.ocr_min_confidence ?? 0;
+
+// A minimum of 0 turns the low confidence warning off
+export const MIN_CONFIDENCE_OPTIONS: { value: number; label: string }[] = [
+  { value: 0, label: "Off" },
+  { value: 40, label: "Below 40%" },
+  { value: 60, label: "Below 60%" },
+  { value: 80, label: "Below 80%" },
+];
@@ -1,3 +1,9 @@
 export * from "./recognition_language";
 export * from "./system_language_button";
 export * from "./tesseract_language_list";
+export * from "./preprocess";
+export * from "./tesseract_options";
+export * from "./line_break_mode";
+export * from "./table_format";
+export * from "./math_mode";
+export * from "./confidence";
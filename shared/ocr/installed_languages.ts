@@ -1,4 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { createSignal } from "solid-js";
 
 import { Language } from "./recognition_language";
@@ -8,6 +9,28 @@ export interface SystemLanguageInfo {
   name: string;
 }
 
+export interface TessdataImportReport {
+  imported: Language[];
+  skipped: { path: string; reason: string }[];
+}
+
+export type TessdataVariant = "standard" | "fast" | "best";
+
+export interface TessdataProgress {
+  lang: Language;
+  variant: TessdataVariant;
+  state: "downloading" | "verifying" | "done" | "cancelled" | "failed";
+  downloaded: number;
+  total: number | null;
+  error: string | null;
+}
+
+// Emitted by the Rust side while a traineddata file downloads
+export const TESSDATA_PROGRESS_EVENT = "tessdata:progress";
+
+export const [downloadProgress, setDownloadProgress] = createSignal<
+  Map<Language, TessdataProgress>
+>(new Map());
 export const [installedLanguages, setInstalledLanguages] = createSignal<Language[]>([]);
 export const [systemLanguages, setSystemLanguages] = createSignal<Language[]>([]);
 export const [systemLanguagesInfo, setSystemLanguagesInfo] = createSignal<SystemLanguageInfo[]>([]);
@@ -100,6 +123,48 @@ export async function downloadLanguage(lang: Language) {
   await refreshInstalledLanguages();
 }
 
+export async function cancelDownload(lang: Language) {
+  await invoke<boolean>("cancel_tess_download", { lang });
+}
+
+export async function listenTessdataProgress() {
+  return await listen<TessdataProgress>(TESSDATA_PROGRESS_EVENT, (event) => {
+    const progress = event.payload;
+
+    setDownloadProgress((prev) => {
+      const next = new Map(prev);
+      if (progress.state === "downloading" || progress.state === "verifying") {
+        next.set(progress.lang, progress);
+      } else {
+        next.delete(progress.lang);
+      }
+      return next;
+    });
+  });
+}
+
+export function formatDownloadProgress(progress: TessdataProgress | undefined) {
+  if (!progress) return "";
+  if (progress.state === "verifying") return "Verifying";
+  if (!progress.total) return `${(progress.downloaded / 1024 / 1024).toFixed(1)} MB`;
+  return `${Math.floor((progress.downloaded / progress.total) * 100)}%`;
+}
+
+export async function importLanguages(paths: string[]) {
+  const report = await invoke<TessdataImportReport>("import_tess_languages", { paths });
+  await refreshInstalledLanguages();
+  return report;
+}
+
+export async function getSystemTessdataDirs() {
+  try {
+    return await invoke<string[]>("get_system_tessdata_dirs");
+  } catch (e) {
+    console.error("Failed to get system tessdata directories", e);
+    return [];
+  }
+}
+
 export async function deleteLanguage(lang: Language) {
   await invoke("delete_tess_language", { lang });
   await refreshInstalledLanguages();
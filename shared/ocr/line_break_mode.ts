@@ -0,0 +1,16 @@
+export type LineBreakMode = "keep" | "collapse" | "reflow";
+
+export const LINE_BREAK_MODE_OPTIONS: { value: LineBreakMode; label: string }[] = [
+  { value: "keep", label: "Keep" },
+  { value: "collapse", label: "Join" },
+  { value: "reflow", label: "Reflow" },
+];
+
+// Before the mode existed line breaks were a keep/collapse switch
+export function resolveLineBreakMode(
+  mode: LineBreakMode | undefined,
+  keepLineBreaks: boolean | undefined,
+): LineBreakMode {
+  if (mode) return mode;
+  return keepLineBreaks === false ? "collapse" : "keep";
+}
@@ -0,0 +1,9 @@
+export type MathMode = "off" | "unicode" | "latex";
+
+export const DEFAULT_MATH_MODE: MathMode = "off";
+
+export const MATH_MODE_OPTIONS: { value: MathMode; label: string }[] = [
+  { value: "off", label: "Off" },
+  { value: "unicode", label: "Unicode" },
+  { value: "latex", label: "LaTeX" },
+];
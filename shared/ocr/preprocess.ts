@@ -0,0 +1,22 @@
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+
+export type PreprocessStep = "grayscale" | "auto_invert" | "upscale" | "deskew" | "binarize";
+
+export const PREPROCESS_STEP_OPTIONS: { value: PreprocessStep; label: string }[] = [
+  { value: "grayscale", label: "Grayscale" },
+  { value: "auto_invert", label: "Invert dark backgrounds" },
+  { value: "upscale", label: "Upscale small captures" },
+  { value: "deskew", label: "Straighten tilted text" },
+  { value: "binarize", label: "Adaptive binarization" },
+];
+
+export const DEFAULT_PREPROCESS_STEPS = SNAPPIT_CONSTS.defaults.ocr_preprocess as PreprocessStep[];
+
+export function togglePreprocessStep(
+  steps: PreprocessStep[],
+  step: PreprocessStep,
+  enabled: boolean,
+): PreprocessStep[] {
+  const rest = steps.filter((s) => s !== step);
+  return enabled ? [...rest, step] : rest;
+}
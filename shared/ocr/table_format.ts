@@ -0,0 +1,9 @@
+export type TableFormat = "tsv" | "csv" | "markdown";
+
+export const DEFAULT_TABLE_FORMAT: TableFormat = "tsv";
+
+export const TABLE_FORMAT_OPTIONS: { value: TableFormat; label: string }[] = [
+  { value: "tsv", label: "TSV" },
+  { value: "csv", label: "CSV" },
+  { value: "markdown", label: "Markdown" },
+];
@@ -0,0 +1,107 @@
+import { open } from "@tauri-apps/plugin-dialog";
+import { createResource, createSignal, For, Show } from "solid-js";
+
+import { Button } from "@/shared/ui/button";
+
+import {
+  getSystemTessdataDirs,
+  importLanguages,
+  TessdataImportReport,
+} from "./installed_languages";
+
+export function TessdataImport() {
+  const [systemDirs] = createResource(getSystemTessdataDirs, { initialValue: [] });
+  const [importing, setImporting] = createSignal(false);
+  const [report, setReport] = createSignal<TessdataImportReport | null>(null);
+  const [error, setError] = createSignal<string | null>(null);
+
+  const runImport = async (paths: string[]) => {
+    if (!paths.length) return;
+
+    setImporting(true);
+    setError(null);
+    try {
+      setReport(await importLanguages(paths));
+    } catch (e) {
+      setReport(null);
+      setError(String(e));
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  const importFiles = async () => {
+    const selected = await open({
+      multiple: true,
+      filters: [{ name: "Tesseract language", extensions: ["traineddata"] }],
+    });
+    if (selected) await runImport(selected);
+  };
+
+  const importFolder = async () => {
+    const selected = await open({ directory: true });
+    if (selected) await runImport([selected]);
+  };
+
+  return (
+    <div class="flex flex-col gap-2 py-2">
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light">Import from disk</div>
+        <div class="flex gap-2">
+          <Button variant={"muted"} size={"sm"} disabled={importing()} onClick={importFiles}>
+            Files
+          </Button>
+          <Button variant={"muted"} size={"sm"} disabled={importing()} onClick={importFolder}>
+            Folder
+          </Button>
+        </div>
+      </div>
+
+      <For each={systemDirs()}>
+        {(dir) => (
+          <div class="flex justify-between items-center gap-2">
+            <div class="text-xs text-muted-foreground truncate" title={dir}>
+              {dir}
+            </div>
+            <Button
+              variant={"muted"}
+              size={"sm"}
+              disabled={importing()}
+              onClick={() => runImport([dir])}
+            >
+              Import
+            </Button>
+          </div>
+        )}
+      </For>
+
+      <Show when={importing()}>
+        <div class="text-xs text-muted-foreground">Checking language files...</div>
+      </Show>
+
+      <Show when={error()}>
+        <div class="text-xs text-destructive">{error()}</div>
+      </Show>
+
+      <Show when={report()}>
+        {(report) => (
+          <div class="text-xs text-muted-foreground">
+            <Show
+              when={report().imported.length}
+              fallback={<div>No languages were imported</div>}
+            >
+              <div>Imported: {report().imported.join(", ")}</div>
+            </Show>
+            <For each={report().skipped}>
+              {(skip) => (
+                <div class="truncate" title={`${skip.path}: ${skip.reason}`}>
+                  Skipped {skip.path}: {skip.reason}
+                </div>
+              )}
+            </For>
+          </div>
+        )}
+      </Show>
+    </div>
+  );
+}
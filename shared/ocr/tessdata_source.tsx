@@ -0,0 +1,64 @@
+import { SNAPPIT_CONSTS } from "@/shared/constants";
+import { SnappitStore } from "@/shared/store";
+import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from "@/shared/ui/select";
+
+import { TessdataVariant } from "./installed_languages";
+
+const TESSDATA_VARIANT_OPTIONS: { value: TessdataVariant; label: string }[] = [
+  { value: "standard", label: "Standard" },
+  { value: "fast", label: "Fast" },
+  { value: "best", label: "Best" },
+];
+
+const DEFAULT_TESSDATA_VARIANT: TessdataVariant = "standard";
+
+export function TessdataSourceSettings() {
+  const [variant, setVariant] = SnappitStore.createValue<TessdataVariant>(
+    SNAPPIT_CONSTS.store.keys.tessdata_variant,
+  );
+  const [mirror, setMirror] = SnappitStore.createValue<string>(
+    SNAPPIT_CONSTS.store.keys.tessdata_mirror,
+  );
+
+  return (
+    <div class="flex flex-col gap-2 py-2">
+      <div class="flex justify-between items-center h-[30px]">
+        <div class="text-sm font-light">Model variant</div>
+        <Select
+          value={variant() ?? DEFAULT_TESSDATA_VARIANT}
+          onChange={(value) => value && setVariant(value)}
+          options={TESSDATA_VARIANT_OPTIONS.map((o) => o.value)}
+          itemComponent={(props) => (
+            <SelectItem item={props.item}>
+              {TESSDATA_VARIANT_OPTIONS.find((o) => o.value === props.item.rawValue)?.label}
+            </SelectItem>
+          )}
+        >
+          <SelectTrigger class="w-[130px]">
+            <SelectValue<TessdataVariant>>
+              {(state) =>
+                TESSDATA_VARIANT_OPTIONS.find((o) => o.value === state.selectedOption())?.label
+              }
+            </SelectValue>
+          </SelectTrigger>
+          <SelectContent />
+        </Select>
+      </div>
+
+      <div class="flex flex-col gap-1">
+        <div class="text-sm font-light">Download mirror</div>
+        <input
+          type="url"
+          placeholder={SNAPPIT_CONSTS.defaults.tessdata_mirror}
+          value={mirror() ?? ""}
+          onChange={(e) => setMirror(e.currentTarget.value.trim())}
+          class="w-full h-7 px-2 text-xs rounded-md border border-input bg-background focus:outline-none focus:ring-1 focus:ring-ring"
+        />
+        <div class="text-xs text-muted-foreground">
+          {"{variant}"} is replaced with tessdata, tessdata_fast or tessdata_best. A SHA256SUMS file
+          next to the models is used to verify downloads.
+        </div>
+      </div>
+    </div>
+  );
+}
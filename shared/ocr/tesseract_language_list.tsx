@@ -6,19 +6,21 @@ import { Checkbox, CheckboxControl } from "@/shared/ui/checkbox";
 import { Tag } from "@/shared/ui/tag";
 
 import { Button } from "../ui/button";
-import { isMacOS } from "./installed_languages";
+import { formatDownloadProgress, isMacOS, TessdataProgress } from "./installed_languages";
 import { Language, RecognitionLanguageOption } from "./recognition_language";
 
 export interface TesseractLanguageListProps {
   options: Accessor<RecognitionLanguageOption[]>;
   installedLanguages: Accessor<string[]>;
   downloading: Accessor<Set<string>>;
+  downloadProgress: (lang: Language) => TessdataProgress | undefined;
   selectedLanguages: Accessor<Set<Language>>;
   isSystemLanguage: (lang: Language) => boolean;
   canDeleteLanguage: (lang: Language) => boolean;
   onToggle: (lang: Language) => void;
   onDownload: (lang: Language) => void;
   onDelete: (lang: Language) => void;
+  onCancelDownload: (lang: Language) => void;
 }
 
 export function TesseractLanguageList(props: TesseractLanguageListProps) {
@@ -190,6 +192,23 @@ export function TesseractLanguageList(props: TesseractLanguageListProps) {
                 </div>
 
                 <div class="flex items-center gap-2">
+                  <Show when={isDownloading()}>
+                    <span class="text-xs text-muted-foreground tabular-nums">
+                      {formatDownloadProgress(props.downloadProgress(option.value))}
+                    </span>
+                    <Button
+                      onClick={(e: MouseEvent) => {
+                        e.stopPropagation();
+                        props.onCancelDownload(option.value);
+                      }}
+                      variant={"muted"}
+                      size={"sm"}
+                      title="Cancel download"
+                    >
+                      Cancel
+                    </Button>
+                  </Show>
+
                   <Show when={isInstalled() && props.canDeleteLanguage(option.value)}>
                     <Button
                       onClick={(e: MouseEvent) => {
@@ -0,0 +1,32 @@
+export type PageSegMode =
+  | "auto"
+  | "single_column"
+  | "vertical_block"
+  | "single_block"
+  | "single_line"
+  | "single_word"
+  | "sparse_text"
+  | "raw_line";
+
+export type OcrEngineMode = "default" | "lstm_only" | "legacy_only" | "legacy_and_lstm";
+
+export const PAGE_SEG_MODE_OPTIONS: { value: PageSegMode; label: string }[] = [
+  { value: "auto", label: "Automatic" },
+  { value: "single_column", label: "Single column" },
+  { value: "vertical_block", label: "Vertical text" },
+  { value: "single_block", label: "Single block" },
+  { value: "single_line", label: "Single line" },
+  { value: "single_word", label: "Single word" },
+  { value: "sparse_text", label: "Sparse text" },
+  { value: "raw_line", label: "Raw line" },
+];
+
+export const OCR_ENGINE_MODE_OPTIONS: { value: OcrEngineMode; label: string }[] = [
+  { value: "default", label: "Default" },
+  { value: "lstm_only", label: "LSTM only" },
+  { value: "legacy_only", label: "Legacy only" },
+  { value: "legacy_and_lstm", label: "Legacy + LSTM" },
+];
+
+export const DEFAULT_PAGE_SEG_MODE: PageSegMode = "auto";
+export const DEFAULT_OCR_ENGINE_MODE: OcrEngineMode = "default";
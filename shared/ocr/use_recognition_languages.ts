@@ -1,4 +1,4 @@
-import { createMemo, createSignal, onMount } from "solid-js";
+import { createMemo, createSignal, onCleanup, onMount } from "solid-js";
 
 import {
   DEFAULT_VALUE,
@@ -8,11 +8,14 @@ import {
 } from "@/shared/ocr/recognition_language";
 
 import {
+  cancelDownload,
   canDeleteLanguage,
   deleteLanguage,
   downloadLanguage,
+  downloadProgress,
   installedLanguages,
   isSystemLanguage,
+  listenTessdataProgress,
   refreshInstalledLanguages,
 } from "./installed_languages";
 
@@ -25,8 +28,15 @@ export function createRecognitionLanguages() {
   const [recognitionLanguage, setRecognitionLanguage, isReady] = RecognitionLanguage.create();
   const [downloading, setDownloading] = createSignal<Set<string>>(new Set());
 
-  onMount(() => {
+  let unlistenProgress: (() => void) | undefined;
+
+  onMount(async () => {
     refreshInstalledLanguages();
+    unlistenProgress = await listenTessdataProgress();
+  });
+
+  onCleanup(() => {
+    unlistenProgress?.();
   });
 
   const handleDownload = async (code: Language) => {
@@ -37,6 +47,9 @@ export function createRecognitionLanguages() {
     });
     try {
       await downloadLanguage(code);
+    } catch (e) {
+      // Cancelled downloads reject as well, the progress event already told the list
+      console.error(`Failed to download ${code}`, e);
     } finally {
       setDownloading((prev) => {
         const next = new Set(prev);
@@ -112,6 +125,8 @@ export function createRecognitionLanguages() {
     recognitionLanguage,
     setRecognitionLanguage,
     downloading,
+    downloadProgress: (lang: Language) => downloadProgress().get(lang),
+    cancelDownload,
     handleDownload,
     tesseractLanguageSet,
     isAutoLanguageSelected,
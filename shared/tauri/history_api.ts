@@ -0,0 +1,29 @@
+import { invoke } from "@tauri-apps/api/core";
+
+import type { CaptureHistoryPage, CaptureHistoryQuery, ExportFormat } from "@/shared/history/types";
+
+export class HistoryApi {
+  static async query(query: CaptureHistoryQuery): Promise<CaptureHistoryPage> {
+    return await invoke<CaptureHistoryPage>("query_history", { query });
+  }
+
+  static async getThumbnail(id: number): Promise<string | null> {
+    return await invoke<string | null>("get_history_thumbnail", { id });
+  }
+
+  static async delete(ids: number[]): Promise<void> {
+    await invoke<void>("delete_history_items", { ids });
+  }
+
+  static async clear(): Promise<void> {
+    await invoke<void>("clear_history");
+  }
+
+  static async export(format: ExportFormat): Promise<string> {
+    return await invoke<string>("export_history", { format });
+  }
+
+  static async addRuler(value: string): Promise<void> {
+    await invoke<void>("add_ruler_history", { value });
+  }
+}
@@ -1,6 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
 
-import { SnappitQrResponse, SnappitResponse } from "@/shared/tauri/snappit_res";
+import {
+  SnappitQrResponse,
+  SnappitResponse,
+  SnappitTableResponse,
+  SnappitTranslationResponse,
+} from "@/shared/tauri/snappit_res";
 
 export interface RegionCaptureParams {
   x: number;
@@ -9,19 +14,60 @@ export interface RegionCaptureParams {
   height: number;
 }
 
+/** Shape drawn onto a screenshot, in points from the top left of the selection */
+export type SnappitAnnotation =
+  | { kind: "rect"; x: number; y: number; width: number; height: number }
+  | { kind: "arrow"; x1: number; y1: number; x2: number; y2: number }
+  | { kind: "highlight"; x: number; y: number; width: number; height: number };
+
+export type SnappitAnnotationKind = SnappitAnnotation["kind"];
+
+export type SnappitCaptureMode = "monitor" | "all_monitors" | "focused_window" | "hovered_window";
+
 export abstract class RegionCaptureApi {
   static async onCapture(params: RegionCaptureParams) {
     return invoke<SnappitResponse>("on_capture", { params });
   }
 
+  static async onCaptureMode(mode: SnappitCaptureMode) {
+    return invoke<SnappitResponse>("on_capture_mode", { mode });
+  }
+
+  /** Captures the last selected region again, on the monitor it was selected on */
+  static async repeatLastRegion() {
+    return invoke<SnappitResponse>("repeat_last_region");
+  }
+
   static async scanRegionQr(params: RegionCaptureParams) {
     return invoke<SnappitQrResponse>("scan_region_qr", { params });
   }
 
+  static async scanRegionTable(params: RegionCaptureParams) {
+    return invoke<SnappitTableResponse>("scan_region_table", { params });
+  }
+
+  static async translateRegion(params: RegionCaptureParams) {
+    return invoke<SnappitTranslationResponse>("translate_region", { params });
+  }
+
+  /** Saves the region with its annotations as an image file and returns its path */
+  static async saveScreenshot(params: RegionCaptureParams, annotations: SnappitAnnotation[]) {
+    return invoke<string>("save_screenshot", { params, annotations });
+  }
+
   static async getLastShotDim() {
     return invoke<[number, number]>("get_last_shot_dim");
   }
 
+  /** Screen frozen for a delayed capture, if the overlay was shown over one */
+  static async getFrozenFrame(): Promise<Blob | undefined> {
+    const res = await fetch("img://frozen", {
+      method: "GET",
+    });
+
+    return res.ok ? res.blob() : undefined;
+  }
+
   static async getLastShotData(): Promise<Blob> {
     const res = await fetch("img://current", {
       method: "GET",
@@ -7,8 +7,9 @@ export interface ColorInfo {
 }
 
 export class ColorDropperApi {
-  static async captureColorAtCursor(x: number, y: number): Promise<ColorInfo> {
-    return await invoke<ColorInfo>("capture_color_at_cursor", { x, y });
+  /** `record` adds the sampled color to the capture history */
+  static async captureColorAtCursor(x: number, y: number, record = false): Promise<ColorInfo> {
+    return await invoke<ColorInfo>("capture_color_at_cursor", { x, y, record });
   }
 
   static async captureMagnifiedView(x: number, y: number): Promise<void> {
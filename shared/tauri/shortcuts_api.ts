@@ -14,6 +14,12 @@ export const DIGITAL_RULER_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys
 export const COLOR_DROPPER_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys
   .hotkey_color_dropper as ShortcutKeys;
 export const QR_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys.hotkey_qr_scanner as ShortcutKeys;
+export const TABLE_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys
+  .hotkey_table_scanner as ShortcutKeys;
+export const TRANSLATOR_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys.hotkey_translator as ShortcutKeys;
+export const REPEAT_REGION_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys
+  .hotkey_repeat_region as ShortcutKeys;
+export const SCREENSHOT_SHORTCUT_KEY = SNAPPIT_CONSTS.store.keys.hotkey_screenshot as ShortcutKeys;
 
 export abstract class ShortcutsApi {
   static async syncShortcut(target: SnappitOverlayTarget) {
@@ -28,6 +28,16 @@ export abstract class SnapOverlayApi {
     return overlay?.listen("snap_overlay:hidden", handler);
   }
 
+  static async onRepeatRegion(handler: EventCallback<boolean>) {
+    const overlay = await this.get();
+    return overlay?.listen("snap_overlay:repeat_region", handler);
+  }
+
+  /** Keeps the overlay on its monitor while a selection continues onto other monitors */
+  static async setSelecting(selecting: boolean) {
+    return invoke("set_snap_overlay_selecting", { selecting });
+  }
+
   static async getCurrentTarget(): Promise<SnappitOverlayTarget | null> {
     return invoke<SnappitOverlayTarget | null>("get_snap_overlay_target");
   }
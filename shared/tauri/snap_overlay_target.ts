@@ -3,4 +3,8 @@ export type SnappitOverlayTarget =
   | "digital_ruler"
   | "color_dropper"
   | "qr_scanner"
+  | "table_scanner"
+  | "translator"
+  | "screenshot"
+  | "repeat_region"
   | "none";
@@ -1,15 +1,80 @@
+import { TableFormat } from "@/shared/ocr/table_format";
 import { ColorInfo } from "@/shared/tauri/screen_capture_api";
 
+export type SnappitBarcodeFormat =
+  | "qr_code"
+  | "micro_qr_code"
+  | "aztec"
+  | "data_matrix"
+  | "pdf417"
+  | "ean8"
+  | "ean13"
+  | "upc_a"
+  | "upc_e"
+  | "code39"
+  | "code93"
+  | "code128"
+  | "codabar"
+  | "itf"
+  | "other";
+
+export interface SnappitBarcodePoint {
+  x: number;
+  y: number;
+}
+
+export interface SnappitBarcode {
+  value: string;
+  format: SnappitBarcodeFormat;
+  points: SnappitBarcodePoint[];
+}
+
 export interface SnappitQrResponse {
   kind: "qr";
-  payload: string;
+  payload: SnappitBarcode[];
+}
+
+export function joinBarcodeValues(codes: SnappitBarcode[]): string {
+  return codes.map((code) => code.value).join("\n");
 }
 
 export type SnappitOcrEngine = "vision" | "tesseract";
 
+export interface SnappitOcrRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface SnappitOcrWord {
+  text: string;
+  rect: SnappitOcrRect;
+  confidence: number;
+}
+
+export interface SnappitOcrLine {
+  rect: SnappitOcrRect;
+  words: SnappitOcrWord[];
+}
+
+export interface SnappitOcrBlock {
+  rect: SnappitOcrRect;
+  lines: SnappitOcrLine[];
+}
+
 export interface SnappitOcrPayload {
   value: string;
   ocr: SnappitOcrEngine;
+  blocks: SnappitOcrBlock[];
+  // Tesseract languages picked by script detection, joined with "+"
+  detected_language: string | null;
+  // Mean confidence in the 0..=100 range, null when nothing was recognized
+  confidence: number | null;
+  // Below the configured minimum, the text is likely garbled
+  low_confidence: boolean;
+  // Recognized a second time after a low confidence first pass
+  retried: boolean;
 }
 
 export interface SnappitOcrResponse {
@@ -17,9 +82,41 @@ export interface SnappitOcrResponse {
   payload: SnappitOcrPayload;
 }
 
+export interface SnappitTablePayload {
+  value: string;
+  format: TableFormat;
+  // Cell texts row by row, every row has the same number of cells
+  rows: string[][];
+  ocr: SnappitOcrEngine;
+}
+
+export interface SnappitTableResponse {
+  kind: "table";
+  payload: SnappitTablePayload;
+}
+
+export interface SnappitTranslationPayload {
+  source_text: string;
+  translated_text: string;
+  // BCP 47 tag of the recognized text, null when unknown
+  source_language: string | null;
+  target_language: string;
+  ocr: SnappitOcrEngine;
+}
+
+export interface SnappitTranslationResponse {
+  kind: "translation";
+  payload: SnappitTranslationPayload;
+}
+
 export interface SnappitDropperResponse {
   kind: "dropper";
   payload: ColorInfo;
 }
 
-export type SnappitResponse = SnappitQrResponse | SnappitOcrResponse | SnappitDropperResponse;
+export type SnappitResponse =
+  | SnappitQrResponse
+  | SnappitOcrResponse
+  | SnappitTableResponse
+  | SnappitTranslationResponse
+  | SnappitDropperResponse;
@@ -0,0 +1,438 @@
+//! Headless command line interface
+//!
+//! Runs OCR, barcode scanning and color sampling on image files (or stdin)
+//! without the Tauri app, using the same `snappit_core` engines as `on_capture`. The
+//! crate only depends on `snappit_core`, so building it doesn't pull in the webview.
+
+use std::{
+    io::{Cursor, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{error::ErrorKind, CommandFactory, Parser, Subcommand, ValueEnum};
+use image::{DynamicImage, ImageReader};
+use serde::{de::DeserializeOwned, Serialize};
+use snappit_core::{
+    errors::{SnappitCoreError, SnappitCoreResult},
+    ocr::{
+        recognition_language::{sanitize_recognition_language, split_recognition_languages},
+        OcrConfig, SnappitLanguageRegistry, SnappitLineBreakMode, SnappitMathNotation, SnappitOcr,
+        SnappitOcrEngineMode, SnappitPageSegMode, SnappitPreprocessStep, SnappitTesseractOcr,
+        SnappitTesseractOptions,
+    },
+    qr::{SnappitBarcodeScope, SnappitQr},
+    res::{SnappitColorInfo, SnappitTableFormat},
+    settings,
+};
+
+const STDIN_INPUT: &str = "-";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "snappit-cli",
+    version,
+    about = "Snappit OCR, QR and color tools for image files"
+)]
+struct SnappitCli {
+    /// Output format
+    #[arg(long, short, value_enum, global = true, default_value_t = SnappitCliFormat::Plain)]
+    format: SnappitCliFormat,
+
+    #[command(subcommand)]
+    command: SnappitCliCommand,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SnappitCliFormat {
+    Plain,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum SnappitCliCommand {
+    /// Recognize text with Tesseract
+    Ocr {
+        /// PNG/JPEG files, or `-` to read a single image from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+
+        /// Tesseract languages joined with `+`, e.g. `eng+deu`. Defaults to system locales,
+        /// `auto` detects the script and picks from the installed languages
+        #[arg(long, short)]
+        lang: Option<String>,
+
+        /// Directory with `.traineddata` files. Defaults to `TESSDATA_PREFIX`
+        #[arg(long)]
+        tessdata: Option<PathBuf>,
+
+        /// Join recognized lines into a single line
+        #[arg(long, conflicts_with = "reflow")]
+        collapse_line_breaks: bool,
+
+        /// Join wrapped lines into paragraphs and undo hyphenation
+        #[arg(long)]
+        reflow: bool,
+
+        /// Read source code: keep indentation and symbols, skip dictionary correction
+        #[arg(long, conflicts_with_all = ["collapse_line_breaks", "reflow"])]
+        code: bool,
+
+        /// Wrap recognized code in a Markdown fenced block
+        #[arg(long, requires = "code")]
+        code_fence: bool,
+
+        /// Read formulas and write super/subscripts and symbols as unicode or latex
+        #[arg(long, value_parser = parse_setting::<SnappitMathNotation>, conflicts_with = "code")]
+        math: Option<SnappitMathNotation>,
+
+        /// Comma separated preprocessing steps: grayscale, auto_invert, upscale, deskew, binarize
+        #[arg(long, value_delimiter = ',', value_parser = parse_setting::<SnappitPreprocessStep>)]
+        preprocess: Vec<SnappitPreprocessStep>,
+
+        /// Page segmentation: auto, single_column, vertical_block, single_block,
+        /// single_line, single_word, sparse_text, raw_line
+        #[arg(long, value_parser = parse_setting::<SnappitPageSegMode>, default_value = "auto")]
+        psm: SnappitPageSegMode,
+
+        /// Engine mode: default, lstm_only, legacy_only, legacy_and_lstm
+        #[arg(long, value_parser = parse_setting::<SnappitOcrEngineMode>, default_value = "default")]
+        oem: SnappitOcrEngineMode,
+
+        /// Only recognize these characters
+        #[arg(long, default_value = "")]
+        whitelist: String,
+
+        /// Never recognize these characters
+        #[arg(long, default_value = "")]
+        blacklist: String,
+
+        /// Keep runs of spaces between words
+        #[arg(long)]
+        preserve_interword_spaces: bool,
+
+        /// Warn about results with a mean confidence (0-100) below this
+        #[arg(long)]
+        min_confidence: Option<f32>,
+
+        /// Recognize low confidence results again with extra preprocessing
+        #[arg(long, requires = "min_confidence")]
+        retry_low_confidence: bool,
+
+        /// Rebuild table rows and columns and print them as tsv, csv or markdown
+        #[arg(long, value_parser = parse_setting::<SnappitTableFormat>)]
+        table: Option<SnappitTableFormat>,
+    },
+    /// Decode QR codes and barcodes
+    Qr {
+        /// PNG/JPEG files, or `-` to read a single image from stdin
+        #[arg(required = true)]
+        inputs: Vec<String>,
+    },
+    /// Sample the color of a single pixel
+    Color {
+        /// PNG/JPEG file, or `-` to read the image from stdin
+        input: String,
+
+        /// Pixel column, defaults to the image center
+        #[arg(long)]
+        x: Option<u32>,
+
+        /// Pixel row, defaults to the image center
+        #[arg(long)]
+        y: Option<u32>,
+    },
+}
+
+#[derive(Serialize)]
+struct SnappitCliRecord<'a, T: Serialize> {
+    input: &'a str,
+    result: T,
+}
+
+fn main() -> ExitCode {
+    let cli = SnappitCli::parse();
+    let mut failed = false;
+
+    if let SnappitCliCommand::Ocr { inputs, .. } | SnappitCliCommand::Qr { inputs } = &cli.command {
+        if let Err(message) = check_inputs(inputs) {
+            SnappitCli::command()
+                .error(ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+    }
+
+    match &cli.command {
+        SnappitCliCommand::Ocr {
+            inputs,
+            lang,
+            tessdata,
+            collapse_line_breaks,
+            reflow,
+            code,
+            code_fence,
+            math,
+            preprocess,
+            psm,
+            oem,
+            whitelist,
+            blacklist,
+            preserve_interword_spaces,
+            min_confidence,
+            retry_low_confidence,
+            table,
+        } => {
+            let detect_language = lang
+                .as_deref()
+                .is_some_and(|lang| lang.trim().eq_ignore_ascii_case("auto"));
+            let mut config = OcrConfig {
+                tessdata_dir: tessdata.clone(),
+                detect_language,
+                line_breaks: match (collapse_line_breaks, reflow) {
+                    (true, _) => SnappitLineBreakMode::Collapse,
+                    (_, true) => SnappitLineBreakMode::Reflow,
+                    _ => SnappitLineBreakMode::Keep,
+                },
+                code: *code,
+                code_fence: *code_fence,
+                math: *math,
+                min_confidence: *min_confidence,
+                retry_low_confidence: *retry_low_confidence,
+                preprocess: preprocess.clone(),
+                tesseract: SnappitTesseractOptions {
+                    page_seg_mode: *psm,
+                    engine_mode: *oem,
+                    char_whitelist: whitelist.clone(),
+                    char_blacklist: blacklist.clone(),
+                    preserve_interword_spaces: *preserve_interword_spaces,
+                    disable_dictionary: false,
+                },
+                ..OcrConfig::default()
+            };
+
+            if let Some(lang) = lang.as_deref().filter(|_| !detect_language) {
+                let installed = config
+                    .resolved_tessdata_dir()
+                    .map(|dir| SnappitTesseractOcr::installed_languages(&dir))
+                    .unwrap_or_default();
+
+                match recognition_language(lang, &installed) {
+                    Ok(language) => config.languages = split_recognition_languages(&language),
+                    Err(message) => SnappitCli::command()
+                        .error(ErrorKind::InvalidValue, message)
+                        .exit(),
+                }
+            }
+
+            if let Some(table_format) = *table {
+                let config = SnappitOcr::table_config(&config);
+
+                for input in inputs {
+                    let result = load_image(input).and_then(|image| {
+                        let result = SnappitOcr::recognize_with_tesseract(&config, &image)?;
+                        Ok(SnappitOcr::table_from_result(&result, table_format))
+                    });
+
+                    failed |= !emit(cli.format, input, result, |res| res.value.clone());
+                }
+            } else {
+                for input in inputs {
+                    let result = load_image(input)
+                        .and_then(|image| SnappitOcr::recognize_with_tesseract(&config, &image));
+
+                    if let Some(res) = result.as_ref().ok().filter(|res| res.low_confidence) {
+                        let confidence = res.confidence.unwrap_or_default();
+                        eprintln!(
+                            "{input}: low confidence ({confidence:.0}), the text may be wrong"
+                        );
+                    }
+
+                    failed |= !emit(cli.format, input, result, |res| res.value.clone());
+                }
+            }
+        }
+        SnappitCliCommand::Qr { inputs } => {
+            for input in inputs {
+                let result = load_image(input).and_then(|image| {
+                    SnappitQr::scan(image.into_rgba8(), SnappitBarcodeScope::All)
+                });
+
+                failed |= !emit(cli.format, input, result, |codes| {
+                    codes
+                        .iter()
+                        .map(|code| code.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                });
+            }
+        }
+        SnappitCliCommand::Color { input, x, y } => {
+            let result = load_image(input).and_then(|image| sample_color(&image, *x, *y));
+
+            failed |= !emit(cli.format, input, result, |color| color.hex.clone());
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Stdin holds a single image, so `-` can't be read twice
+fn check_inputs(inputs: &[String]) -> Result<(), String> {
+    if inputs.iter().filter(|input| *input == STDIN_INPUT).count() > 1 {
+        return Err(format!(
+            "`{STDIN_INPUT}` can only be given once, stdin holds one image"
+        ));
+    }
+
+    Ok(())
+}
+
+/// `lang` cleaned up for Tesseract, or an error listing the `installed` languages when
+/// one of its codes isn't among them. Without installed languages to compare with,
+/// the codes have to be known languages
+fn recognition_language(lang: &str, installed: &[String]) -> Result<String, String> {
+    let is_available = |code: &str| {
+        if installed.is_empty() {
+            SnappitLanguageRegistry::find(code).is_some()
+        } else {
+            installed.iter().any(|language| language == code)
+        }
+    };
+
+    let unknown = match sanitize_recognition_language(lang) {
+        Some(language) => {
+            match split_recognition_languages(&language)
+                .into_iter()
+                .find(|code| !is_available(code))
+            {
+                Some(code) => code,
+                None => return Ok(language),
+            }
+        }
+        None => lang.trim().to_string(),
+    };
+
+    let installed = if installed.is_empty() {
+        "none found, pass --tessdata or set TESSDATA_PREFIX".to_string()
+    } else {
+        installed.join(", ")
+    };
+
+    Err(format!(
+        "unknown language `{unknown}`, installed languages: {installed}"
+    ))
+}
+
+fn load_image(input: &str) -> SnappitCoreResult<DynamicImage> {
+    let bytes = if input == STDIN_INPUT {
+        let mut buf = Vec::new();
+        std::io::stdin().read_to_end(&mut buf)?;
+        buf
+    } else {
+        std::fs::read(input)?
+    };
+
+    let image = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .decode()?;
+
+    Ok(image)
+}
+
+/// Parses the snake_case names the settings store uses
+fn parse_setting<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    settings::parse_setting(value).ok_or_else(|| format!("unknown value `{value}`"))
+}
+
+fn sample_color(
+    image: &DynamicImage,
+    x: Option<u32>,
+    y: Option<u32>,
+) -> SnappitCoreResult<SnappitColorInfo> {
+    let rgba = image.to_rgba8();
+    let x = x.unwrap_or(rgba.width() / 2);
+    let y = y.unwrap_or(rgba.height() / 2);
+
+    let pixel = rgba
+        .get_pixel_checked(x, y)
+        .ok_or(SnappitCoreError::PixelOutOfBounds(x, y))?;
+
+    Ok(SnappitColorInfo::from_rgba(
+        pixel[0], pixel[1], pixel[2], pixel[3],
+    ))
+}
+
+/// Prints a single result, returns `false` if it was an error
+fn emit<T: Serialize>(
+    format: SnappitCliFormat,
+    input: &str,
+    result: SnappitCoreResult<T>,
+    to_plain: impl Fn(&T) -> String,
+) -> bool {
+    let value = match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{input}: {err}");
+            return false;
+        }
+    };
+
+    let line = match format {
+        SnappitCliFormat::Plain => to_plain(&value),
+        SnappitCliFormat::Json => {
+            let record = SnappitCliRecord {
+                input,
+                result: value,
+            };
+
+            match serde_json::to_string(&record) {
+                Ok(json) => json,
+                Err(err) => {
+                    eprintln!("{input}: {err}");
+                    return false;
+                }
+            }
+        }
+    };
+
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "{line}").is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_check_inputs_rejects_repeated_stdin() {
+        assert!(check_inputs(&strings(&["a.png", "-"])).is_ok());
+        assert!(check_inputs(&strings(&["-", "a.png", "-"])).is_err());
+    }
+
+    #[test]
+    fn test_recognition_language_checks_installed_languages() {
+        let installed = strings(&["deu", "eng"]);
+
+        assert_eq!(
+            recognition_language(" ENG+deu ", &installed),
+            Ok("eng+deu".to_string())
+        );
+        assert_eq!(
+            recognition_language("eng+fra", &installed),
+            Err("unknown language `fra`, installed languages: deu, eng".to_string())
+        );
+        assert!(recognition_language("+", &installed).is_err());
+
+        // Without a tessdata directory the codes only have to be real languages
+        assert!(recognition_language("fra", &[]).is_ok());
+        assert!(recognition_language("english", &[]).is_err());
+    }
+}
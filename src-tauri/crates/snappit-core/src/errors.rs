@@ -0,0 +1,44 @@
+use std::{io, str::Utf8Error};
+
+use image::ImageError;
+use leptess::{leptonica::PixError, tesseract::TessInitError};
+use tesseract_plumbing::TessBaseApiSetImageSafetyError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnappitCoreError {
+    #[error("PixError error: {0}")]
+    PixError(#[from] PixError),
+
+    #[error("TessInitError error: {0}")]
+    TessInitError(#[from] TessInitError),
+
+    #[error("Tesseract rejected variable {0}")]
+    TessSetVariableError(String),
+
+    #[error("Tesseract rejected the image: {0}")]
+    TessSetImageError(#[from] TessBaseApiSetImageSafetyError),
+
+    #[error("Utf8Error error: {0}")]
+    Utf8Error(#[from] Utf8Error),
+
+    #[error("ImageError error: {0}")]
+    ImageError(#[from] ImageError),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("macOS Vision OCR unavailable: {0}")]
+    MacOSVisionOcrUnavailable(String),
+
+    #[error("QR scan failed: {0}")]
+    QrScanFailed(String),
+
+    #[error("Vision QR unavailable: {0}")]
+    VisionQrUnavailable(String),
+
+    #[error("Pixel ({0}, {1}) is outside of the image")]
+    PixelOutOfBounds(u32, u32),
+}
+
+pub type SnappitCoreResult<T> = Result<T, SnappitCoreError>;
@@ -0,0 +1,12 @@
+//! Snappit recognition core
+//!
+//! OCR and barcode engines that work on plain images and an explicit
+//! [`ocr::OcrConfig`], without any dependency on the Tauri app. The app,
+//! the `snappit-cli` crate and tests all go through this crate.
+
+pub mod errors;
+pub mod ocr;
+pub mod qr;
+pub mod res;
+pub mod settings;
+pub mod text_processing;
@@ -0,0 +1,205 @@
+//! Rebuilds the whitespace of source code from word positions
+//!
+//! Engines report the words of each line but drop the spaces in front of them.
+//! Code is set in a monospace font, so the width of one character follows from
+//! the word boxes, and every gap, at the start of a line or between words,
+//! converts back into a number of spaces.
+
+use crate::res::{SnappitOcrBlock, SnappitOcrRect, SnappitOcrWord};
+
+use super::ocr_layout::center_y;
+
+/// Blank lines kept in a row, larger gaps are usually not part of the snippet
+const MAX_BLANK_LINES: usize = 2;
+
+struct SnappitCodeLine<'a> {
+    rect: SnappitOcrRect,
+    words: Vec<&'a SnappitOcrWord>,
+}
+
+/// Lines top to bottom, parts of one line split into separate blocks are joined again
+fn code_lines(blocks: &[SnappitOcrBlock]) -> Vec<SnappitCodeLine<'_>> {
+    let mut ocr_lines: Vec<_> = blocks
+        .iter()
+        .flat_map(|block| &block.lines)
+        .filter(|line| line.words.iter().any(|word| !word.text.trim().is_empty()))
+        .collect();
+    ocr_lines.sort_by_key(|line| center_y(&line.rect));
+
+    let mut lines: Vec<SnappitCodeLine> = Vec::new();
+    for ocr_line in ocr_lines {
+        let words = ocr_line
+            .words
+            .iter()
+            .filter(|word| !word.text.trim().is_empty());
+
+        match lines.last_mut() {
+            Some(line) if center_y(&ocr_line.rect) <= line.rect.bottom() => {
+                line.rect = line.rect.union(&ocr_line.rect);
+                line.words.extend(words);
+            }
+            _ => lines.push(SnappitCodeLine {
+                rect: ocr_line.rect,
+                words: words.collect(),
+            }),
+        }
+    }
+
+    for line in &mut lines {
+        line.words.sort_by_key(|word| word.rect.x);
+    }
+
+    lines
+}
+
+fn median(mut values: Vec<f32>) -> Option<f32> {
+    values.sort_by(f32::total_cmp);
+    values.get(values.len() / 2).copied()
+}
+
+/// Width of one character, single characters are left out as their boxes hug the glyph
+fn char_width(lines: &[SnappitCodeLine]) -> Option<f32> {
+    let widths = lines
+        .iter()
+        .flat_map(|line| &line.words)
+        .filter_map(|word| {
+            let chars = word.text.trim().chars().count();
+            (chars > 1).then(|| word.rect.width as f32 / chars as f32)
+        })
+        .collect();
+
+    median(widths).filter(|width| *width > 0.0)
+}
+
+/// Usual distance between the tops of two consecutive lines
+fn line_pitch(lines: &[SnappitCodeLine]) -> Option<f32> {
+    let pitches = lines
+        .windows(2)
+        .map(|pair| pair[1].rect.y.saturating_sub(pair[0].rect.y) as f32)
+        .collect();
+
+    median(pitches).filter(|pitch| *pitch > 0.0)
+}
+
+fn spaces(gap: u32, char_width: f32) -> usize {
+    (gap as f32 / char_width).round() as usize
+}
+
+/// Text of the code in `blocks` with its indentation, alignment and blank lines,
+/// `None` when there are no words to measure
+pub fn code_text(blocks: &[SnappitOcrBlock]) -> Option<String> {
+    let lines = code_lines(blocks);
+    let char_width = char_width(&lines)?;
+    let pitch = line_pitch(&lines);
+    let left = lines.iter().map(|line| line.rect.x).min()?;
+
+    let mut text = String::new();
+    let mut previous: Option<&SnappitCodeLine> = None;
+
+    for line in &lines {
+        if let Some(previous) = previous {
+            let distance = line.rect.y.saturating_sub(previous.rect.y) as f32;
+            let blank_lines = pitch
+                .map(|pitch| (distance / pitch).round() as usize)
+                .unwrap_or(1)
+                .saturating_sub(1)
+                .min(MAX_BLANK_LINES);
+
+            text.push_str(&"\n".repeat(blank_lines + 1));
+        }
+
+        let mut right = left;
+        for (index, word) in line.words.iter().enumerate() {
+            let gap = spaces(word.rect.x.saturating_sub(right), char_width);
+            // Words of a line never touch, however close the boxes are
+            let gap = if index == 0 { gap } else { gap.max(1) };
+
+            text.push_str(&" ".repeat(gap));
+            text.push_str(word.text.trim());
+            right = word.rect.right();
+        }
+
+        previous = Some(line);
+    }
+
+    Some(text)
+}
+
+/// Wraps `code` in a Markdown fenced block, with a fence longer than any run of
+/// backticks inside the code
+pub fn fence_code(code: &str) -> String {
+    let longest_run = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest_run.max(2) + 1);
+
+    format!("{fence}\n{code}\n{fence}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ocr::test_fixtures::{self, block},
+        res::SnappitOcrLine,
+    };
+
+    use super::*;
+
+    /// Monospace line with 10px wide characters, `column` counted from the left edge
+    fn line(text: &str, column: u32, row: u32) -> SnappitOcrLine {
+        let mut words = Vec::new();
+        let mut x = 20 + column * 10;
+
+        for part in text.split(' ') {
+            if !part.is_empty() {
+                let width = part.chars().count() as u32 * 10;
+                let rect = SnappitOcrRect::new(x, 10 + row * 24, width, 18);
+                words.push(test_fixtures::word(part, rect));
+            }
+            x += (part.chars().count() as u32 + 1) * 10;
+        }
+
+        test_fixtures::line(words)
+    }
+
+    fn blocks(lines: Vec<SnappitOcrLine>) -> Vec<SnappitOcrBlock> {
+        vec![block(lines)]
+    }
+
+    #[test]
+    fn test_code_text_rebuilds_indentation() {
+        let code = blocks(vec![
+            line("fn main() {", 0, 0),
+            line("if ready {", 4, 1),
+            line("run();", 8, 2),
+            line("}", 4, 3),
+            line("}", 0, 5),
+        ]);
+
+        assert_eq!(
+            code_text(&code).as_deref(),
+            Some("fn main() {\n    if ready {\n        run();\n    }\n\n}")
+        );
+    }
+
+    #[test]
+    fn test_code_text_keeps_alignment_and_joins_split_lines() {
+        let mut first = line("let a   = 1;", 0, 0);
+        let comment = first.words.split_off(3);
+        let code = vec![
+            block(vec![first]),
+            block(vec![test_fixtures::line(comment)]),
+        ];
+
+        assert_eq!(code_text(&code).as_deref(), Some("let a   = 1;"));
+        assert_eq!(code_text(&[]), None);
+    }
+
+    #[test]
+    fn test_fence_code() {
+        assert_eq!(fence_code("x = 1"), "```\nx = 1\n```");
+        assert_eq!(fence_code("a ``` b"), "````\na ``` b\n````");
+    }
+}
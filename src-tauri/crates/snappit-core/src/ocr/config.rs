@@ -0,0 +1,101 @@
+use std::{env, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    recognition_language::{default_recognition_language, split_recognition_languages},
+    SnappitPreprocessStep, SnappitTesseractOptions,
+};
+
+/// What happens to the line breaks of recognized text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitLineBreakMode {
+    /// Lines as they are in the image
+    #[default]
+    Keep,
+    /// Everything on a single line
+    Collapse,
+    /// Wrapped lines joined into paragraphs, keeping paragraph breaks and list items
+    Reflow,
+}
+
+/// How formulas are written out in math mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitMathNotation {
+    /// Unicode super/subscripts and symbols, `x² + y₁ ≤ π`
+    #[default]
+    Unicode,
+    /// LaTeX commands, `x^2 + y_1 \leq \pi`
+    Latex,
+}
+
+/// Everything an engine needs to know to recognize an image
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrConfig {
+    /// Tesseract language codes in priority order, e.g. `["eng", "deu"]`
+    pub languages: Vec<String>,
+    /// Directory with `.traineddata` files. `None` lets Tesseract fall back
+    /// to `TESSDATA_PREFIX` or its compiled-in location
+    pub tessdata_dir: Option<PathBuf>,
+    /// Treats `languages` as a fallback and picks installed traineddata for
+    /// the script found in the image, set when the language setting is `auto`
+    pub detect_language: bool,
+    pub line_breaks: SnappitLineBreakMode,
+    /// Reads source code: indentation rebuilt from word positions, no dictionary
+    /// correction and symbols left as they are. `line_breaks` doesn't apply
+    pub code: bool,
+    /// Wraps recognized code in a Markdown fenced block
+    pub code_fence: bool,
+    /// Reads formulas: super/subscripts found by their baseline offset and
+    /// symbols written as `notation`. `None` recognizes plain text
+    pub math: Option<SnappitMathNotation>,
+    /// Results with a mean confidence (0..=100) below this are flagged as low
+    /// confidence, `None` never flags them
+    pub min_confidence: Option<f32>,
+    /// Recognizes low confidence results once more, with Tesseract instead of
+    /// Vision or with extra preprocessing. A Tesseract pass replaces a Vision one
+    /// only when it reaches `min_confidence`, otherwise the more confident pass wins
+    pub retry_low_confidence: bool,
+    /// Steps applied to the image before Tesseract sees it
+    pub preprocess: Vec<SnappitPreprocessStep>,
+    pub tesseract: SnappitTesseractOptions,
+}
+
+impl OcrConfig {
+    /// Directory Tesseract reads traineddata from, `TESSDATA_PREFIX` when none is
+    /// configured. `None` leaves it to Tesseract's compiled-in location
+    pub fn resolved_tessdata_dir(&self) -> Option<PathBuf> {
+        self.tessdata_dir
+            .clone()
+            .or_else(|| env::var_os("TESSDATA_PREFIX").map(PathBuf::from))
+    }
+
+    /// Languages joined with `+` the way Tesseract expects them
+    pub fn tesseract_language(&self) -> String {
+        if self.languages.is_empty() {
+            return default_recognition_language();
+        }
+
+        self.languages.join("+")
+    }
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            languages: split_recognition_languages(&default_recognition_language()),
+            tessdata_dir: None,
+            detect_language: false,
+            line_breaks: SnappitLineBreakMode::Keep,
+            code: false,
+            code_fence: false,
+            math: None,
+            min_confidence: None,
+            retry_low_confidence: false,
+            preprocess: Vec::new(),
+            tesseract: SnappitTesseractOptions::default(),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use image::DynamicImage;
+
+use crate::{errors::SnappitCoreResult, res::SnappitOcrEngine};
+
+use super::{OcrConfig, SnappitOcrOutput};
+
+/// A text recognition backend
+pub trait OcrEngine {
+    /// Which engine produced the output, reported back to the frontend
+    fn kind(&self) -> SnappitOcrEngine;
+
+    fn recognize(
+        &self,
+        config: &OcrConfig,
+        image: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrOutput>;
+}
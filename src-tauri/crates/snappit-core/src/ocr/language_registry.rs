@@ -0,0 +1,351 @@
+//! Every language Snappit can recognize, keyed by its Tesseract traineddata name
+//!
+//! Locale matching, display names, Vision language codes and the list of
+//! downloadable traineddata files are all derived from [`LANGUAGES`], so adding
+//! a language is a one line change.
+
+use serde::Serialize;
+
+/// Writing system of a language, as reported by Tesseract's script detection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitScript {
+    Arabic,
+    Armenian,
+    Bengali,
+    CanadianAboriginal,
+    Cherokee,
+    /// Not a script, used by `osd` which only detects them
+    Common,
+    Cyrillic,
+    Devanagari,
+    Ethiopic,
+    Fraktur,
+    Georgian,
+    Greek,
+    Gujarati,
+    Gurmukhi,
+    Han,
+    Hangul,
+    Hebrew,
+    Japanese,
+    Kannada,
+    Khmer,
+    Lao,
+    Latin,
+    Malayalam,
+    Math,
+    Myanmar,
+    Oriya,
+    Sinhala,
+    Syriac,
+    Tamil,
+    Telugu,
+    Thaana,
+    Thai,
+    Tibetan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SnappitLanguage {
+    /// Traineddata name, e.g. `chi_sim`
+    pub tesseract: &'static str,
+    /// BCP-47 tag, variants of a language (vertical, historic) share the tag of the base language
+    pub bcp47: &'static str,
+    /// English display name
+    pub name: &'static str,
+    pub script: SnappitScript,
+    /// Whether words are separated by spaces, false for Chinese, Japanese, Thai and the like
+    pub uses_spaces: bool,
+}
+
+const fn lang(
+    tesseract: &'static str,
+    bcp47: &'static str,
+    name: &'static str,
+    script: SnappitScript,
+    uses_spaces: bool,
+) -> SnappitLanguage {
+    SnappitLanguage {
+        tesseract,
+        bcp47,
+        name,
+        script,
+        uses_spaces,
+    }
+}
+
+/// All traineddata files published in tesseract-ocr/tessdata. A base language
+/// must come before its variants, locale matching picks the first entry on a tie
+#[rustfmt::skip]
+pub const LANGUAGES: &[SnappitLanguage] = &[
+    lang("afr", "af", "Afrikaans", SnappitScript::Latin, true),
+    lang("amh", "am", "Amharic", SnappitScript::Ethiopic, true),
+    lang("ara", "ar", "Arabic", SnappitScript::Arabic, true),
+    lang("asm", "as", "Assamese", SnappitScript::Bengali, true),
+    lang("aze", "az", "Azerbaijani", SnappitScript::Latin, true),
+    lang("aze_cyrl", "az-Cyrl", "Azerbaijani (Cyrillic)", SnappitScript::Cyrillic, true),
+    lang("bel", "be", "Belarusian", SnappitScript::Cyrillic, true),
+    lang("ben", "bn", "Bengali", SnappitScript::Bengali, true),
+    lang("bod", "bo", "Tibetan", SnappitScript::Tibetan, false),
+    lang("bos", "bs", "Bosnian", SnappitScript::Latin, true),
+    lang("bre", "br", "Breton", SnappitScript::Latin, true),
+    lang("bul", "bg", "Bulgarian", SnappitScript::Cyrillic, true),
+    lang("cat", "ca", "Catalan", SnappitScript::Latin, true),
+    lang("ceb", "ceb", "Cebuano", SnappitScript::Latin, true),
+    lang("ces", "cs", "Czech", SnappitScript::Latin, true),
+    lang("chi_sim", "zh-Hans", "Chinese (Simplified)", SnappitScript::Han, false),
+    lang("chi_sim_vert", "zh-Hans", "Chinese (Simplified, Vertical)", SnappitScript::Han, false),
+    lang("chi_tra", "zh-Hant", "Chinese (Traditional)", SnappitScript::Han, false),
+    lang("chi_tra_vert", "zh-Hant", "Chinese (Traditional, Vertical)", SnappitScript::Han, false),
+    lang("chr", "chr", "Cherokee", SnappitScript::Cherokee, true),
+    lang("cos", "co", "Corsican", SnappitScript::Latin, true),
+    lang("cym", "cy", "Welsh", SnappitScript::Latin, true),
+    lang("dan", "da", "Danish", SnappitScript::Latin, true),
+    lang("dan_frak", "da-Latf", "Danish (Fraktur)", SnappitScript::Fraktur, true),
+    lang("deu", "de", "German", SnappitScript::Latin, true),
+    lang("deu_frak", "de-Latf", "German (Fraktur, legacy)", SnappitScript::Fraktur, true),
+    lang("deu_latf", "de-Latf", "German (Fraktur)", SnappitScript::Fraktur, true),
+    lang("div", "dv", "Dhivehi", SnappitScript::Thaana, true),
+    lang("dzo", "dz", "Dzongkha", SnappitScript::Tibetan, false),
+    lang("ell", "el", "Greek", SnappitScript::Greek, true),
+    lang("eng", "en", "English", SnappitScript::Latin, true),
+    lang("enm", "enm", "Middle English", SnappitScript::Latin, true),
+    lang("epo", "eo", "Esperanto", SnappitScript::Latin, true),
+    lang("equ", "und-Zmth", "Math / Equations", SnappitScript::Math, true),
+    lang("est", "et", "Estonian", SnappitScript::Latin, true),
+    lang("eus", "eu", "Basque", SnappitScript::Latin, true),
+    lang("fao", "fo", "Faroese", SnappitScript::Latin, true),
+    lang("fas", "fa", "Persian", SnappitScript::Arabic, true),
+    lang("fil", "fil", "Filipino", SnappitScript::Latin, true),
+    lang("fin", "fi", "Finnish", SnappitScript::Latin, true),
+    lang("fra", "fr", "French", SnappitScript::Latin, true),
+    lang("frm", "frm", "Middle French", SnappitScript::Latin, true),
+    lang("fry", "fy", "Western Frisian", SnappitScript::Latin, true),
+    lang("gla", "gd", "Scottish Gaelic", SnappitScript::Latin, true),
+    lang("gle", "ga", "Irish", SnappitScript::Latin, true),
+    lang("glg", "gl", "Galician", SnappitScript::Latin, true),
+    lang("grc", "grc", "Ancient Greek", SnappitScript::Greek, true),
+    lang("guj", "gu", "Gujarati", SnappitScript::Gujarati, true),
+    lang("hat", "ht", "Haitian Creole", SnappitScript::Latin, true),
+    lang("heb", "he", "Hebrew", SnappitScript::Hebrew, true),
+    lang("hin", "hi", "Hindi", SnappitScript::Devanagari, true),
+    lang("hrv", "hr", "Croatian", SnappitScript::Latin, true),
+    lang("hun", "hu", "Hungarian", SnappitScript::Latin, true),
+    lang("hye", "hy", "Armenian", SnappitScript::Armenian, true),
+    lang("iku", "iu", "Inuktitut", SnappitScript::CanadianAboriginal, true),
+    lang("ind", "id", "Indonesian", SnappitScript::Latin, true),
+    lang("isl", "is", "Icelandic", SnappitScript::Latin, true),
+    lang("ita", "it", "Italian", SnappitScript::Latin, true),
+    lang("ita_old", "it", "Old Italian", SnappitScript::Latin, true),
+    lang("jav", "jv", "Javanese", SnappitScript::Latin, true),
+    lang("jpn", "ja", "Japanese", SnappitScript::Japanese, false),
+    lang("jpn_vert", "ja", "Japanese (Vertical)", SnappitScript::Japanese, false),
+    lang("kan", "kn", "Kannada", SnappitScript::Kannada, true),
+    lang("kat", "ka", "Georgian", SnappitScript::Georgian, true),
+    lang("kat_old", "ka", "Old Georgian", SnappitScript::Georgian, true),
+    lang("kaz", "kk", "Kazakh", SnappitScript::Cyrillic, true),
+    lang("khm", "km", "Khmer", SnappitScript::Khmer, false),
+    lang("kir", "ky", "Kyrgyz", SnappitScript::Cyrillic, true),
+    lang("kmr", "kmr", "Kurdish (Kurmanji)", SnappitScript::Latin, true),
+    lang("kor", "ko", "Korean", SnappitScript::Hangul, true),
+    lang("kor_vert", "ko", "Korean (Vertical)", SnappitScript::Hangul, true),
+    lang("lao", "lo", "Lao", SnappitScript::Lao, false),
+    lang("lat", "la", "Latin", SnappitScript::Latin, true),
+    lang("lav", "lv", "Latvian", SnappitScript::Latin, true),
+    lang("lit", "lt", "Lithuanian", SnappitScript::Latin, true),
+    lang("ltz", "lb", "Luxembourgish", SnappitScript::Latin, true),
+    lang("mal", "ml", "Malayalam", SnappitScript::Malayalam, true),
+    lang("mar", "mr", "Marathi", SnappitScript::Devanagari, true),
+    lang("mkd", "mk", "Macedonian", SnappitScript::Cyrillic, true),
+    lang("mlt", "mt", "Maltese", SnappitScript::Latin, true),
+    lang("mon", "mn", "Mongolian", SnappitScript::Cyrillic, true),
+    lang("mri", "mi", "Maori", SnappitScript::Latin, true),
+    lang("msa", "ms", "Malay", SnappitScript::Latin, true),
+    lang("mya", "my", "Burmese", SnappitScript::Myanmar, false),
+    lang("nep", "ne", "Nepali", SnappitScript::Devanagari, true),
+    lang("nld", "nl", "Dutch", SnappitScript::Latin, true),
+    lang("nor", "no", "Norwegian", SnappitScript::Latin, true),
+    lang("oci", "oc", "Occitan", SnappitScript::Latin, true),
+    lang("ori", "or", "Odia", SnappitScript::Oriya, true),
+    lang("osd", "und", "Orientation & Script Detection", SnappitScript::Common, true),
+    lang("pan", "pa", "Punjabi", SnappitScript::Gurmukhi, true),
+    lang("pol", "pl", "Polish", SnappitScript::Latin, true),
+    lang("por", "pt", "Portuguese", SnappitScript::Latin, true),
+    lang("pus", "ps", "Pashto", SnappitScript::Arabic, true),
+    lang("que", "qu", "Quechua", SnappitScript::Latin, true),
+    lang("ron", "ro", "Romanian", SnappitScript::Latin, true),
+    lang("rus", "ru", "Russian", SnappitScript::Cyrillic, true),
+    lang("san", "sa", "Sanskrit", SnappitScript::Devanagari, true),
+    lang("sin", "si", "Sinhala", SnappitScript::Sinhala, true),
+    lang("slk", "sk", "Slovak", SnappitScript::Latin, true),
+    lang("slk_frak", "sk-Latf", "Slovak (Fraktur)", SnappitScript::Fraktur, true),
+    lang("slv", "sl", "Slovenian", SnappitScript::Latin, true),
+    lang("snd", "sd", "Sindhi", SnappitScript::Arabic, true),
+    lang("spa", "es", "Spanish", SnappitScript::Latin, true),
+    lang("spa_old", "es", "Old Spanish", SnappitScript::Latin, true),
+    lang("sqi", "sq", "Albanian", SnappitScript::Latin, true),
+    lang("srp", "sr", "Serbian", SnappitScript::Cyrillic, true),
+    lang("srp_latn", "sr-Latn", "Serbian (Latin)", SnappitScript::Latin, true),
+    lang("sun", "su", "Sundanese", SnappitScript::Latin, true),
+    lang("swa", "sw", "Swahili", SnappitScript::Latin, true),
+    lang("swe", "sv", "Swedish", SnappitScript::Latin, true),
+    lang("syr", "syr", "Syriac", SnappitScript::Syriac, true),
+    lang("tam", "ta", "Tamil", SnappitScript::Tamil, true),
+    lang("tat", "tt", "Tatar", SnappitScript::Cyrillic, true),
+    lang("tel", "te", "Telugu", SnappitScript::Telugu, true),
+    lang("tgk", "tg", "Tajik", SnappitScript::Cyrillic, true),
+    lang("tgl", "tl", "Tagalog", SnappitScript::Latin, true),
+    lang("tha", "th", "Thai", SnappitScript::Thai, false),
+    lang("tir", "ti", "Tigrinya", SnappitScript::Ethiopic, true),
+    lang("ton", "to", "Tongan", SnappitScript::Latin, true),
+    lang("tur", "tr", "Turkish", SnappitScript::Latin, true),
+    lang("uig", "ug", "Uyghur", SnappitScript::Arabic, true),
+    lang("ukr", "uk", "Ukrainian", SnappitScript::Cyrillic, true),
+    lang("urd", "ur", "Urdu", SnappitScript::Arabic, true),
+    lang("uzb", "uz", "Uzbek", SnappitScript::Latin, true),
+    lang("uzb_cyrl", "uz-Cyrl", "Uzbek (Cyrillic)", SnappitScript::Cyrillic, true),
+    lang("vie", "vi", "Vietnamese", SnappitScript::Latin, true),
+    lang("yid", "yi", "Yiddish", SnappitScript::Hebrew, true),
+    lang("yor", "yo", "Yoruba", SnappitScript::Latin, true),
+];
+
+/// Locale language subtags that differ from the ones used in [`LANGUAGES`]
+const LOCALE_ALIASES: &[(&str, &str)] = &[
+    ("nb", "no"),
+    ("nn", "no"),
+    ("ku", "kmr"),
+    // Deprecated codes still returned by some platforms
+    ("iw", "he"),
+    ("in", "id"),
+    ("ji", "yi"),
+];
+
+pub struct SnappitLanguageRegistry;
+
+impl SnappitLanguageRegistry {
+    pub fn all() -> &'static [SnappitLanguage] {
+        LANGUAGES
+    }
+
+    pub fn find(tesseract: &str) -> Option<&'static SnappitLanguage> {
+        LANGUAGES
+            .iter()
+            .find(|language| language.tesseract.eq_ignore_ascii_case(tesseract))
+    }
+
+    /// English name of a traineddata code, the code itself when unknown
+    pub fn display_name(tesseract: &str) -> String {
+        Self::find(tesseract)
+            .map(|language| language.name.to_string())
+            .unwrap_or_else(|| tesseract.to_string())
+    }
+
+    /// Picks the language for a system locale such as `uk-UA`, `zh_TW` or `sr-Latn-RS`.
+    /// Every subtag of the language's BCP-47 tag has to appear in the locale,
+    /// the most specific match wins
+    pub fn from_locale(locale: &str) -> Option<&'static SnappitLanguage> {
+        let subtags = locale_subtags(locale);
+        let primary = subtags.first()?;
+
+        LANGUAGES
+            .iter()
+            .filter_map(|language| {
+                let mut tag = language.bcp47.split('-');
+                if !tag.next()?.eq_ignore_ascii_case(primary) {
+                    return None;
+                }
+
+                let mut specificity = 0;
+                for subtag in tag {
+                    if !subtags.iter().any(|s| s.eq_ignore_ascii_case(subtag)) {
+                        return None;
+                    }
+                    specificity += 1;
+                }
+
+                Some((language, specificity))
+            })
+            .fold(
+                None,
+                |best: Option<(&SnappitLanguage, usize)>, candidate| match best {
+                    Some(best) if best.1 >= candidate.1 => Some(best),
+                    _ => Some(candidate),
+                },
+            )
+            .map(|(language, _)| language)
+    }
+}
+
+/// Lowercased subtags of a locale, with aliases resolved and the script
+/// Chinese locales usually leave implicit filled in
+fn locale_subtags(locale: &str) -> Vec<String> {
+    // POSIX locales may carry an encoding or modifier: `en_US.UTF-8`, `sr_RS@latin`
+    let locale = locale.split(['.', '@']).next().unwrap_or_default();
+    let mut subtags: Vec<String> = locale
+        .split(['-', '_'])
+        .map(|subtag| subtag.trim().to_ascii_lowercase())
+        .filter(|subtag| !subtag.is_empty())
+        .collect();
+
+    let Some(primary) = subtags.first_mut() else {
+        return subtags;
+    };
+
+    if let Some((_, alias)) = LOCALE_ALIASES.iter().find(|(from, _)| from == primary) {
+        *primary = alias.to_string();
+    }
+
+    if subtags[0] == "zh" && !subtags.iter().any(|s| s == "hans" || s == "hant") {
+        let is_traditional = subtags
+            .iter()
+            .skip(1)
+            .any(|subtag| matches!(subtag.as_str(), "tw" | "hk" | "mo"));
+        subtags.push(if is_traditional { "hant" } else { "hans" }.to_string());
+    }
+
+    subtags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_locale(locale: &str) -> Option<&'static str> {
+        SnappitLanguageRegistry::from_locale(locale).map(|language| language.tesseract)
+    }
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(from_locale("en_US.UTF-8"), Some("eng"));
+        assert_eq!(from_locale("uk-UA"), Some("ukr"));
+        assert_eq!(from_locale("pl_PL"), Some("pol"));
+        assert_eq!(from_locale("ar-SA"), Some("ara"));
+        assert_eq!(from_locale("he-IL"), Some("heb"));
+        assert_eq!(from_locale("iw"), Some("heb"));
+        assert_eq!(from_locale("hi-IN"), Some("hin"));
+        assert_eq!(from_locale("ka-GE"), Some("kat"));
+        assert_eq!(from_locale("ja-JP"), Some("jpn"));
+        assert_eq!(from_locale("zh-CN"), Some("chi_sim"));
+        assert_eq!(from_locale("zh-Hant"), Some("chi_tra"));
+        assert_eq!(from_locale("zh_TW"), Some("chi_tra"));
+        assert_eq!(from_locale("sr-RS"), Some("srp"));
+        assert_eq!(from_locale("sr-Latn-RS"), Some("srp_latn"));
+        assert_eq!(from_locale("nb-NO"), Some("nor"));
+        assert_eq!(from_locale("C"), None);
+        assert_eq!(from_locale(""), None);
+    }
+
+    #[test]
+    fn test_languages_are_unique() {
+        for (index, language) in LANGUAGES.iter().enumerate() {
+            assert!(
+                LANGUAGES[index + 1..]
+                    .iter()
+                    .all(|other| other.tesseract != language.tesseract),
+                "{} is listed twice",
+                language.tesseract
+            );
+        }
+    }
+}
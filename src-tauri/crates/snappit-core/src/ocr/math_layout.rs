@@ -0,0 +1,421 @@
+//! Reads formulas out of word positions
+//!
+//! Engines recognize the characters of a formula but flatten its layout, so
+//! `x²` turns into `x 2`. A word set clearly smaller than the rest of its line
+//! and raised above or lowered below the line's baseline is a super- or
+//! subscript of the word before it. Scripts and the symbols of the mapping
+//! table are then written out as Unicode characters or LaTeX commands.
+
+use crate::res::{SnappitOcrBlock, SnappitOcrRect, SnappitOcrWord};
+
+use super::SnappitMathNotation;
+
+/// Scripts are at most this tall, relative to the usual word height of their line
+const SCRIPT_MAX_HEIGHT_RATIO: f32 = 0.75;
+/// Superscripts end at least this far above the baseline, relative to the word height
+const SUPERSCRIPT_MIN_RAISE_RATIO: f32 = 0.25;
+/// Subscripts are centered no higher than this above the baseline, relative to the word height
+const SUBSCRIPT_MAX_CENTER_RATIO: f32 = 0.2;
+/// Vertical overlap, relative to the smaller line, that makes two engine lines one
+const SAME_LINE_OVERLAP_RATIO: f32 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SnappitScriptPosition {
+    Normal,
+    Super,
+    Sub,
+}
+
+/// Unicode symbols and the LaTeX commands they stand for
+#[rustfmt::skip]
+const SYMBOLS: &[(char, &str)] = &[
+    ('α', "\\alpha"), ('β', "\\beta"), ('γ', "\\gamma"), ('δ', "\\delta"),
+    ('ε', "\\varepsilon"), ('ϵ', "\\epsilon"), ('ζ', "\\zeta"), ('η', "\\eta"),
+    ('θ', "\\theta"), ('ϑ', "\\vartheta"), ('ι', "\\iota"), ('κ', "\\kappa"),
+    ('λ', "\\lambda"), ('μ', "\\mu"), ('ν', "\\nu"), ('ξ', "\\xi"), ('π', "\\pi"),
+    ('ρ', "\\rho"), ('σ', "\\sigma"), ('ς', "\\varsigma"), ('τ', "\\tau"),
+    ('υ', "\\upsilon"), ('φ', "\\varphi"), ('ϕ', "\\phi"), ('χ', "\\chi"),
+    ('ψ', "\\psi"), ('ω', "\\omega"),
+    ('Γ', "\\Gamma"), ('Δ', "\\Delta"), ('Θ', "\\Theta"), ('Λ', "\\Lambda"),
+    ('Ξ', "\\Xi"), ('Π', "\\Pi"), ('Σ', "\\Sigma"), ('Υ', "\\Upsilon"),
+    ('Φ', "\\Phi"), ('Ψ', "\\Psi"), ('Ω', "\\Omega"),
+    ('∞', "\\infty"), ('±', "\\pm"), ('∓', "\\mp"), ('×', "\\times"), ('÷', "\\div"),
+    ('·', "\\cdot"), ('∘', "\\circ"), ('−', "-"), ('≤', "\\leq"), ('≥', "\\geq"),
+    ('≠', "\\neq"), ('≈', "\\approx"), ('≡', "\\equiv"), ('∼', "\\sim"),
+    ('∝', "\\propto"), ('→', "\\to"), ('←', "\\leftarrow"), ('↔', "\\leftrightarrow"),
+    ('⇒', "\\Rightarrow"), ('⇔', "\\Leftrightarrow"), ('∑', "\\sum"), ('∏', "\\prod"),
+    ('∫', "\\int"), ('∮', "\\oint"), ('√', "\\sqrt"), ('∂', "\\partial"),
+    ('∇', "\\nabla"), ('∈', "\\in"), ('∉', "\\notin"), ('⊂', "\\subset"),
+    ('⊆', "\\subseteq"), ('∪', "\\cup"), ('∩', "\\cap"), ('∅', "\\emptyset"),
+    ('∀', "\\forall"), ('∃', "\\exists"), ('¬', "\\neg"), ('∧', "\\wedge"),
+    ('∨', "\\vee"), ('′', "'"), ('°', "^\\circ"), ('ℝ', "\\mathbb{R}"),
+    ('ℕ', "\\mathbb{N}"), ('ℤ', "\\mathbb{Z}"), ('ℚ', "\\mathbb{Q}"),
+    ('ℂ', "\\mathbb{C}"), ('½', "\\frac{1}{2}"), ('⅓', "\\frac{1}{3}"),
+    ('⅔', "\\frac{2}{3}"), ('¼', "\\frac{1}{4}"), ('¾', "\\frac{3}{4}"),
+];
+
+/// ASCII spellings engines produce for symbols they can't read
+#[rustfmt::skip]
+const ASCII_SYMBOLS: &[(&str, char)] = &[
+    ("<=>", '⇔'), ("<=", '≤'), (">=", '≥'), ("!=", '≠'), ("+-", '±'), ("->", '→'),
+    ("=>", '⇒'),
+];
+
+#[rustfmt::skip]
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'), ('1', '¹'), ('2', '²'), ('3', '³'), ('4', '⁴'), ('5', '⁵'), ('6', '⁶'),
+    ('7', '⁷'), ('8', '⁸'), ('9', '⁹'), ('+', '⁺'), ('-', '⁻'), ('=', '⁼'), ('(', '⁽'),
+    (')', '⁾'), ('a', 'ᵃ'), ('b', 'ᵇ'), ('c', 'ᶜ'), ('d', 'ᵈ'), ('e', 'ᵉ'), ('f', 'ᶠ'),
+    ('g', 'ᵍ'), ('h', 'ʰ'), ('i', 'ⁱ'), ('j', 'ʲ'), ('k', 'ᵏ'), ('l', 'ˡ'), ('m', 'ᵐ'),
+    ('n', 'ⁿ'), ('o', 'ᵒ'), ('p', 'ᵖ'), ('r', 'ʳ'), ('s', 'ˢ'), ('t', 'ᵗ'), ('u', 'ᵘ'),
+    ('v', 'ᵛ'), ('w', 'ʷ'), ('x', 'ˣ'), ('y', 'ʸ'), ('z', 'ᶻ'), ('T', 'ᵀ'),
+];
+
+#[rustfmt::skip]
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'), ('1', '₁'), ('2', '₂'), ('3', '₃'), ('4', '₄'), ('5', '₅'), ('6', '₆'),
+    ('7', '₇'), ('8', '₈'), ('9', '₉'), ('+', '₊'), ('-', '₋'), ('=', '₌'), ('(', '₍'),
+    (')', '₎'), ('a', 'ₐ'), ('e', 'ₑ'), ('h', 'ₕ'), ('i', 'ᵢ'), ('j', 'ⱼ'), ('k', 'ₖ'),
+    ('l', 'ₗ'), ('m', 'ₘ'), ('n', 'ₙ'), ('o', 'ₒ'), ('p', 'ₚ'), ('r', 'ᵣ'), ('s', 'ₛ'),
+    ('t', 'ₜ'), ('u', 'ᵤ'), ('v', 'ᵥ'), ('x', 'ₓ'),
+];
+
+/// Value at `quantile` (0..=1) of `values`
+fn quantile(mut values: Vec<u32>, quantile: f32) -> Option<u32> {
+    values.sort_unstable();
+    let index = ((values.len().saturating_sub(1)) as f32 * quantile).round() as usize;
+    values.get(index).copied()
+}
+
+fn vertical_overlap(a: &SnappitOcrRect, b: &SnappitOcrRect) -> u32 {
+    a.bottom().min(b.bottom()).saturating_sub(a.y.max(b.y))
+}
+
+/// Words of each line left to right, lines top to bottom. Engines often put a
+/// raised or lowered word on a line of its own, those are joined back
+fn math_lines(blocks: &[SnappitOcrBlock]) -> Vec<Vec<&SnappitOcrWord>> {
+    let mut ocr_lines: Vec<_> = blocks.iter().flat_map(|block| &block.lines).collect();
+    ocr_lines.sort_by_key(|line| line.rect.y);
+
+    let mut lines: Vec<(SnappitOcrRect, Vec<&SnappitOcrWord>)> = Vec::new();
+    for ocr_line in ocr_lines {
+        let words = ocr_line
+            .words
+            .iter()
+            .filter(|word| !word.text.trim().is_empty());
+
+        let joins_previous = lines.last().is_some_and(|(rect, _)| {
+            let smaller = rect.height.min(ocr_line.rect.height) as f32;
+            vertical_overlap(rect, &ocr_line.rect) as f32 >= smaller * SAME_LINE_OVERLAP_RATIO
+        });
+
+        match lines.last_mut() {
+            Some((rect, line)) if joins_previous => {
+                *rect = rect.union(&ocr_line.rect);
+                line.extend(words);
+            }
+            _ => lines.push((ocr_line.rect, words.collect())),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(_, mut words)| {
+            words.sort_by_key(|word| word.rect.x);
+            words
+        })
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+/// Positions of `words` relative to the baseline and height of their line.
+/// Words without ascenders are short too, so the height is taken from the taller
+/// words, and descenders pull bottoms down, so the baseline from the higher ones.
+/// Always one position per word, all normal when the line has no usable heights
+fn script_positions(words: &[&SnappitOcrWord]) -> Vec<SnappitScriptPosition> {
+    let all_normal = || vec![SnappitScriptPosition::Normal; words.len()];
+
+    let Some(height) = quantile(words.iter().map(|word| word.rect.height).collect(), 0.75) else {
+        return all_normal();
+    };
+    let height = height as f32;
+
+    let full_size_bottoms = words
+        .iter()
+        .filter(|word| word.rect.height as f32 > height * SCRIPT_MAX_HEIGHT_RATIO)
+        .map(|word| word.rect.bottom())
+        .collect();
+    let Some(baseline) = quantile(full_size_bottoms, 0.25) else {
+        return all_normal();
+    };
+
+    words
+        .iter()
+        .map(|word| {
+            let rect = &word.rect;
+            // Operators like `=` sit small and raised as well, only letters and digits move
+            let is_small = (rect.height as f32) <= height * SCRIPT_MAX_HEIGHT_RATIO
+                && word.text.chars().any(char::is_alphanumeric)
+                && words.len() > 1;
+            let center = rect.y as f32 + rect.height as f32 / 2.0;
+
+            if is_small
+                && rect.bottom() as f32 <= baseline as f32 - height * SUPERSCRIPT_MIN_RAISE_RATIO
+            {
+                SnappitScriptPosition::Super
+            } else if is_small && center >= baseline as f32 - height * SUBSCRIPT_MAX_CENTER_RATIO {
+                SnappitScriptPosition::Sub
+            } else {
+                SnappitScriptPosition::Normal
+            }
+        })
+        .collect()
+}
+
+fn script_char(c: char, position: SnappitScriptPosition) -> Option<char> {
+    let table = match position {
+        SnappitScriptPosition::Super => SUPERSCRIPTS,
+        SnappitScriptPosition::Sub => SUBSCRIPTS,
+        SnappitScriptPosition::Normal => return Some(c),
+    };
+
+    table
+        .iter()
+        .find(|(plain, _)| *plain == c)
+        .map(|(_, script)| *script)
+}
+
+/// Plain character and position of a Unicode super- or subscript character
+fn plain_char(c: char) -> Option<(char, SnappitScriptPosition)> {
+    let find = |table: &[(char, char)]| {
+        table
+            .iter()
+            .find(|(_, script)| *script == c)
+            .map(|(plain, _)| *plain)
+    };
+
+    find(SUPERSCRIPTS)
+        .map(|plain| (plain, SnappitScriptPosition::Super))
+        .or_else(|| find(SUBSCRIPTS).map(|plain| (plain, SnappitScriptPosition::Sub)))
+}
+
+/// `^2`, `_{n+1}` or, with notation Unicode, `²` and `ₙ₊₁`. Scripts
+/// Unicode has no characters for fall back to `^(q)`
+fn render_script(
+    text: &str,
+    position: SnappitScriptPosition,
+    notation: SnappitMathNotation,
+) -> String {
+    let marker = if position == SnappitScriptPosition::Sub {
+        '_'
+    } else {
+        '^'
+    };
+
+    match notation {
+        SnappitMathNotation::Unicode => {
+            let unicode: Option<String> = text.chars().map(|c| script_char(c, position)).collect();
+
+            unicode.unwrap_or_else(|| match text.chars().count() {
+                1 => format!("{marker}{text}"),
+                _ => format!("{marker}({text})"),
+            })
+        }
+        SnappitMathNotation::Latex => {
+            let latex = map_math_symbols(text, notation);
+
+            match latex.chars().count() {
+                1 => format!("{marker}{latex}"),
+                _ => format!("{marker}{{{latex}}}"),
+            }
+        }
+    }
+}
+
+/// Replaces ASCII spellings like `<=` with their symbols and, with notation LaTeX,
+/// writes symbols and Unicode super/subscripts as commands
+pub fn map_math_symbols(text: &str, notation: SnappitMathNotation) -> String {
+    let mut unicode = text.to_string();
+    for (ascii, symbol) in ASCII_SYMBOLS {
+        unicode = unicode.replace(ascii, &symbol.to_string());
+    }
+
+    if notation == SnappitMathNotation::Unicode {
+        return unicode;
+    }
+
+    let chars: Vec<char> = unicode.chars().collect();
+    let mut latex = String::with_capacity(unicode.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        if let Some((_, position)) = plain_char(chars[i]) {
+            let mut script = String::new();
+            while let Some((plain, _)) = plain_char(chars[i]).filter(|(_, p)| *p == position) {
+                script.push(plain);
+                i += 1;
+                if i == chars.len() {
+                    break;
+                }
+            }
+
+            latex.push_str(&render_script(&script, position, notation));
+            continue;
+        }
+
+        match SYMBOLS.iter().find(|(symbol, _)| *symbol == chars[i]) {
+            Some((_, command)) => {
+                latex.push_str(command);
+                // `\alpha x`, `\alphax` would be an unknown command
+                let next_is_letter = chars.get(i + 1).is_some_and(char::is_ascii_alphabetic);
+                if command.ends_with(|c: char| c.is_ascii_alphabetic()) && next_is_letter {
+                    latex.push(' ');
+                }
+            }
+            None => latex.push(chars[i]),
+        }
+
+        i += 1;
+    }
+
+    latex
+}
+
+/// Formula text of `blocks` with super/subscripts and symbols written as `notation`,
+/// `None` when there are no words
+pub fn math_text(blocks: &[SnappitOcrBlock], notation: SnappitMathNotation) -> Option<String> {
+    let lines = math_lines(blocks);
+    if lines.is_empty() {
+        return None;
+    }
+
+    let text = lines
+        .iter()
+        .map(|words| {
+            let positions = script_positions(words);
+            let mut line = String::new();
+            let mut index = 0;
+
+            while index < words.len() {
+                let position = positions[index];
+                let mut text = words[index].text.trim().to_string();
+                index += 1;
+
+                // Neighbouring words of one script are a single script, `n` `+1`
+                while position != SnappitScriptPosition::Normal
+                    && positions.get(index) == Some(&position)
+                {
+                    text.push_str(words[index].text.trim());
+                    index += 1;
+                }
+
+                if position == SnappitScriptPosition::Normal {
+                    if !line.is_empty() {
+                        line.push(' ');
+                    }
+                    line.push_str(&map_math_symbols(&text, notation));
+                } else {
+                    // Attached to the word before it, whatever the gap
+                    line.push_str(&render_script(&text, position, notation));
+                }
+            }
+
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocr::test_fixtures::{self, block, line};
+
+    use super::*;
+
+    /// Word with 12px wide characters
+    fn word(text: &str, x: u32, y: u32, height: u32) -> SnappitOcrWord {
+        let width = text.chars().count() as u32 * 12;
+        test_fixtures::word(text, SnappitOcrRect::new(x, y, width, height))
+    }
+
+    fn blocks(lines: Vec<Vec<SnappitOcrWord>>) -> Vec<SnappitOcrBlock> {
+        vec![block(lines.into_iter().map(line).collect())]
+    }
+
+    /// `E = mc²` and `x₁ + yₙ₊₁` with the baseline at y = 40
+    fn formulas() -> Vec<SnappitOcrBlock> {
+        blocks(vec![
+            vec![
+                word("E", 0, 20, 20),
+                word("=", 20, 26, 8),
+                word("mc", 40, 26, 14),
+                // Engines tend to put raised words on a line of their own
+            ],
+            vec![word("2", 66, 14, 10)],
+            vec![
+                word("x", 0, 86, 14),
+                word("1", 14, 94, 10),
+                word("+", 30, 88, 10),
+                word("y", 50, 86, 18),
+                word("n", 64, 94, 10),
+                word("+1", 74, 94, 10),
+            ],
+        ])
+    }
+
+    #[test]
+    fn test_math_text_unicode() {
+        assert_eq!(
+            math_text(&formulas(), SnappitMathNotation::Unicode).as_deref(),
+            Some("E = mc²\nx₁ + yₙ₊₁")
+        );
+    }
+
+    #[test]
+    fn test_math_text_latex() {
+        assert_eq!(
+            math_text(&formulas(), SnappitMathNotation::Latex).as_deref(),
+            Some("E = mc^2\nx_1 + y_{n+1}")
+        );
+        assert_eq!(math_text(&[], SnappitMathNotation::Latex), None);
+    }
+
+    #[test]
+    fn test_math_text_zero_height_words() {
+        let flat = blocks(vec![vec![word("a", 0, 20, 0), word("b", 20, 20, 0)]]);
+
+        assert_eq!(
+            math_text(&flat, SnappitMathNotation::Unicode).as_deref(),
+            Some("a b")
+        );
+    }
+
+    #[test]
+    fn test_map_math_symbols() {
+        assert_eq!(
+            map_math_symbols("αx <= π·r²", SnappitMathNotation::Latex),
+            "\\alpha x \\leq \\pi\\cdot r^2"
+        );
+        assert_eq!(
+            map_math_symbols("a != b -> c", SnappitMathNotation::Unicode),
+            "a ≠ b → c"
+        );
+        assert_eq!(
+            map_math_symbols("x₁₂ + ∑", SnappitMathNotation::Latex),
+            "x_{12} + \\sum"
+        );
+    }
+
+    #[test]
+    fn test_render_script_falls_back_without_unicode_characters() {
+        let sup = SnappitScriptPosition::Super;
+
+        assert_eq!(render_script("q", sup, SnappitMathNotation::Unicode), "^q");
+        assert_eq!(
+            render_script("2q", sup, SnappitMathNotation::Unicode),
+            "^(2q)"
+        );
+    }
+}
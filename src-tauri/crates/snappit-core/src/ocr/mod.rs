@@ -0,0 +1,28 @@
+mod code_layout;
+mod config;
+mod engine;
+pub mod language_registry;
+mod math_layout;
+mod ocr_layout;
+mod preprocess;
+pub mod recognition_language;
+mod script_detection;
+mod snappit_ocr;
+mod table_layout;
+mod tesseract_ocr;
+mod tesseract_options;
+mod tesseract_pool;
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+mod vision_ocr;
+
+pub use config::{OcrConfig, SnappitLineBreakMode, SnappitMathNotation};
+pub use engine::OcrEngine;
+pub use language_registry::{SnappitLanguage, SnappitLanguageRegistry, SnappitScript};
+pub use ocr_layout::{parse_tesseract_tsv, SnappitOcrOutput};
+pub use preprocess::{preprocess, SnappitPreprocessStep, SnappitPreprocessTransform};
+pub use snappit_ocr::SnappitOcr;
+pub use table_layout::{format_table, table_rows};
+pub use tesseract_ocr::SnappitTesseractOcr;
+pub use tesseract_options::{SnappitOcrEngineMode, SnappitPageSegMode, SnappitTesseractOptions};
+pub use vision_ocr::SnappitMacOSVisionOcr;
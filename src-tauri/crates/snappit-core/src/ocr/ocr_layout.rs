@@ -0,0 +1,165 @@
+use crate::res::{SnappitOcrBlock, SnappitOcrLine, SnappitOcrRect, SnappitOcrWord};
+
+/// Raw engine output: plain text plus the recognized layout
+#[derive(Debug, Clone, Default)]
+pub struct SnappitOcrOutput {
+    pub text: String,
+    pub blocks: Vec<SnappitOcrBlock>,
+    /// Confidence the engine reports for the whole text, 0..=100. `None` leaves
+    /// it to the word confidences
+    pub confidence: Option<f32>,
+}
+
+const TSV_LEVEL_WORD: u32 = 5;
+const TSV_COLUMNS: usize = 12;
+
+/// Builds blocks/lines/words from Tesseract TSV output.
+///
+/// Columns: level, page, block, par, line, word, left, top, width, height, conf, text.
+/// Paragraphs are flattened into their block, lines and blocks get the union of their word rects.
+pub fn parse_tesseract_tsv(tsv: &str) -> Vec<SnappitOcrBlock> {
+    let mut blocks: Vec<SnappitOcrBlock> = Vec::new();
+    let mut current_block: Option<u32> = None;
+    let mut current_line: Option<(u32, u32, u32)> = None;
+
+    for row in tsv.lines() {
+        let columns: Vec<&str> = row.splitn(TSV_COLUMNS, '\t').collect();
+        if columns.len() < TSV_COLUMNS {
+            continue;
+        }
+
+        // Skips the header row as well as malformed rows
+        let Some(numbers) = parse_numbers(&columns[..10]) else {
+            continue;
+        };
+
+        if numbers[0] != TSV_LEVEL_WORD {
+            continue;
+        }
+
+        let text = columns[11].trim();
+        let confidence = columns[10].trim().parse::<f32>().unwrap_or(-1.0);
+        if text.is_empty() || confidence < 0.0 {
+            continue;
+        }
+
+        let (block_num, par_num, line_num) = (numbers[2], numbers[3], numbers[4]);
+        let rect = SnappitOcrRect::new(numbers[6], numbers[7], numbers[8], numbers[9]);
+        let word = SnappitOcrWord {
+            text: text.to_string(),
+            rect,
+            confidence,
+        };
+
+        if current_block != Some(block_num) || blocks.is_empty() {
+            blocks.push(SnappitOcrBlock {
+                rect,
+                lines: Vec::new(),
+            });
+            current_block = Some(block_num);
+            current_line = None;
+        }
+
+        let block = blocks.last_mut().expect("block was pushed above");
+        block.rect = block.rect.union(&rect);
+
+        if current_line != Some((block_num, par_num, line_num)) || block.lines.is_empty() {
+            block.lines.push(SnappitOcrLine {
+                rect,
+                words: Vec::new(),
+            });
+            current_line = Some((block_num, par_num, line_num));
+        }
+
+        let line = block.lines.last_mut().expect("line was pushed above");
+        line.rect = line.rect.union(&rect);
+        line.words.push(word);
+    }
+
+    blocks
+}
+
+/// Vertical center, words whose centers fall inside another rect share its line
+pub fn center_y(rect: &SnappitOcrRect) -> u32 {
+    rect.y + rect.height / 2
+}
+
+/// Mean confidence of the recognized words, `None` when there are none
+pub fn mean_word_confidence(blocks: &[SnappitOcrBlock]) -> Option<f32> {
+    let confidences: Vec<f32> = blocks
+        .iter()
+        .flat_map(|block| &block.lines)
+        .flat_map(|line| &line.words)
+        .map(|word| word.confidence)
+        .collect();
+
+    if confidences.is_empty() {
+        return None;
+    }
+
+    Some(confidences.iter().sum::<f32>() / confidences.len() as f32)
+}
+
+fn parse_numbers(columns: &[&str]) -> Option<Vec<u32>> {
+    columns
+        .iter()
+        .map(|column| column.trim().parse::<i64>().ok().map(|n| n.max(0) as u32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &SnappitOcrLine) -> String {
+        line.words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    const TSV: &str = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext
+1\t1\t0\t0\t0\t0\t0\t0\t400\t200\t-1\t
+2\t1\t1\t0\t0\t0\t10\t10\t300\t60\t-1\t
+4\t1\t1\t1\t1\t0\t10\t10\t200\t20\t-1\t
+5\t1\t1\t1\t1\t1\t10\t10\t80\t20\t96.5\tHello
+5\t1\t1\t1\t1\t2\t100\t12\t110\t18\t91.0\tWorld
+5\t1\t1\t1\t2\t1\t10\t40\t60\t20\t88.0\tSecond
+5\t1\t1\t1\t2\t2\t80\t40\t60\t20\t-1\t
+5\t1\t2\t1\t1\t1\t20\t150\t50\t20\t70.0\tNext";
+
+    #[test]
+    fn test_parse_tsv_groups_words() {
+        let blocks = parse_tesseract_tsv(TSV);
+
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].lines.len(), 2);
+        assert_eq!(line_text(&blocks[0].lines[0]), "Hello World");
+        assert_eq!(line_text(&blocks[0].lines[1]), "Second");
+        assert_eq!(line_text(&blocks[1].lines[0]), "Next");
+    }
+
+    #[test]
+    fn test_parse_tsv_rects_and_confidence() {
+        let blocks = parse_tesseract_tsv(TSV);
+        let line = &blocks[0].lines[0];
+
+        assert_eq!(line.rect, SnappitOcrRect::new(10, 10, 200, 20));
+        assert_eq!(blocks[0].rect, SnappitOcrRect::new(10, 10, 200, 50));
+        assert_eq!(line.words[1].confidence, 91.0);
+    }
+
+    #[test]
+    fn test_parse_tsv_empty() {
+        assert!(parse_tesseract_tsv("").is_empty());
+    }
+
+    #[test]
+    fn test_mean_word_confidence() {
+        let blocks = parse_tesseract_tsv(TSV);
+
+        assert_eq!(mean_word_confidence(&blocks), Some(86.375));
+        assert_eq!(mean_word_confidence(&[]), None);
+    }
+}
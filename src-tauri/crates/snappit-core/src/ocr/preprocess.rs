@@ -0,0 +1,440 @@
+//! Image preprocessing applied before Tesseract recognition
+//!
+//! Steps always run in the order of [`SnappitPreprocessStep::ORDER`], no matter how
+//! they are listed in the settings. Everything except upscaling works on luma, so
+//! enabling any other step implies grayscale. Upscaling and deskewing move the
+//! text, the returned [`SnappitPreprocessTransform`] maps positions back.
+
+use image::{
+    imageops::{self, FilterType},
+    DynamicImage, GrayImage, Luma,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::res::{SnappitOcrBlock, SnappitOcrRect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitPreprocessStep {
+    Grayscale,
+    /// Inverts light-on-dark captures (dark themes, terminals) so text ends up dark
+    AutoInvert,
+    /// Scales small captures up so glyphs reach the size Tesseract is trained on
+    Upscale,
+    /// Rotates slightly tilted text back to horizontal
+    Deskew,
+    /// Local mean thresholding, copes with gradients and uneven backgrounds
+    Binarize,
+}
+
+impl SnappitPreprocessStep {
+    pub const ORDER: [SnappitPreprocessStep; 5] = [
+        SnappitPreprocessStep::Grayscale,
+        SnappitPreprocessStep::AutoInvert,
+        SnappitPreprocessStep::Upscale,
+        SnappitPreprocessStep::Deskew,
+        SnappitPreprocessStep::Binarize,
+    ];
+}
+
+/// Captures with fewer pixels than this are upscaled 2x
+const UPSCALE_MAX_PIXELS: u32 = 1_500_000;
+/// Captures lower than this (usually a single line of UI text) are upscaled 3x
+const UPSCALE_SMALL_HEIGHT: u32 = 64;
+
+/// Images with a median luma below this are light text on a dark background
+const DARK_BACKGROUND_LUMA: u8 = 128;
+
+/// Threshold window as a fraction of the image width, as in Bradley-Roth
+const BINARIZE_WINDOW_DIVISOR: u32 = 8;
+/// A pixel turns black when it is this many percent darker than its neighbourhood
+const BINARIZE_THRESHOLD_PERCENT: u64 = 15;
+
+const DESKEW_MAX_ANGLE: f32 = 5.0;
+const DESKEW_ANGLE_STEP: f32 = 0.25;
+/// Smaller skew is left alone, rotating would only blur the glyphs
+const DESKEW_MIN_ANGLE: f32 = 0.5;
+/// How much darker than the background a pixel must be to count as ink
+const DESKEW_INK_CONTRAST: u8 = 48;
+const DESKEW_MAX_SAMPLES: usize = 20_000;
+const DESKEW_MIN_SAMPLES: usize = 32;
+
+/// Geometry [`preprocess`] changed, to map positions in the preprocessed image back
+/// onto the original one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnappitPreprocessTransform {
+    /// Upscale factor, 1 when the size was kept
+    pub scale: u32,
+    /// Deskew rotation in degrees around the center of the upscaled image
+    pub angle: f32,
+    /// Size of the preprocessed image
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SnappitPreprocessTransform {
+    fn identity(width: u32, height: u32) -> Self {
+        Self {
+            scale: 1,
+            angle: 0.0,
+            width,
+            height,
+        }
+    }
+
+    /// `rect` in the preprocessed image as a rectangle of the original image. Rotated
+    /// rects become the bounding box of their corners
+    pub fn map_rect(&self, rect: SnappitOcrRect) -> SnappitOcrRect {
+        let (mut left, mut top) = (rect.x as f32, rect.y as f32);
+        let (mut right, mut bottom) = (rect.right() as f32, rect.bottom() as f32);
+
+        if self.angle != 0.0 {
+            // Same mapping `rotate_to_horizontal` samples the source with
+            let (cx, cy) = (self.width as f32 / 2.0, self.height as f32 / 2.0);
+            let (sin, cos) = self.angle.to_radians().sin_cos();
+            let corners = [(left, top), (right, top), (left, bottom), (right, bottom)]
+                .map(|(x, y)| (x - cx, y - cy))
+                .map(|(dx, dy)| (cx + dx * cos - dy * sin, cy + dx * sin + dy * cos));
+            let (xs, ys) = (corners.map(|(x, _)| x), corners.map(|(_, y)| y));
+
+            left = xs.into_iter().fold(f32::MAX, f32::min).max(0.0);
+            top = ys.into_iter().fold(f32::MAX, f32::min).max(0.0);
+            right = xs.into_iter().fold(0.0, f32::max).min(self.width as f32);
+            bottom = ys.into_iter().fold(0.0, f32::max).min(self.height as f32);
+        }
+
+        let scale = self.scale as f32;
+        let (x, y) = ((left / scale).floor() as u32, (top / scale).floor() as u32);
+        let (right, bottom) = (
+            (right / scale).ceil() as u32,
+            (bottom / scale).ceil() as u32,
+        );
+
+        SnappitOcrRect::new(x, y, right.max(x) - x, bottom.max(y) - y)
+    }
+
+    /// Maps the rects of every block, line and word with [`Self::map_rect`]
+    pub fn map_blocks(&self, blocks: &mut [SnappitOcrBlock]) {
+        if self.scale == 1 && self.angle == 0.0 {
+            return;
+        }
+
+        for block in blocks {
+            block.rect = self.map_rect(block.rect);
+            for line in &mut block.lines {
+                line.rect = self.map_rect(line.rect);
+                for word in &mut line.words {
+                    word.rect = self.map_rect(word.rect);
+                }
+            }
+        }
+    }
+}
+
+/// Runs the enabled steps over a copy of `image`
+pub fn preprocess(
+    image: &DynamicImage,
+    steps: &[SnappitPreprocessStep],
+) -> (DynamicImage, SnappitPreprocessTransform) {
+    let needs_luma = steps
+        .iter()
+        .any(|step| *step != SnappitPreprocessStep::Upscale);
+
+    let mut image = if needs_luma {
+        DynamicImage::ImageLuma8(image.to_luma8())
+    } else {
+        image.clone()
+    };
+    let mut transform = SnappitPreprocessTransform::identity(image.width(), image.height());
+
+    for step in SnappitPreprocessStep::ORDER {
+        if !steps.contains(&step) {
+            continue;
+        }
+
+        image = match step {
+            SnappitPreprocessStep::Grayscale => image,
+            SnappitPreprocessStep::AutoInvert => {
+                let mut luma = image.into_luma8();
+                if median_luma(&luma) < DARK_BACKGROUND_LUMA {
+                    imageops::invert(&mut luma);
+                }
+                DynamicImage::ImageLuma8(luma)
+            }
+            SnappitPreprocessStep::Upscale => {
+                let (upscaled, scale) = upscale(image);
+                transform.scale = scale;
+                upscaled
+            }
+            SnappitPreprocessStep::Deskew => {
+                let (deskewed, angle) = deskew(&image.into_luma8());
+                transform.angle = angle;
+                DynamicImage::ImageLuma8(deskewed)
+            }
+            SnappitPreprocessStep::Binarize => {
+                DynamicImage::ImageLuma8(binarize(&image.into_luma8()))
+            }
+        };
+    }
+
+    transform.width = image.width();
+    transform.height = image.height();
+
+    (image, transform)
+}
+
+fn median_luma(image: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in image.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+
+    let half = (image.width() as u64 * image.height() as u64).div_ceil(2);
+    let mut seen = 0u64;
+
+    for (luma, count) in histogram.iter().enumerate() {
+        seen += count;
+        if seen >= half {
+            return luma as u8;
+        }
+    }
+
+    u8::MAX
+}
+
+fn upscale_factor(width: u32, height: u32) -> u32 {
+    if height < UPSCALE_SMALL_HEIGHT {
+        3
+    } else if width.saturating_mul(height) < UPSCALE_MAX_PIXELS {
+        2
+    } else {
+        1
+    }
+}
+
+/// The upscaled image and the factor it was scaled by
+fn upscale(image: DynamicImage) -> (DynamicImage, u32) {
+    let (width, height) = (image.width(), image.height());
+    let factor = upscale_factor(width, height);
+
+    if factor == 1 || width == 0 || height == 0 {
+        return (image, 1);
+    }
+
+    (
+        image.resize_exact(width * factor, height * factor, FilterType::CatmullRom),
+        factor,
+    )
+}
+
+/// Bradley-Roth adaptive threshold over an integral image
+fn binarize(image: &GrayImage) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let stride = width as usize + 1;
+    let radius = (width / BINARIZE_WINDOW_DIVISOR / 2).max(1);
+
+    let mut integral = vec![0u64; stride * (height as usize + 1)];
+    for y in 0..height as usize {
+        let mut row_sum = 0u64;
+        for x in 0..width as usize {
+            row_sum += image.get_pixel(x as u32, y as u32)[0] as u64;
+            integral[(y + 1) * stride + x + 1] = integral[y * stride + x + 1] + row_sum;
+        }
+    }
+
+    GrayImage::from_fn(width, height, |x, y| {
+        let (x0, y0) = (
+            x.saturating_sub(radius) as usize,
+            y.saturating_sub(radius) as usize,
+        );
+        let x1 = (x + radius + 1).min(width) as usize;
+        let y1 = (y + radius + 1).min(height) as usize;
+
+        let area = ((x1 - x0) * (y1 - y0)) as u64;
+        let sum = integral[y1 * stride + x1] + integral[y0 * stride + x0]
+            - integral[y0 * stride + x1]
+            - integral[y1 * stride + x0];
+
+        let value = image.get_pixel(x, y)[0] as u64;
+        if value * area * 100 <= sum * (100 - BINARIZE_THRESHOLD_PERCENT) {
+            Luma([0])
+        } else {
+            Luma([255])
+        }
+    })
+}
+
+/// The straightened image and the angle it was rotated by, 0 when left alone
+fn deskew(image: &GrayImage) -> (GrayImage, f32) {
+    let angle = estimate_skew(image);
+    if angle.abs() < DESKEW_MIN_ANGLE {
+        return (image.clone(), 0.0);
+    }
+
+    (
+        rotate_to_horizontal(image, angle, median_luma(image)),
+        angle,
+    )
+}
+
+/// Finds the text line angle in degrees (positive when lines go down to the right)
+/// by maximizing the sharpness of the horizontal projection profile of ink pixels
+fn estimate_skew(image: &GrayImage) -> f32 {
+    let (width, height) = image.dimensions();
+    let ink_threshold = median_luma(image).saturating_sub(DESKEW_INK_CONTRAST);
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+
+    let ink: Vec<(f32, f32)> = image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[0] < ink_threshold)
+        .map(|(x, y, _)| (x as f32 - cx, y as f32 - cy))
+        .collect();
+
+    if ink.len() < DESKEW_MIN_SAMPLES {
+        return 0.0;
+    }
+
+    let sample_stride = ink.len().div_ceil(DESKEW_MAX_SAMPLES);
+    let offset = (cx.hypot(cy).ceil() as usize) + 1;
+    let mut bins = vec![0u32; offset * 2 + 1];
+
+    let steps = (DESKEW_MAX_ANGLE / DESKEW_ANGLE_STEP) as i32;
+    let mut best = (0.0f32, 0u64);
+
+    // Zero goes first so that flat profiles keep the image untouched
+    for step in (0..=steps).flat_map(|step| [step, -step]).skip(1) {
+        let angle = step as f32 * DESKEW_ANGLE_STEP;
+        let (sin, cos) = angle.to_radians().sin_cos();
+
+        bins.fill(0);
+        for (x, y) in ink.iter().step_by(sample_stride) {
+            let row = (y * cos - x * sin).round() as isize + offset as isize;
+            bins[row as usize] += 1;
+        }
+
+        let score = bins.iter().map(|count| (*count as u64).pow(2)).sum::<u64>();
+        if score > best.1 {
+            best = (angle, score);
+        }
+    }
+
+    best.0
+}
+
+/// Rotates by `-angle` degrees around the center with bilinear sampling
+fn rotate_to_horizontal(image: &GrayImage, angle: f32, fill: u8) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let (cx, cy) = (width as f32 / 2.0, height as f32 / 2.0);
+    let (sin, cos) = angle.to_radians().sin_cos();
+
+    GrayImage::from_fn(width, height, |x, y| {
+        let (dx, dy) = (x as f32 - cx, y as f32 - cy);
+        let sx = cx + dx * cos - dy * sin;
+        let sy = cy + dx * sin + dy * cos;
+
+        if sx < 0.0 || sy < 0.0 || sx > (width - 1) as f32 || sy > (height - 1) as f32 {
+            return Luma([fill]);
+        }
+
+        let (x0, y0) = (sx.floor() as u32, sy.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+        let (fx, fy) = (sx - x0 as f32, sy - y0 as f32);
+
+        let sample = |x: u32, y: u32| image.get_pixel(x, y)[0] as f32;
+        let top = sample(x0, y0) * (1.0 - fx) + sample(x1, y0) * fx;
+        let bottom = sample(x0, y1) * (1.0 - fx) + sample(x1, y1) * fx;
+
+        Luma([(top * (1.0 - fy) + bottom * fy).round() as u8])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White image with dark horizontal stripes tilted by `angle` degrees
+    fn striped_image(angle: f32) -> GrayImage {
+        let slope = angle.to_radians().tan();
+
+        GrayImage::from_fn(400, 200, |x, y| {
+            let row = y as f32 - x as f32 * slope;
+            if row.rem_euclid(40.0) < 6.0 {
+                Luma([20])
+            } else {
+                Luma([235])
+            }
+        })
+    }
+
+    #[test]
+    fn test_auto_invert_dark_background() {
+        let dark = DynamicImage::ImageLuma8(GrayImage::from_pixel(10, 10, Luma([30])));
+        let light = DynamicImage::ImageLuma8(GrayImage::from_pixel(10, 10, Luma([220])));
+
+        let steps = [SnappitPreprocessStep::AutoInvert];
+        assert_eq!(
+            preprocess(&dark, &steps).0.to_luma8().get_pixel(0, 0)[0],
+            225
+        );
+        assert_eq!(
+            preprocess(&light, &steps).0.to_luma8().get_pixel(0, 0)[0],
+            220
+        );
+    }
+
+    #[test]
+    fn test_upscale_small_capture() {
+        let image = DynamicImage::ImageLuma8(GrayImage::new(200, 40));
+        let (upscaled, transform) = preprocess(&image, &[SnappitPreprocessStep::Upscale]);
+
+        assert_eq!((upscaled.width(), upscaled.height()), (600, 120));
+        assert_eq!(transform.scale, 3);
+        assert_eq!(
+            transform.map_rect(SnappitOcrRect::new(30, 30, 61, 30)),
+            SnappitOcrRect::new(10, 10, 21, 10)
+        );
+        assert_eq!(upscale_factor(2000, 1000), 1);
+    }
+
+    #[test]
+    fn test_binarize_keeps_text_dark() {
+        let image = striped_image(0.0);
+        let binary = binarize(&image);
+
+        assert!(binary
+            .pixels()
+            .all(|pixel| pixel[0] == 0 || pixel[0] == 255));
+        assert_eq!(binary.get_pixel(200, 2)[0], 0);
+        assert_eq!(binary.get_pixel(200, 20)[0], 255);
+    }
+
+    #[test]
+    fn test_estimate_skew() {
+        assert_eq!(estimate_skew(&striped_image(0.0)), 0.0);
+        assert!((estimate_skew(&striped_image(3.0)) - 3.0).abs() <= DESKEW_ANGLE_STEP);
+        assert!((estimate_skew(&striped_image(-2.0)) + 2.0).abs() <= DESKEW_ANGLE_STEP);
+    }
+
+    #[test]
+    fn test_deskew_straightens_lines() {
+        let (straightened, angle) = deskew(&striped_image(3.0));
+        assert!(estimate_skew(&straightened).abs() < DESKEW_MIN_ANGLE);
+        assert!((angle - 3.0).abs() <= DESKEW_ANGLE_STEP);
+    }
+
+    #[test]
+    fn test_transform_undoes_deskew() {
+        let transform = SnappitPreprocessTransform {
+            angle: 3.0,
+            ..SnappitPreprocessTransform::identity(400, 200)
+        };
+
+        // The center stays put, text further right was lower before straightening
+        assert_eq!(
+            transform.map_rect(SnappitOcrRect::new(200, 100, 0, 0)),
+            SnappitOcrRect::new(200, 100, 0, 0)
+        );
+        let right = transform.map_rect(SnappitOcrRect::new(380, 100, 10, 10));
+        assert!(right.y >= 108 && right.bottom() <= 120);
+    }
+}
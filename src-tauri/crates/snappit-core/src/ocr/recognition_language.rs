@@ -1,6 +1,4 @@
-use crate::{
-    snappit_consts::SNAPPIT_CONSTS, snappit_errors::SnappitResult, snappit_store::SnappitStore,
-};
+use super::language_registry::SnappitLanguageRegistry;
 
 pub const FALLBACK_RECOGNITION_LANGUAGE: &str = "eng";
 
@@ -39,46 +37,7 @@ pub fn get_system_recognition_languages() -> Vec<String> {
 }
 
 fn map_locale_to_recognition_code(locale: &str) -> Option<&'static str> {
-    let normalized = locale.trim();
-    if normalized.is_empty() {
-        return None;
-    }
-
-    let normalized = normalized.replace('_', "-");
-    let segments: Vec<String> = normalized
-        .split('-')
-        .map(|segment| segment.trim().to_ascii_lowercase())
-        .filter(|segment| !segment.is_empty())
-        .collect();
-
-    let Some(language) = segments.get(0) else {
-        return None;
-    };
-
-    match language.as_str() {
-        "en" => Some("eng"),
-        "ru" => Some("rus"),
-        "zh" => {
-            let is_traditional = segments
-                .iter()
-                .skip(1)
-                .any(|segment| matches!(segment.as_str(), "hant" | "tw" | "hk" | "mo"));
-
-            if is_traditional {
-                Some("chi_tra")
-            } else {
-                Some("chi_sim")
-            }
-        }
-        "ja" => Some("jpn"),
-        "ko" => Some("kor"),
-        "es" => Some("spa"),
-        "fr" => Some("fra"),
-        "de" => Some("deu"),
-        "th" => Some("tha"),
-        "ka" => Some("kat"),
-        _ => None,
-    }
+    SnappitLanguageRegistry::from_locale(locale).map(|language| language.tesseract)
 }
 
 pub fn sanitize_recognition_language(raw: &str) -> Option<String> {
@@ -98,25 +57,26 @@ pub fn sanitize_recognition_language(raw: &str) -> Option<String> {
     }
 }
 
-pub fn resolve_recognition_language(app: &tauri::AppHandle) -> SnappitResult<String> {
-    let key = SNAPPIT_CONSTS.store.keys.recognition_lang.as_str();
-    let value =
-        SnappitStore::get_value(app, key)?.and_then(|stored| stored.as_str().map(String::from));
+/// Whether the stored `recognition_lang` setting leaves the choice to Snappit:
+/// missing, empty or `auto`
+pub fn is_auto_recognition_language(stored: Option<&str>) -> bool {
+    stored.is_none_or(|lang| {
+        let trimmed = lang.trim();
+        trimmed.eq_ignore_ascii_case("auto") || trimmed.is_empty()
+    })
+}
 
+/// Resolves the stored `recognition_lang` setting, treating empty values and `auto`
+/// as the languages of the system locales
+pub fn resolve_recognition_language(stored: Option<&str>) -> String {
     let default_language = default_recognition_language();
-    let lang = match value {
-        Some(lang) => {
-            let trimmed = lang.trim();
-            if trimmed.eq_ignore_ascii_case("auto") || trimmed.is_empty() {
-                default_language
-            } else {
-                sanitize_recognition_language(trimmed).unwrap_or(default_language)
-            }
-        }
-        None => default_language,
-    };
 
-    Ok(lang)
+    match stored {
+        Some(lang) if !is_auto_recognition_language(stored) => {
+            sanitize_recognition_language(lang.trim()).unwrap_or(default_language)
+        }
+        _ => default_language,
+    }
 }
 
 pub fn split_recognition_languages(value: &str) -> Vec<String> {
@@ -144,3 +104,24 @@ pub fn languages_match_system(languages: &[String], system_languages: &[String])
             .any(|system_lang| system_lang.eq_ignore_ascii_case(lang))
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_recognition_language() {
+        assert_eq!(
+            resolve_recognition_language(Some(" ENG + deu+eng ")),
+            "eng+deu"
+        );
+        assert_eq!(
+            resolve_recognition_language(Some("auto")),
+            default_recognition_language()
+        );
+        assert_eq!(
+            resolve_recognition_language(None),
+            default_recognition_language()
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Picks traineddata for the script an image is written in
+//!
+//! Tesseract reads one script per model, so an English-only setup turns a Japanese
+//! screenshot into Latin garbage. A first pass with one installed language per
+//! script still gets most characters of each script right, which is enough to
+//! count them and pick the languages for a second pass.
+
+use std::collections::HashMap;
+
+use super::language_registry::{SnappitLanguageRegistry, SnappitScript};
+
+/// Fewer recognized letters than this are too little to tell scripts apart
+const MIN_SCRIPT_CHARS: usize = 3;
+/// Languages combined in the first pass, each one slows Tesseract down
+const MAX_PROBE_LANGUAGES: usize = 4;
+/// Languages of the detected script used for the second pass
+const MAX_DETECTED_LANGUAGES: usize = 3;
+
+/// Script of a single character, `None` for digits, punctuation and symbols
+fn char_script(c: char) -> Option<SnappitScript> {
+    let script = match c {
+        'A'..='Z' | 'a'..='z' => SnappitScript::Latin,
+        '\u{00C0}'..='\u{024F}' | '\u{1E00}'..='\u{1EFF}' if c.is_alphabetic() => {
+            SnappitScript::Latin
+        }
+        '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => SnappitScript::Greek,
+        '\u{0400}'..='\u{052F}' => SnappitScript::Cyrillic,
+        '\u{0530}'..='\u{058F}' => SnappitScript::Armenian,
+        '\u{0590}'..='\u{05FF}' => SnappitScript::Hebrew,
+        '\u{0600}'..='\u{06FF}'
+        | '\u{0750}'..='\u{077F}'
+        | '\u{08A0}'..='\u{08FF}'
+        | '\u{FB50}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}' => SnappitScript::Arabic,
+        '\u{0700}'..='\u{074F}' => SnappitScript::Syriac,
+        '\u{0780}'..='\u{07BF}' => SnappitScript::Thaana,
+        '\u{0900}'..='\u{097F}' => SnappitScript::Devanagari,
+        '\u{0980}'..='\u{09FF}' => SnappitScript::Bengali,
+        '\u{0A00}'..='\u{0A7F}' => SnappitScript::Gurmukhi,
+        '\u{0A80}'..='\u{0AFF}' => SnappitScript::Gujarati,
+        '\u{0B00}'..='\u{0B7F}' => SnappitScript::Oriya,
+        '\u{0B80}'..='\u{0BFF}' => SnappitScript::Tamil,
+        '\u{0C00}'..='\u{0C7F}' => SnappitScript::Telugu,
+        '\u{0C80}'..='\u{0CFF}' => SnappitScript::Kannada,
+        '\u{0D00}'..='\u{0D7F}' => SnappitScript::Malayalam,
+        '\u{0D80}'..='\u{0DFF}' => SnappitScript::Sinhala,
+        '\u{0E00}'..='\u{0E7F}' => SnappitScript::Thai,
+        '\u{0E80}'..='\u{0EFF}' => SnappitScript::Lao,
+        '\u{0F00}'..='\u{0FFF}' => SnappitScript::Tibetan,
+        '\u{1000}'..='\u{109F}' => SnappitScript::Myanmar,
+        '\u{10A0}'..='\u{10FF}' | '\u{2D00}'..='\u{2D2F}' => SnappitScript::Georgian,
+        '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}' | '\u{AC00}'..='\u{D7AF}' => {
+            SnappitScript::Hangul
+        }
+        '\u{1200}'..='\u{139F}' => SnappitScript::Ethiopic,
+        '\u{13A0}'..='\u{13FF}' => SnappitScript::Cherokee,
+        '\u{1400}'..='\u{167F}' => SnappitScript::CanadianAboriginal,
+        '\u{1780}'..='\u{17FF}' => SnappitScript::Khmer,
+        // Kana only, ideographs are counted as Han and folded in below
+        '\u{3040}'..='\u{30FF}' | '\u{31F0}'..='\u{31FF}' | '\u{FF66}'..='\u{FF9F}' => {
+            SnappitScript::Japanese
+        }
+        '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+            SnappitScript::Han
+        }
+        _ => return None,
+    };
+
+    Some(script)
+}
+
+/// The script most letters of `text` are written in
+pub fn detect_script(text: &str) -> Option<SnappitScript> {
+    let mut counts: HashMap<SnappitScript, usize> = HashMap::new();
+    for script in text.chars().filter_map(char_script) {
+        *counts.entry(script).or_default() += 1;
+    }
+
+    // Japanese mixes kana with ideographs, Chinese text has no kana at all
+    if let Some(kana) = counts.get(&SnappitScript::Japanese).copied() {
+        let han = counts.remove(&SnappitScript::Han).unwrap_or_default();
+        counts.insert(SnappitScript::Japanese, kana + han);
+    }
+
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= MIN_SCRIPT_CHARS)
+        .max_by_key(|(_, count)| *count)
+        .map(|(script, _)| script)
+}
+
+/// `preferred` first, then the rest of `installed`, without duplicates
+fn ordered<'a>(installed: &'a [String], preferred: &'a [String]) -> Vec<&'a str> {
+    let mut ordered: Vec<&str> = Vec::new();
+
+    for lang in preferred.iter().filter(|lang| installed.contains(lang)) {
+        if !ordered.contains(&lang.as_str()) {
+            ordered.push(lang);
+        }
+    }
+    for lang in installed {
+        if !ordered.contains(&lang.as_str()) {
+            ordered.push(lang);
+        }
+    }
+
+    ordered
+}
+
+fn text_script(lang: &str) -> Option<SnappitScript> {
+    let script = SnappitLanguageRegistry::find(lang)?.script;
+
+    match script {
+        SnappitScript::Common | SnappitScript::Math => None,
+        // Fraktur is still Latin as far as the recognized characters go
+        SnappitScript::Fraktur => Some(SnappitScript::Latin),
+        script => Some(script),
+    }
+}
+
+/// One installed language per script for the first pass, `preferred` ones first
+pub fn probe_languages(installed: &[String], preferred: &[String]) -> Vec<String> {
+    let mut scripts: Vec<SnappitScript> = Vec::new();
+    let mut probe: Vec<String> = Vec::new();
+
+    for lang in ordered(installed, preferred) {
+        let Some(script) = text_script(lang) else {
+            continue;
+        };
+
+        if !scripts.contains(&script) && probe.len() < MAX_PROBE_LANGUAGES {
+            scripts.push(script);
+            probe.push(lang.to_string());
+        }
+    }
+
+    probe
+}
+
+/// Installed languages written in `script`. When some of the `preferred` ones
+/// are, only those are used, so Latin text on an English system stays `eng`
+pub fn languages_for_script(
+    script: SnappitScript,
+    installed: &[String],
+    preferred: &[String],
+) -> Vec<String> {
+    let matching = |script: SnappitScript| -> Vec<&str> {
+        ordered(installed, preferred)
+            .into_iter()
+            .filter(|lang| text_script(lang) == Some(script))
+            .collect()
+    };
+
+    let mut candidates = matching(script);
+    // Chinese text read with Japanese models beats Latin garbage
+    if candidates.is_empty() && script == SnappitScript::Han {
+        candidates = matching(SnappitScript::Japanese);
+    }
+
+    let preferred_matches: Vec<&str> = candidates
+        .iter()
+        .copied()
+        .filter(|lang| preferred.iter().any(|p| p == lang))
+        .collect();
+    if !preferred_matches.is_empty() {
+        candidates = preferred_matches;
+    }
+
+    candidates
+        .into_iter()
+        .take(MAX_DETECTED_LANGUAGES)
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn langs(codes: &[&str]) -> Vec<String> {
+        codes.iter().map(|code| code.to_string()).collect()
+    }
+
+    #[test]
+    fn test_detect_script() {
+        assert_eq!(detect_script("Hello, world"), Some(SnappitScript::Latin));
+        assert_eq!(
+            detect_script("Привіт, світе"),
+            Some(SnappitScript::Cyrillic)
+        );
+        assert_eq!(detect_script("東京都の天気"), Some(SnappitScript::Japanese));
+        assert_eq!(detect_script("北京市天气"), Some(SnappitScript::Han));
+        assert_eq!(detect_script("مرحبا بالعالم"), Some(SnappitScript::Arabic));
+        assert_eq!(
+            detect_script("v1 東京都の天気"),
+            Some(SnappitScript::Japanese)
+        );
+        assert_eq!(detect_script("12 + 34"), None);
+    }
+
+    #[test]
+    fn test_probe_languages() {
+        let installed = langs(&["deu", "eng", "jpn", "osd", "rus", "ukr"]);
+        assert_eq!(
+            probe_languages(&installed, &langs(&["eng"])),
+            langs(&["eng", "jpn", "rus"])
+        );
+    }
+
+    #[test]
+    fn test_languages_for_script() {
+        let installed = langs(&["deu", "eng", "jpn", "rus", "ukr"]);
+        let preferred = langs(&["eng"]);
+
+        assert_eq!(
+            languages_for_script(SnappitScript::Latin, &installed, &preferred),
+            langs(&["eng"])
+        );
+        assert_eq!(
+            languages_for_script(SnappitScript::Cyrillic, &installed, &preferred),
+            langs(&["rus", "ukr"])
+        );
+        assert_eq!(
+            languages_for_script(SnappitScript::Han, &installed, &preferred),
+            langs(&["jpn"])
+        );
+        assert!(languages_for_script(SnappitScript::Arabic, &installed, &preferred).is_empty());
+    }
+}
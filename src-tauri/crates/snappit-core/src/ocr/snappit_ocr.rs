@@ -0,0 +1,469 @@
+use std::borrow::Cow;
+
+use image::DynamicImage;
+use log::warn;
+
+use crate::{
+    errors::SnappitCoreResult,
+    res::{SnappitOcrEngine, SnappitOcrResult, SnappitTableFormat, SnappitTableResult},
+    text_processing::{
+        collapse_line_breaks, fix_code_symbols, normalize_nfc, reading_order_text,
+        reflow_paragraphs, remove_non_spaced_script_spaces, strip_bidi_controls,
+    },
+};
+
+use super::code_layout::{code_text, fence_code};
+use super::math_layout::{map_math_symbols, math_text};
+use super::ocr_layout::mean_word_confidence;
+#[cfg(target_os = "macos")]
+use super::recognition_language::{get_system_recognition_languages, languages_match_system};
+use super::script_detection::{detect_script, languages_for_script, probe_languages};
+use super::table_layout::{format_table, table_rows};
+use super::{
+    preprocess, OcrConfig, OcrEngine, SnappitLineBreakMode, SnappitMacOSVisionOcr,
+    SnappitPageSegMode, SnappitPreprocessStep, SnappitTesseractOcr,
+};
+
+/// Steps added to the configured ones when a Tesseract pass is retried
+const RETRY_PREPROCESS_STEPS: [SnappitPreprocessStep; 4] = [
+    SnappitPreprocessStep::Grayscale,
+    SnappitPreprocessStep::AutoInvert,
+    SnappitPreprocessStep::Upscale,
+    SnappitPreprocessStep::Binarize,
+];
+
+pub struct SnappitOcr;
+
+impl SnappitOcr {
+    /// Recognizes text with the best engine for the configured languages:
+    /// Vision when they match the system languages on macOS, Tesseract otherwise.
+    /// Character white/blacklists always go to Tesseract, Vision can't honour them.
+    /// With [`OcrConfig::detect_language`] both engines detect the language themselves
+    pub fn recognize(
+        config: &OcrConfig,
+        image: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrResult> {
+        if !config.tesseract.restricts_characters()
+            && Self::should_use_macos_vision(&config.languages)
+        {
+            match Self::recognize_with(&SnappitMacOSVisionOcr, config, image) {
+                Ok(result) => return Ok(Self::retry_low_confidence(config, image, result)),
+                Err(err) => {
+                    warn!("macOS Vision OCR unavailable, falling back to Tesseract: {err}");
+                }
+            }
+        }
+
+        Self::recognize_with_tesseract(config, image)
+    }
+
+    /// Recognizes text with Tesseract, detecting the script first when
+    /// [`OcrConfig::detect_language`] is set
+    pub fn recognize_with_tesseract(
+        config: &OcrConfig,
+        image: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrResult> {
+        let result = Self::tesseract_pass(config, image)?;
+
+        Ok(Self::retry_low_confidence(config, image, result))
+    }
+
+    /// A single recognition, low confidence results are left as they are
+    fn tesseract_pass(
+        config: &OcrConfig,
+        image: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrResult> {
+        let installed = if config.detect_language {
+            config
+                .resolved_tessdata_dir()
+                .map(|dir| SnappitTesseractOcr::installed_languages(&dir))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let probe = probe_languages(&installed, &config.languages);
+        // A single installed script leaves nothing to detect
+        if probe.len() < 2 {
+            return Self::recognize_with(&SnappitTesseractOcr, config, image);
+        }
+
+        let probe_config = OcrConfig {
+            languages: probe,
+            ..config.clone()
+        };
+        let first_pass = Self::recognize_with(&SnappitTesseractOcr, &probe_config, image)?;
+
+        let languages = detect_script(&first_pass.value)
+            .map(|script| languages_for_script(script, &installed, &config.languages))
+            .unwrap_or_default();
+        if languages.is_empty() {
+            return Ok(first_pass);
+        }
+
+        let detected_language = Some(languages.join("+"));
+        let detected_config = OcrConfig {
+            languages,
+            ..config.clone()
+        };
+
+        Ok(SnappitOcrResult {
+            detected_language,
+            ..Self::recognize_with(&SnappitTesseractOcr, &detected_config, image)?
+        })
+    }
+
+    /// With [`OcrConfig::retry_low_confidence`], recognizes a low confidence
+    /// `result` once more and keeps the retry when [`Self::retry_wins`].
+    /// Vision results are retried with Tesseract, Tesseract ones with more preprocessing
+    fn retry_low_confidence(
+        config: &OcrConfig,
+        image: &DynamicImage,
+        result: SnappitOcrResult,
+    ) -> SnappitOcrResult {
+        if !config.retry_low_confidence || !result.low_confidence {
+            return result;
+        }
+
+        let retry = match result.ocr {
+            SnappitOcrEngine::Vision => Self::tesseract_pass(config, image),
+            SnappitOcrEngine::Tesseract => match Self::retry_config(config) {
+                Some(retry_config) => Self::tesseract_pass(&retry_config, image),
+                None => return result,
+            },
+        };
+
+        match retry {
+            Ok(retried) if Self::retry_wins(config, &result, &retried) => SnappitOcrResult {
+                retried: true,
+                ..retried
+            },
+            Ok(_) => result,
+            Err(err) => {
+                warn!("Retrying low confidence OCR failed: {err}");
+                result
+            }
+        }
+    }
+
+    /// Confidences are only comparable within one engine: a Tesseract retry of a
+    /// Tesseract pass has to be more confident, one of a Vision pass has to clear
+    /// the minimum confidence on its own
+    fn retry_wins(
+        config: &OcrConfig,
+        result: &SnappitOcrResult,
+        retried: &SnappitOcrResult,
+    ) -> bool {
+        if retried.ocr == result.ocr {
+            return retried.confidence > result.confidence;
+        }
+
+        config
+            .min_confidence
+            .zip(retried.confidence)
+            .is_some_and(|(min_confidence, confidence)| confidence >= min_confidence)
+    }
+
+    /// `config` with the retry steps added, `None` when all of them already run
+    fn retry_config(config: &OcrConfig) -> Option<OcrConfig> {
+        let missing: Vec<SnappitPreprocessStep> = RETRY_PREPROCESS_STEPS
+            .into_iter()
+            .filter(|step| !config.preprocess.contains(step))
+            .collect();
+        if missing.is_empty() {
+            return None;
+        }
+
+        let mut config = config.clone();
+        config.preprocess.extend(missing);
+
+        Some(config)
+    }
+
+    /// Recognizes a table and rebuilds its rows and columns from word positions
+    pub fn recognize_table(
+        config: &OcrConfig,
+        image: &DynamicImage,
+        format: SnappitTableFormat,
+    ) -> SnappitCoreResult<SnappitTableResult> {
+        let result = Self::recognize(&Self::table_config(config), image)?;
+
+        Ok(Self::table_from_result(&result, format))
+    }
+
+    /// Tesseract reads tables as sparse text unless a mode is configured,
+    /// its automatic layout splits columns into paragraphs and drops empty cells
+    pub fn table_config(config: &OcrConfig) -> OcrConfig {
+        let mut config = config.clone();
+        if config.tesseract.page_seg_mode == SnappitPageSegMode::Auto {
+            config.tesseract.page_seg_mode = SnappitPageSegMode::SparseText;
+        }
+
+        config
+    }
+
+    /// Groups the recognized words into table cells and formats them as `format`
+    pub fn table_from_result(
+        result: &SnappitOcrResult,
+        format: SnappitTableFormat,
+    ) -> SnappitTableResult {
+        let is_tesseract = result.ocr == SnappitOcrEngine::Tesseract;
+
+        let rows: Vec<Vec<String>> = table_rows(&result.blocks)
+            .into_iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| {
+                        Self::process_text(cell, SnappitLineBreakMode::Collapse, is_tesseract)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        SnappitTableResult {
+            value: format_table(&rows, format),
+            format,
+            rows,
+            ocr: result.ocr,
+        }
+    }
+
+    /// Recognizes text with a specific engine
+    pub fn recognize_with(
+        engine: &dyn OcrEngine,
+        config: &OcrConfig,
+        image: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrResult> {
+        let ocr = engine.kind();
+        let config = if config.code {
+            Cow::Owned(Self::code_config(config))
+        } else if config.math.is_some() {
+            Cow::Owned(Self::math_config(config))
+        } else {
+            Cow::Borrowed(config)
+        };
+
+        // Vision normalizes the image itself and does worse on binarized input
+        let output = if ocr == SnappitOcrEngine::Tesseract && !config.preprocess.is_empty() {
+            let (preprocessed, transform) = preprocess(image, &config.preprocess);
+            let mut output = engine.recognize(&config, &preprocessed)?;
+            // Word positions are reported against the capture, not the upscaled copy
+            transform.map_blocks(&mut output.blocks);
+            output
+        } else {
+            engine.recognize(&config, image)?
+        };
+
+        let value = if config.code {
+            let code = code_text(&output.blocks).unwrap_or_else(|| output.text.clone());
+            Self::process_code(&code, config.code_fence)
+        } else if let Some(notation) = config.math {
+            let math = math_text(&output.blocks, notation)
+                .unwrap_or_else(|| map_math_symbols(&output.text, notation));
+            normalize_nfc(&strip_bidi_controls(&math))
+                .trim()
+                .to_string()
+        } else {
+            // Word positions give the reading order engines get wrong in RTL and vertical text
+            let text = reading_order_text(&output.text, &output.blocks);
+            Self::process_text(
+                &text,
+                config.line_breaks,
+                ocr == SnappitOcrEngine::Tesseract,
+            )
+        };
+
+        let confidence = output
+            .confidence
+            .or_else(|| mean_word_confidence(&output.blocks));
+        let low_confidence = config
+            .min_confidence
+            .zip(confidence)
+            .is_some_and(|(min_confidence, confidence)| confidence < min_confidence);
+
+        Ok(SnappitOcrResult {
+            value,
+            ocr,
+            blocks: output.blocks,
+            detected_language: None,
+            confidence,
+            low_confidence,
+            retried: false,
+        })
+    }
+
+    pub fn process_text(
+        text: &str,
+        line_breaks: SnappitLineBreakMode,
+        is_tesseract: bool,
+    ) -> String {
+        let mut text = normalize_nfc(&strip_bidi_controls(text));
+
+        if is_tesseract {
+            text = remove_non_spaced_script_spaces(&text);
+        }
+
+        match line_breaks {
+            SnappitLineBreakMode::Keep => text,
+            SnappitLineBreakMode::Collapse => collapse_line_breaks(&text),
+            SnappitLineBreakMode::Reflow => reflow_paragraphs(&text),
+        }
+    }
+
+    /// Code keeps its whitespace, only invisible marks and typographic symbols change
+    pub fn process_code(code: &str, fence: bool) -> String {
+        let code = fix_code_symbols(&normalize_nfc(&strip_bidi_controls(code)));
+        let code = code
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let code = code.trim_matches('\n');
+
+        if fence {
+            fence_code(code)
+        } else {
+            code.to_string()
+        }
+    }
+
+    /// Code is read as one block of text, without preferring dictionary words
+    fn code_config(config: &OcrConfig) -> OcrConfig {
+        let mut config = config.clone();
+        if config.tesseract.page_seg_mode == SnappitPageSegMode::Auto {
+            config.tesseract.page_seg_mode = SnappitPageSegMode::SingleBlock;
+        }
+        config.tesseract.disable_dictionary = true;
+        config.tesseract.preserve_interword_spaces = true;
+
+        config
+    }
+
+    /// Formulas are mostly single letters and symbols that no dictionary knows
+    fn math_config(config: &OcrConfig) -> OcrConfig {
+        let mut config = config.clone();
+        config.tesseract.disable_dictionary = true;
+
+        config
+    }
+
+    fn should_use_macos_vision(languages: &[String]) -> bool {
+        #[cfg(target_os = "macos")]
+        {
+            let system_languages = get_system_recognition_languages();
+            languages_match_system(languages, &system_languages)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = languages;
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{GrayImage, Luma};
+
+    use crate::{
+        ocr::{
+            test_fixtures::{block, line, word},
+            SnappitOcrOutput,
+        },
+        res::SnappitOcrRect,
+    };
+
+    use super::*;
+
+    /// Reports the bounding box of the dark pixels as a single word
+    struct InkEngine;
+
+    impl OcrEngine for InkEngine {
+        fn kind(&self) -> SnappitOcrEngine {
+            SnappitOcrEngine::Tesseract
+        }
+
+        fn recognize(
+            &self,
+            _config: &OcrConfig,
+            image: &DynamicImage,
+        ) -> SnappitCoreResult<SnappitOcrOutput> {
+            let rect = image
+                .to_luma8()
+                .enumerate_pixels()
+                .filter(|(_, _, pixel)| pixel[0] < 128)
+                .map(|(x, y, _)| SnappitOcrRect::new(x, y, 1, 1))
+                .reduce(|rect, pixel| rect.union(&pixel))
+                .unwrap_or_default();
+
+            Ok(SnappitOcrOutput {
+                text: "ink".to_string(),
+                blocks: vec![block(vec![line(vec![word("ink", rect)])])],
+                confidence: None,
+            })
+        }
+    }
+
+    fn result(ocr: SnappitOcrEngine, confidence: f32) -> SnappitOcrResult {
+        SnappitOcrResult {
+            value: "text".to_string(),
+            ocr,
+            blocks: Vec::new(),
+            detected_language: None,
+            confidence: Some(confidence),
+            low_confidence: confidence < 80.0,
+            retried: false,
+        }
+    }
+
+    #[test]
+    fn test_retry_wins() {
+        let config = OcrConfig {
+            min_confidence: Some(80.0),
+            ..OcrConfig::default()
+        };
+        let vision = result(SnappitOcrEngine::Vision, 40.0);
+        let tesseract = |confidence| result(SnappitOcrEngine::Tesseract, confidence);
+
+        // More confident than Vision claims to be, but still below the minimum
+        assert!(!SnappitOcr::retry_wins(&config, &vision, &tesseract(60.0)));
+        assert!(SnappitOcr::retry_wins(&config, &vision, &tesseract(85.0)));
+
+        assert!(!SnappitOcr::retry_wins(
+            &config,
+            &tesseract(50.0),
+            &tesseract(45.0)
+        ));
+        assert!(SnappitOcr::retry_wins(
+            &config,
+            &tesseract(50.0),
+            &tesseract(60.0)
+        ));
+    }
+
+    #[test]
+    fn test_upscaled_rects_match_capture() {
+        let image = DynamicImage::ImageLuma8(GrayImage::from_fn(200, 40, |x, y| {
+            if (20..80).contains(&x) && (10..30).contains(&y) {
+                Luma([0])
+            } else {
+                Luma([255])
+            }
+        }));
+        let rects = |preprocess: Vec<SnappitPreprocessStep>| {
+            let config = OcrConfig {
+                preprocess,
+                ..OcrConfig::default()
+            };
+            let result = SnappitOcr::recognize_with(&InkEngine, &config, &image).unwrap();
+            let line = &result.blocks[0].lines[0];
+
+            (result.blocks[0].rect, line.rect, line.words[0].rect)
+        };
+
+        let plain = rects(Vec::new());
+        assert_eq!(plain.2, SnappitOcrRect::new(20, 10, 60, 20));
+        assert_eq!(rects(vec![SnappitPreprocessStep::Upscale]), plain);
+    }
+}
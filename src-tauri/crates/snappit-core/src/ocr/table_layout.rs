@@ -0,0 +1,307 @@
+//! Rebuilds the grid of a captured table from word positions
+//!
+//! Words whose vertical centers fall into the same band form a row. Inside a
+//! row, words closer than a few letters apart form a cell. Columns are the
+//! horizontal ranges covered by cells, with gaps between them that no cell of
+//! a multi-cell row crosses, so titles spanning the whole table don't glue all
+//! columns together.
+
+use crate::res::{SnappitOcrBlock, SnappitOcrRect, SnappitOcrWord, SnappitTableFormat};
+
+use super::ocr_layout::center_y;
+
+/// Horizontal gap between words, relative to their height, that starts a new cell
+const CELL_GAP_RATIO: f32 = 0.8;
+
+struct SnappitTableWord<'a> {
+    word: &'a SnappitOcrWord,
+    /// Engine line the word was recognized in, engines rarely join cells into one line
+    line: usize,
+}
+
+struct SnappitTableCell {
+    rect: SnappitOcrRect,
+    text: String,
+}
+
+fn table_words(blocks: &[SnappitOcrBlock]) -> Vec<SnappitTableWord<'_>> {
+    blocks
+        .iter()
+        .flat_map(|block| &block.lines)
+        .enumerate()
+        .flat_map(|(line, ocr_line)| {
+            ocr_line
+                .words
+                .iter()
+                .map(move |word| SnappitTableWord { word, line })
+        })
+        .filter(|table_word| !table_word.word.text.trim().is_empty())
+        .collect()
+}
+
+fn group_rows(mut words: Vec<SnappitTableWord<'_>>) -> Vec<Vec<SnappitTableWord<'_>>> {
+    words.sort_by_key(|table_word| center_y(&table_word.word.rect));
+
+    let mut rows: Vec<(SnappitOcrRect, Vec<SnappitTableWord>)> = Vec::new();
+    for table_word in words {
+        let rect = table_word.word.rect;
+
+        match rows.last_mut() {
+            Some((band, row)) if center_y(&rect) <= band.bottom() => {
+                *band = band.union(&rect);
+                row.push(table_word);
+            }
+            _ => rows.push((rect, vec![table_word])),
+        }
+    }
+
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+fn split_cells(mut row: Vec<SnappitTableWord<'_>>) -> Vec<SnappitTableCell> {
+    row.sort_by_key(|table_word| table_word.word.rect.x);
+
+    let mut cells: Vec<SnappitTableCell> = Vec::new();
+    let mut last_line = None;
+
+    for table_word in row {
+        let word = table_word.word;
+
+        let joins_cell = cells.last().is_some_and(|cell| {
+            let gap = word.rect.x.saturating_sub(cell.rect.right()) as f32;
+            let height = word.rect.height.max(cell.rect.height) as f32;
+
+            last_line == Some(table_word.line) && gap <= height * CELL_GAP_RATIO
+        });
+
+        match cells.last_mut() {
+            Some(cell) if joins_cell => {
+                cell.rect = cell.rect.union(&word.rect);
+                cell.text.push(' ');
+                cell.text.push_str(word.text.trim());
+            }
+            _ => cells.push(SnappitTableCell {
+                rect: word.rect,
+                text: word.text.trim().to_string(),
+            }),
+        }
+
+        last_line = Some(table_word.line);
+    }
+
+    cells
+}
+
+/// Horizontal `(left, right)` ranges of the columns, left to right
+fn column_spans(rows: &[Vec<SnappitTableCell>]) -> Vec<(u32, u32)> {
+    let has_multi_cell_rows = rows.iter().any(|row| row.len() > 1);
+
+    let mut ranges: Vec<(u32, u32)> = rows
+        .iter()
+        .filter(|row| row.len() > 1 || !has_multi_cell_rows)
+        .flatten()
+        .map(|cell| (cell.rect.x, cell.rect.right()))
+        .collect();
+    ranges.sort_unstable();
+
+    let mut spans: Vec<(u32, u32)> = Vec::new();
+    for (left, right) in ranges {
+        match spans.last_mut() {
+            Some(span) if left < span.1 => span.1 = span.1.max(right),
+            _ => spans.push((left, right)),
+        }
+    }
+
+    spans
+}
+
+/// The column overlapping `rect` the most, the nearest one when none does
+fn column_of(rect: &SnappitOcrRect, spans: &[(u32, u32)]) -> usize {
+    let overlap =
+        |&(left, right): &(u32, u32)| right.min(rect.right()).saturating_sub(left.max(rect.x));
+    let distance = |&(left, right): &(u32, u32)| {
+        let center = rect.x + rect.width / 2;
+        ((left + right) / 2).abs_diff(center)
+    };
+
+    spans
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, span)| (overlap(span), std::cmp::Reverse(distance(span))))
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// Cell texts of the table in `blocks`, row by row and padded to the same width
+pub fn table_rows(blocks: &[SnappitOcrBlock]) -> Vec<Vec<String>> {
+    let rows: Vec<Vec<SnappitTableCell>> = group_rows(table_words(blocks))
+        .into_iter()
+        .map(split_cells)
+        .collect();
+    let spans = column_spans(&rows);
+
+    rows.into_iter()
+        .map(|row| {
+            let mut cells = vec![String::new(); spans.len()];
+
+            for cell in row {
+                let text = &mut cells[column_of(&cell.rect, &spans)];
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(&cell.text);
+            }
+
+            cells
+        })
+        .collect()
+}
+
+/// Writes `rows` out as `format`, the first row is the Markdown header
+pub fn format_table(rows: &[Vec<String>], format: SnappitTableFormat) -> String {
+    match format {
+        SnappitTableFormat::Tsv => rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.replace(['\t', '\n', '\r'], " "))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        SnappitTableFormat::Csv => rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| csv_cell(cell))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        SnappitTableFormat::Markdown => markdown_table(rows),
+    }
+}
+
+/// Quotes cells with separators, quotes or line breaks as RFC 4180 asks
+fn csv_cell(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn markdown_table(rows: &[Vec<String>]) -> String {
+    let Some((header, body)) = rows.split_first() else {
+        return String::new();
+    };
+
+    let markdown_row = |row: &[String]| {
+        let cells: Vec<String> = row
+            .iter()
+            .map(|cell| cell.replace('|', "\\|").replace(['\n', '\r'], " "))
+            .collect();
+
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let mut lines = vec![
+        markdown_row(header),
+        format!("|{}", " --- |".repeat(header.len())),
+    ];
+    lines.extend(body.iter().map(|row| markdown_row(row)));
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ocr::test_fixtures::{self, block, line};
+
+    use super::*;
+
+    fn word(text: &str, x: u32, y: u32, width: u32) -> SnappitOcrWord {
+        test_fixtures::word(text, SnappitOcrRect::new(x, y, width, 20))
+    }
+
+    /// Every word in a line of its own, the way sparse-text mode returns cells
+    fn blocks(words: Vec<SnappitOcrWord>) -> Vec<SnappitOcrBlock> {
+        vec![block(
+            words.into_iter().map(|word| line(vec![word])).collect(),
+        )]
+    }
+
+    fn cells(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_table_rows() {
+        let words = vec![
+            word("Quarterly", 10, 0, 100),
+            word("report", 116, 0, 70),
+            word("Name", 10, 40, 50),
+            word("Price", 200, 42, 50),
+            word("Qty", 300, 40, 30),
+            word("Apple", 10, 80, 50),
+            word("1.20", 205, 78, 40),
+            word("Green", 10, 120, 50),
+            word("pear", 66, 120, 40),
+            word("0.90", 205, 121, 40),
+            word("7", 310, 120, 10),
+        ];
+
+        let mut block = blocks(words);
+        // Words of one engine line close together form a single cell
+        let report = block[0].lines.remove(1);
+        block[0].lines[0].words.extend(report.words);
+        let pear = block[0].lines.remove(7);
+        block[0].lines[6].words.extend(pear.words);
+
+        assert_eq!(
+            table_rows(&block),
+            cells(&[
+                &["Quarterly report", "", ""],
+                &["Name", "Price", "Qty"],
+                &["Apple", "1.20", ""],
+                &["Green pear", "0.90", "7"],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_table_rows_splits_cells_by_line_and_gap() {
+        let mut block = blocks(vec![word("Left", 10, 0, 40), word("Right", 56, 0, 50)]);
+        assert_eq!(table_rows(&block), cells(&[&["Left", "Right"]]));
+
+        let right = block[0].lines.remove(1);
+        block[0].lines[0].words.extend(right.words);
+        assert_eq!(table_rows(&block), cells(&[&["Left Right"]]));
+
+        block[0].lines[0].words[1].rect.x = 120;
+        assert_eq!(table_rows(&block), cells(&[&["Left", "Right"]]));
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows = cells(&[&["Name", "Note"], &["A|B", "say \"hi\", then\tgo"]]);
+
+        assert_eq!(
+            format_table(&rows, SnappitTableFormat::Tsv),
+            "Name\tNote\nA|B\tsay \"hi\", then go"
+        );
+        assert_eq!(
+            format_table(&rows, SnappitTableFormat::Csv),
+            "Name,Note\nA|B,\"say \"\"hi\"\", then\tgo\""
+        );
+        assert_eq!(
+            format_table(&rows, SnappitTableFormat::Markdown),
+            "| Name | Note |\n| --- | --- |\n| A\\|B | say \"hi\", then\tgo |"
+        );
+        assert_eq!(format_table(&[], SnappitTableFormat::Markdown), "");
+    }
+}
@@ -0,0 +1,202 @@
+use std::{borrow::Cow, ffi::CString, fs, path::Path};
+
+use image::DynamicImage;
+use leptess::{
+    tesseract::{TessApi, TessInitError},
+    Variable,
+};
+use tesseract_plumbing::TessBaseApi;
+
+use crate::errors::{SnappitCoreError, SnappitCoreResult};
+use crate::res::SnappitOcrEngine;
+
+use super::ocr_layout::{parse_tesseract_tsv, SnappitOcrOutput};
+use super::tesseract_pool::{SnappitTessPoolKey, SnappitTesseractPool};
+use super::{OcrConfig, OcrEngine, SnappitTesseractOptions};
+
+/// Tesseract's own defaults, restored on pooled instances after a code capture
+const DEFAULT_NON_DICT_PENALTY: &str = "0.15";
+const DEFAULT_NON_FREQ_DICT_PENALTY: &str = "0.1";
+
+pub struct SnappitTesseractOcr;
+
+impl SnappitTesseractOcr {
+    /// `LepTess::new` always initializes with the default engine mode, so the
+    /// API is set up by hand to be able to pass `--oem`
+    fn init(config: &OcrConfig) -> SnappitCoreResult<TessApi> {
+        let data_path = config
+            .tessdata_dir
+            .as_deref()
+            .and_then(|path| path.to_str())
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| TessInitError { code: -1 })?;
+        let language =
+            CString::new(config.tesseract_language()).map_err(|_| TessInitError { code: -1 })?;
+
+        let mut api = TessApi {
+            raw: TessBaseApi::create(),
+        };
+        api.raw
+            .init_4(
+                data_path.as_deref(),
+                Some(language.as_c_str()),
+                config.tesseract.engine_mode.tesseract_value(),
+            )
+            .map_err(|_| TessInitError { code: -1 })?;
+
+        Ok(api)
+    }
+
+    fn pool_key(config: &OcrConfig) -> SnappitTessPoolKey {
+        SnappitTessPoolKey {
+            language: config.tesseract_language(),
+            tessdata_dir: config.tessdata_dir.clone(),
+            engine_mode: config.tesseract.engine_mode,
+        }
+    }
+
+    /// Drops the cached Tesseract instances, needed after traineddata files change
+    pub fn invalidate_cache() {
+        SnappitTesseractPool::invalidate();
+    }
+
+    /// Checks that `<tessdata_dir>/<lang>.traineddata` loads, without touching the cache
+    pub fn validate_traineddata(tessdata_dir: &Path, lang: &str) -> SnappitCoreResult<()> {
+        let config = OcrConfig {
+            languages: vec![lang.to_string()],
+            tessdata_dir: Some(tessdata_dir.to_path_buf()),
+            ..OcrConfig::default()
+        };
+
+        Self::init(&config).map(|_| ())
+    }
+
+    /// Traineddata files in `tessdata_dir`, sorted by name
+    pub fn installed_languages(tessdata_dir: &Path) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(tessdata_dir) else {
+            return Vec::new();
+        };
+
+        let mut languages: Vec<String> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "traineddata"))
+            .filter_map(|path| path.file_stem()?.to_str().map(String::from))
+            .collect();
+
+        languages.sort();
+        languages
+    }
+
+    /// Pooled instances keep the variables of their previous run, so every
+    /// option is set each time, empty filters included
+    fn apply_options(
+        api: &mut TessApi,
+        options: &SnappitTesseractOptions,
+    ) -> SnappitCoreResult<()> {
+        let page_seg_mode = options.page_seg_mode.tesseract_value().to_string();
+        let preserve_interword_spaces = if options.preserve_interword_spaces {
+            "1"
+        } else {
+            "0"
+        };
+        let (non_dict_penalty, non_freq_dict_penalty) = if options.disable_dictionary {
+            ("0", "0")
+        } else {
+            (DEFAULT_NON_DICT_PENALTY, DEFAULT_NON_FREQ_DICT_PENALTY)
+        };
+
+        let variables = [
+            (Variable::TesseditPagesegMode, page_seg_mode.as_str()),
+            (Variable::PreserveInterwordSpaces, preserve_interword_spaces),
+            (
+                Variable::TesseditCharWhitelist,
+                options.char_whitelist.as_str(),
+            ),
+            (
+                Variable::TesseditCharBlacklist,
+                options.char_blacklist.as_str(),
+            ),
+        ];
+        // The dictionaries themselves can only be left out at init, the penalties for
+        // words missing from them can change between runs
+        let dictionary_variables = [
+            (c"language_model_penalty_non_dict_word", non_dict_penalty),
+            (
+                c"language_model_penalty_non_freq_dict_word",
+                non_freq_dict_penalty,
+            ),
+        ];
+
+        let named_variables = variables
+            .iter()
+            .map(|(variable, value)| (variable.as_cstr(), *value))
+            .chain(dictionary_variables);
+
+        for (name, value) in named_variables {
+            let rejected =
+                || SnappitCoreError::TessSetVariableError(name.to_string_lossy().into_owned());
+
+            // Settings may contain anything, an interior NUL must not panic the recognition thread
+            let value = CString::new(value).map_err(|_| rejected())?;
+            api.raw.set_variable(name, &value).map_err(|_| rejected())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl OcrEngine for SnappitTesseractOcr {
+    fn kind(&self) -> SnappitOcrEngine {
+        SnappitOcrEngine::Tesseract
+    }
+
+    fn recognize(
+        &self,
+        config: &OcrConfig,
+        img: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrOutput> {
+        let (pixels, bytes_per_pixel) = raw_pixels(img);
+        let (width, height) = (img.width() as i32, img.height() as i32);
+
+        SnappitTesseractPool::with(
+            &Self::pool_key(config),
+            || Self::init(config),
+            |api| {
+                Self::apply_options(api, &config.tesseract)?;
+
+                api.raw.set_image(
+                    &pixels,
+                    width,
+                    height,
+                    bytes_per_pixel,
+                    width * bytes_per_pixel,
+                )?;
+                api.set_source_resolution(300);
+
+                let text = api.get_utf8_text()?;
+                let blocks = parse_tesseract_tsv(&api.get_tsv_text(0)?);
+                // Tesseract reports 0 for an image without text, not a real confidence
+                let confidence = (!blocks.is_empty()).then(|| api.mean_text_conf() as f32);
+
+                Ok(SnappitOcrOutput {
+                    text,
+                    blocks,
+                    confidence,
+                })
+            },
+        )
+    }
+}
+
+/// Pixels in a layout Tesseract reads directly, borrowed when the image
+/// already is 8-bit gray, RGB or RGBA
+fn raw_pixels(img: &DynamicImage) -> (Cow<'_, [u8]>, i32) {
+    match img {
+        DynamicImage::ImageLuma8(gray) => (Cow::Borrowed(gray.as_raw().as_slice()), 1),
+        DynamicImage::ImageRgb8(rgb) => (Cow::Borrowed(rgb.as_raw().as_slice()), 3),
+        DynamicImage::ImageRgba8(rgba) => (Cow::Borrowed(rgba.as_raw().as_slice()), 4),
+        other => (Cow::Owned(other.to_rgba8().into_raw()), 4),
+    }
+}
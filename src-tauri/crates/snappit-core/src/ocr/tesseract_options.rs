@@ -0,0 +1,140 @@
+//! Tesseract specific recognition options
+//!
+//! None of these affect macOS Vision, which segments the page on its own.
+
+use serde::{Deserialize, Serialize};
+
+/// Selections at most this tall (in logical pixels) can be a single line of text
+const SINGLE_LINE_MAX_HEIGHT: u32 = 60;
+/// ...as long as they are at least this many times wider than tall
+const SINGLE_LINE_MIN_ASPECT: u32 = 3;
+
+/// Tesseract page segmentation mode (`--psm`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitPageSegMode {
+    /// Fully automatic, switches to single line for short and wide selections
+    #[default]
+    Auto,
+    SingleColumn,
+    /// Single block of vertically aligned text, for vertical CJK
+    VerticalBlock,
+    SingleBlock,
+    SingleLine,
+    SingleWord,
+    /// As much text as possible in no particular order, for scattered UI labels
+    SparseText,
+    /// Single line without Tesseract's own layout hacks
+    RawLine,
+}
+
+impl SnappitPageSegMode {
+    /// Value of `tessedit_pageseg_mode`
+    pub fn tesseract_value(self) -> u32 {
+        match self {
+            SnappitPageSegMode::Auto => 3,
+            SnappitPageSegMode::SingleColumn => 4,
+            SnappitPageSegMode::VerticalBlock => 5,
+            SnappitPageSegMode::SingleBlock => 6,
+            SnappitPageSegMode::SingleLine => 7,
+            SnappitPageSegMode::SingleWord => 8,
+            SnappitPageSegMode::SparseText => 11,
+            SnappitPageSegMode::RawLine => 13,
+        }
+    }
+
+    /// Picks single line mode for [`SnappitPageSegMode::Auto`] when the selected
+    /// region looks like one line of text, any explicit mode is kept
+    pub fn for_region(self, width: u32, height: u32) -> Self {
+        if self == SnappitPageSegMode::Auto && looks_like_single_line(width, height) {
+            SnappitPageSegMode::SingleLine
+        } else {
+            self
+        }
+    }
+}
+
+/// Tesseract OCR engine mode (`--oem`), only applied when the API is initialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitOcrEngineMode {
+    /// Whatever the traineddata files support, LSTM for the ones Snappit downloads
+    #[default]
+    Default,
+    LstmOnly,
+    /// Needs traineddata with legacy models, e.g. from `tessdata` rather than `tessdata_fast`
+    LegacyOnly,
+    LegacyAndLstm,
+}
+
+impl SnappitOcrEngineMode {
+    /// Value of `tessedit_ocr_engine_mode`
+    pub fn tesseract_value(self) -> u32 {
+        match self {
+            SnappitOcrEngineMode::LegacyOnly => 0,
+            SnappitOcrEngineMode::LstmOnly => 1,
+            SnappitOcrEngineMode::LegacyAndLstm => 2,
+            SnappitOcrEngineMode::Default => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnappitTesseractOptions {
+    pub page_seg_mode: SnappitPageSegMode,
+    pub engine_mode: SnappitOcrEngineMode,
+    /// Only these characters are recognized when not empty
+    pub char_whitelist: String,
+    /// These characters are never recognized
+    pub char_blacklist: String,
+    /// Keeps runs of spaces between words instead of collapsing them to one
+    pub preserve_interword_spaces: bool,
+    /// Stops preferring dictionary words, so identifiers and serials aren't
+    /// bent into the nearest known word
+    pub disable_dictionary: bool,
+}
+
+impl SnappitTesseractOptions {
+    /// Whether the options limit which characters may be recognized,
+    /// something only Tesseract can honour
+    pub fn restricts_characters(&self) -> bool {
+        !self.char_whitelist.is_empty() || !self.char_blacklist.is_empty()
+    }
+}
+
+/// Short, wide selections are usually a single line: a serial number, a label, a URL
+fn looks_like_single_line(width: u32, height: u32) -> bool {
+    height > 0
+        && height <= SINGLE_LINE_MAX_HEIGHT
+        && width >= height.saturating_mul(SINGLE_LINE_MIN_ASPECT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_single_line() {
+        assert!(looks_like_single_line(300, 24));
+        assert!(looks_like_single_line(180, 60));
+        assert!(!looks_like_single_line(100, 40));
+        assert!(!looks_like_single_line(800, 200));
+        assert!(!looks_like_single_line(300, 0));
+    }
+
+    #[test]
+    fn test_page_seg_mode_for_region() {
+        assert_eq!(
+            SnappitPageSegMode::Auto.for_region(400, 30),
+            SnappitPageSegMode::SingleLine
+        );
+        assert_eq!(
+            SnappitPageSegMode::Auto.for_region(400, 300),
+            SnappitPageSegMode::Auto
+        );
+        assert_eq!(
+            SnappitPageSegMode::SparseText.for_region(400, 30),
+            SnappitPageSegMode::SparseText
+        );
+    }
+}
@@ -0,0 +1,121 @@
+//! Initialized Tesseract instances kept around between recognitions
+//!
+//! Initializing reads every `.traineddata` file of the language set, which takes
+//! longer than recognizing a typical capture (`chi_sim` alone is tens of megabytes).
+//! Instances are keyed by everything that can only be chosen at init time and are
+//! dropped by [`SnappitTesseractPool::invalidate`] once traineddata files change.
+
+use std::{collections::HashMap, path::PathBuf, sync::Mutex, time::Instant};
+
+use leptess::tesseract::TessApi;
+use once_cell::sync::Lazy;
+
+use crate::errors::SnappitCoreResult;
+
+use super::SnappitOcrEngineMode;
+
+/// Idle instances kept per key, more are only needed for overlapping captures
+const MAX_IDLE_PER_KEY: usize = 2;
+/// Language sets kept initialized, the least recently used is dropped first
+const MAX_KEYS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SnappitTessPoolKey {
+    pub language: String,
+    pub tessdata_dir: Option<PathBuf>,
+    pub engine_mode: SnappitOcrEngineMode,
+}
+
+struct SnappitTessPoolEntry {
+    idle: Vec<TessApi>,
+    last_used: Instant,
+}
+
+#[derive(Default)]
+struct SnappitTessPoolState {
+    entries: HashMap<SnappitTessPoolKey, SnappitTessPoolEntry>,
+    /// Bumped on invalidation so instances checked out before it are not returned
+    generation: u64,
+}
+
+static TESS_POOL: Lazy<Mutex<SnappitTessPoolState>> =
+    Lazy::new(|| Mutex::new(SnappitTessPoolState::default()));
+
+pub struct SnappitTesseractPool;
+
+impl SnappitTesseractPool {
+    /// Runs `f` with an instance for `key`, initializing one with `init` when
+    /// none is idle. The instance goes back to the pool unless `f` failed
+    pub fn with<T>(
+        key: &SnappitTessPoolKey,
+        init: impl FnOnce() -> SnappitCoreResult<TessApi>,
+        f: impl FnOnce(&mut TessApi) -> SnappitCoreResult<T>,
+    ) -> SnappitCoreResult<T> {
+        let (pooled, generation) = Self::checkout(key);
+        let mut api = match pooled {
+            Some(api) => api,
+            None => init()?,
+        };
+
+        let result = f(&mut api)?;
+        Self::checkin(key, api, generation);
+
+        Ok(result)
+    }
+
+    /// Drops every idle instance, call after traineddata files were added, replaced or removed
+    pub fn invalidate() {
+        let mut state = Self::lock();
+        state.entries.clear();
+        state.generation += 1;
+    }
+
+    fn checkout(key: &SnappitTessPoolKey) -> (Option<TessApi>, u64) {
+        let mut state = Self::lock();
+        let api = state
+            .entries
+            .get_mut(key)
+            .and_then(|entry| entry.idle.pop());
+
+        (api, state.generation)
+    }
+
+    fn checkin(key: &SnappitTessPoolKey, api: TessApi, generation: u64) {
+        let mut state = Self::lock();
+        if state.generation != generation {
+            return;
+        }
+
+        if !state.entries.contains_key(key) && state.entries.len() >= MAX_KEYS {
+            let oldest = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                state.entries.remove(&oldest);
+            }
+        }
+
+        let entry = state
+            .entries
+            .entry(key.clone())
+            .or_insert_with(|| SnappitTessPoolEntry {
+                idle: Vec::new(),
+                last_used: Instant::now(),
+            });
+
+        entry.last_used = Instant::now();
+        if entry.idle.len() < MAX_IDLE_PER_KEY {
+            entry.idle.push(api);
+        }
+    }
+
+    /// A panic while holding the lock leaves only cached instances behind, safe to keep using
+    fn lock() -> std::sync::MutexGuard<'static, SnappitTessPoolState> {
+        TESS_POOL
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
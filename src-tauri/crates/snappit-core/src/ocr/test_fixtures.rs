@@ -0,0 +1,32 @@
+//! Words, lines and blocks for the layout tests
+
+use crate::res::{SnappitOcrBlock, SnappitOcrLine, SnappitOcrRect, SnappitOcrWord};
+
+pub fn word(text: &str, rect: SnappitOcrRect) -> SnappitOcrWord {
+    SnappitOcrWord {
+        text: text.to_string(),
+        rect,
+        confidence: 90.0,
+    }
+}
+
+/// Line around `words`, which must not be empty
+pub fn line(words: Vec<SnappitOcrWord>) -> SnappitOcrLine {
+    let rect = words
+        .iter()
+        .skip(1)
+        .fold(words[0].rect, |rect, word| rect.union(&word.rect));
+
+    SnappitOcrLine { rect, words }
+}
+
+/// Block around `lines`
+pub fn block(lines: Vec<SnappitOcrLine>) -> SnappitOcrBlock {
+    let rect = lines
+        .iter()
+        .map(|line| line.rect)
+        .reduce(|rect, line| rect.union(&line))
+        .unwrap_or_default();
+
+    SnappitOcrBlock { rect, lines }
+}
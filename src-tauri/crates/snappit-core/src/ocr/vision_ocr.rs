@@ -0,0 +1,300 @@
+use image::DynamicImage;
+#[cfg(target_os = "macos")]
+use image::ImageFormat;
+#[cfg(target_os = "macos")]
+use objc2::rc::Retained;
+#[cfg(target_os = "macos")]
+use objc2::runtime::AnyObject;
+#[cfg(target_os = "macos")]
+use objc2::{AnyThread, ClassType};
+#[cfg(target_os = "macos")]
+use objc2_foundation::{NSArray, NSData, NSDictionary, NSError, NSRange, NSString};
+#[cfg(target_os = "macos")]
+use objc2_vision::{
+    VNImageOption, VNImageRequestHandler, VNRecognizeTextRequest, VNRecognizedText,
+    VNRequestTextRecognitionLevel,
+};
+#[cfg(target_os = "macos")]
+use std::io::Cursor;
+
+use crate::errors::{SnappitCoreError, SnappitCoreResult};
+use crate::res::SnappitOcrEngine;
+#[cfg(target_os = "macos")]
+use crate::res::{SnappitOcrBlock, SnappitOcrLine, SnappitOcrRect, SnappitOcrWord};
+
+#[cfg(target_os = "macos")]
+use super::language_registry::SnappitLanguageRegistry;
+use super::ocr_layout::SnappitOcrOutput;
+use super::{OcrConfig, OcrEngine};
+
+pub struct SnappitMacOSVisionOcr;
+
+impl OcrEngine for SnappitMacOSVisionOcr {
+    fn kind(&self) -> SnappitOcrEngine {
+        SnappitOcrEngine::Vision
+    }
+
+    #[cfg(target_os = "macos")]
+    fn recognize(
+        &self,
+        config: &OcrConfig,
+        img: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrOutput> {
+        use objc2::rc::autoreleasepool;
+
+        let png_bytes = encode_png(img)?;
+        let (width, height) = (img.width(), img.height());
+
+        autoreleasepool(|_| -> SnappitCoreResult<SnappitOcrOutput> {
+            let data = NSData::with_bytes(&png_bytes);
+            let options: Retained<NSDictionary<VNImageOption, AnyObject>> = NSDictionary::new();
+            let handler = VNImageRequestHandler::initWithData_options(
+                VNImageRequestHandler::alloc(),
+                &data,
+                options.as_ref(),
+            );
+
+            let request = VNRecognizeTextRequest::new();
+            request.setRecognitionLevel(VNRequestTextRecognitionLevel::Accurate);
+            // Language correction turns identifiers in code and variables in
+            // formulas into dictionary words
+            request.setUsesLanguageCorrection(!config.code && config.math.is_none());
+
+            // Asked after the recognition level is set, the fast level supports fewer languages
+            let supported: Option<Vec<String>> = request
+                .supportedRecognitionLanguagesAndReturnError()
+                .ok()
+                .map(|codes| codes.to_vec().iter().map(|code| code.to_string()).collect());
+            // Vision detects the language on its own when given none
+            let vision_languages = if config.detect_language {
+                Vec::new()
+            } else {
+                normalize_languages(&config.languages, supported.as_deref())
+            };
+            let should_autodetect = vision_languages.is_empty();
+            request.setAutomaticallyDetectsLanguage(should_autodetect);
+
+            if !vision_languages.is_empty() {
+                let languages_array = nsstring_array(&vision_languages);
+                request.setRecognitionLanguages(&languages_array);
+            }
+
+            let requests = NSArray::from_slice(&[request.as_super().as_super()]);
+            handler
+                .performRequests_error(&requests)
+                .map_err(|err| vision_error(&err))?;
+
+            Ok(extract_output(&request, width, height))
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn recognize(
+        &self,
+        _config: &OcrConfig,
+        _img: &DynamicImage,
+    ) -> SnappitCoreResult<SnappitOcrOutput> {
+        Err(SnappitCoreError::MacOSVisionOcrUnavailable(
+            "macOS Vision OCR is only available on macOS".into(),
+        ))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn encode_png(img: &DynamicImage) -> SnappitCoreResult<Vec<u8>> {
+    let mut buf = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?;
+    Ok(buf)
+}
+
+/// Vision codes for the configured traineddata languages, dropping the ones
+/// Vision can't recognize. Without a `supported` list every known language is passed on
+#[cfg(target_os = "macos")]
+fn normalize_languages(languages: &[String], supported: Option<&[String]>) -> Vec<String> {
+    let mut mapped: Vec<String> = Vec::new();
+
+    for lang in languages {
+        if let Some(mapped_code) = map_language_code(lang, supported) {
+            if !mapped
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&mapped_code))
+            {
+                mapped.push(mapped_code);
+            }
+        }
+    }
+
+    mapped
+}
+
+#[cfg(target_os = "macos")]
+fn nsstring_array(codes: &[String]) -> Retained<NSArray<NSString>> {
+    let retained: Vec<Retained<NSString>> =
+        codes.iter().map(|code| NSString::from_str(code)).collect();
+
+    let refs: Vec<&NSString> = retained.iter().map(|s| s.as_ref()).collect();
+    NSArray::from_slice(&refs)
+}
+
+#[cfg(target_os = "macos")]
+fn extract_output(request: &VNRecognizeTextRequest, width: u32, height: u32) -> SnappitOcrOutput {
+    let Some(observations) = request.results() else {
+        return SnappitOcrOutput::default();
+    };
+
+    let mut lines = Vec::new();
+    let mut layout_lines: Vec<SnappitOcrLine> = Vec::new();
+    let mut confidences: Vec<f32> = Vec::new();
+
+    for observation in observations.to_vec() {
+        let candidates = observation.topCandidates(1);
+        let top_candidate = candidates.to_vec().into_iter().next();
+
+        if let Some(recognized) = top_candidate {
+            let candidate_text = recognized.string().to_string();
+            if !candidate_text.trim().is_empty() {
+                let bounds = unsafe { observation.boundingBox() };
+                let line_rect = to_pixel_rect(
+                    (bounds.origin.x, bounds.origin.y),
+                    (bounds.size.width, bounds.size.height),
+                    width,
+                    height,
+                );
+                let words = extract_words(&recognized, &candidate_text, width, height);
+                confidences.push(unsafe { recognized.confidence() } * 100.0);
+
+                layout_lines.push(SnappitOcrLine {
+                    rect: line_rect,
+                    words,
+                });
+                lines.push(candidate_text);
+            }
+        }
+    }
+
+    // Vision has no notion of blocks, so all lines end up in a single one
+    let block_rect = layout_lines
+        .iter()
+        .map(|line| line.rect)
+        .reduce(|acc, rect| acc.union(&rect));
+    let blocks = match block_rect {
+        Some(rect) => vec![SnappitOcrBlock {
+            rect,
+            lines: layout_lines,
+        }],
+        None => Vec::new(),
+    };
+
+    // Vision rates whole lines, their mean is the confidence of the text
+    let confidence = (!confidences.is_empty())
+        .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+
+    SnappitOcrOutput {
+        text: lines.join("\n"),
+        blocks,
+        confidence,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn extract_words(
+    recognized: &VNRecognizedText,
+    text: &str,
+    width: u32,
+    height: u32,
+) -> Vec<SnappitOcrWord> {
+    let confidence = unsafe { recognized.confidence() } * 100.0;
+    let mut words = Vec::new();
+    let mut utf16_offset = 0usize;
+    let mut word_start: Option<(usize, usize)> = None;
+
+    // Vision expects NSRange in UTF-16 code units
+    for (byte_index, ch) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        if ch.is_whitespace() {
+            if let Some((start_byte, start_utf16)) = word_start.take() {
+                let range = NSRange::new(start_utf16, utf16_offset - start_utf16);
+                let rect = unsafe { recognized.boundingBoxForRange_error(range) }
+                    .ok()
+                    .map(|observation| {
+                        let bounds = unsafe { observation.boundingBox() };
+                        to_pixel_rect(
+                            (bounds.origin.x, bounds.origin.y),
+                            (bounds.size.width, bounds.size.height),
+                            width,
+                            height,
+                        )
+                    })
+                    .unwrap_or_default();
+
+                words.push(SnappitOcrWord {
+                    text: text[start_byte..byte_index].to_string(),
+                    rect,
+                    confidence,
+                });
+            }
+        } else if word_start.is_none() {
+            word_start = Some((byte_index, utf16_offset));
+        }
+
+        utf16_offset += ch.len_utf16();
+    }
+
+    words
+}
+
+/// Converts Vision's normalized, bottom-left based rect into image pixels
+#[cfg(target_os = "macos")]
+fn to_pixel_rect(origin: (f64, f64), size: (f64, f64), width: u32, height: u32) -> SnappitOcrRect {
+    let (w, h) = (width as f64, height as f64);
+    let x = (origin.0 * w).clamp(0.0, w);
+    let y = ((1.0 - origin.1 - size.1) * h).clamp(0.0, h);
+    let rect_width = (size.0 * w).clamp(0.0, w - x);
+    let rect_height = (size.1 * h).clamp(0.0, h - y);
+
+    SnappitOcrRect::new(
+        x.round() as u32,
+        y.round() as u32,
+        rect_width.round() as u32,
+        rect_height.round() as u32,
+    )
+}
+
+/// Vision lists its languages with a region (`en-US`, `ja-JP`), so a tag
+/// without one matches the first supported code of that language
+#[cfg(target_os = "macos")]
+fn map_language_code(code: &str, supported: Option<&[String]>) -> Option<String> {
+    let bcp47 = SnappitLanguageRegistry::find(code)?.bcp47;
+
+    let Some(supported) = supported else {
+        return Some(bcp47.to_string());
+    };
+
+    supported
+        .iter()
+        .find(|candidate| candidate.eq_ignore_ascii_case(bcp47))
+        .or_else(|| {
+            supported.iter().find(|candidate| {
+                !bcp47.contains('-')
+                    && candidate
+                        .split('-')
+                        .next()
+                        .is_some_and(|language| language.eq_ignore_ascii_case(bcp47))
+            })
+        })
+        .cloned()
+}
+
+#[cfg(target_os = "macos")]
+fn vision_error(err: &NSError) -> SnappitCoreError {
+    let description = err.localizedDescription().to_string();
+    let message = if description.is_empty() {
+        "Vision request failed".to_string()
+    } else {
+        description
+    };
+
+    SnappitCoreError::MacOSVisionOcrUnavailable(message)
+}
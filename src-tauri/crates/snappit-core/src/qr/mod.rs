@@ -1,6 +1,6 @@
-//! QR code scanning module
+//! QR code and barcode scanning module
 //!
-//! This module provides QR code scanning functionality with platform-specific implementations:
+//! This module provides barcode scanning functionality with platform-specific implementations:
 //! - macOS: Uses Vision Framework with rxing fallback
 //! - Other platforms: Uses rxing library (ZXing port)
 
@@ -9,4 +9,4 @@ mod snappit_qr;
 #[cfg(target_os = "macos")]
 mod vision_qr;
 
-pub use snappit_qr::SnappitQr;
+pub use snappit_qr::{SnappitBarcodeScope, SnappitQr};
@@ -0,0 +1,143 @@
+//! Barcode scanning using rxing (ZXing port)
+//!
+//! This module provides barcode scanning using the rxing library,
+//! a Rust port of the ZXing barcode library known for handling
+//! damaged and stylized QR codes well. Several codes of different
+//! formats can be decoded from a single image.
+
+use std::collections::HashSet;
+
+use image::{ImageBuffer, Rgba};
+use rxing::{
+    common::GlobalHistogramBinarizer,
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    BarcodeFormat, BinaryBitmap, DecodeHints, Luma8LuminanceSource, MultiFormatReader, RXingResult,
+};
+
+use crate::errors::{SnappitCoreError, SnappitCoreResult};
+use crate::res::{SnappitBarcode, SnappitBarcodeFormat, SnappitBarcodePoint};
+
+use super::SnappitBarcodeScope;
+
+/// 2D formats handed to rxing for every scan
+const MATRIX_FORMATS: [BarcodeFormat; 5] = [
+    BarcodeFormat::QR_CODE,
+    BarcodeFormat::MICRO_QR_CODE,
+    BarcodeFormat::AZTEC,
+    BarcodeFormat::DATA_MATRIX,
+    BarcodeFormat::PDF_417,
+];
+
+/// 1D formats, only handed to rxing when the scope asks for them
+const LINEAR_FORMATS: [BarcodeFormat; 9] = [
+    BarcodeFormat::EAN_8,
+    BarcodeFormat::EAN_13,
+    BarcodeFormat::UPC_A,
+    BarcodeFormat::UPC_E,
+    BarcodeFormat::CODE_39,
+    BarcodeFormat::CODE_93,
+    BarcodeFormat::CODE_128,
+    BarcodeFormat::CODABAR,
+    BarcodeFormat::ITF,
+];
+
+pub struct RxingQr;
+
+impl RxingQr {
+    /// Scan image for the barcodes of `scope` using rxing
+    pub fn scan(
+        image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+        scope: SnappitBarcodeScope,
+    ) -> SnappitCoreResult<Vec<SnappitBarcode>> {
+        let width = image.width() as u32;
+        let height = image.height() as u32;
+
+        // Convert RGBA to grayscale (luma8) for rxing
+        let luma_data: Vec<u8> = image
+            .pixels()
+            .map(|p| {
+                // Standard grayscale conversion: 0.299*R + 0.587*G + 0.114*B
+                ((p[0] as u32 * 299 + p[1] as u32 * 587 + p[2] as u32 * 114) / 1000) as u8
+            })
+            .collect();
+
+        let source = Luma8LuminanceSource::new(luma_data, width, height);
+        let mut bitmap = BinaryBitmap::new(GlobalHistogramBinarizer::new(source));
+
+        let mut hints = DecodeHints::default();
+        hints.PossibleFormats = Some(Self::formats(scope));
+        hints.TryHarder = Some(true);
+
+        let mut reader = GenericMultipleBarcodeReader::new(MultiFormatReader::default());
+
+        match reader.decode_multiple_with_hints(&mut bitmap, &hints) {
+            Ok(results) => Ok(Self::collect_barcodes(results)),
+            Err(rxing::Exceptions::NotFoundException(_)) => Ok(Vec::new()),
+            Err(e) => Err(SnappitCoreError::QrScanFailed(e.to_string())),
+        }
+    }
+
+    fn formats(scope: SnappitBarcodeScope) -> HashSet<BarcodeFormat> {
+        let mut formats = HashSet::from(MATRIX_FORMATS);
+        if scope == SnappitBarcodeScope::All {
+            formats.extend(LINEAR_FORMATS);
+        }
+
+        formats
+    }
+
+    fn collect_barcodes(results: Vec<RXingResult>) -> Vec<SnappitBarcode> {
+        let mut barcodes: Vec<SnappitBarcode> = Vec::new();
+
+        for result in results {
+            let value = result.getText().to_string();
+            let format = Self::map_format(result.getBarcodeFormat());
+
+            // The multi reader may report the same code twice from overlapping sub-images
+            let is_duplicate = barcodes
+                .iter()
+                .any(|existing| existing.value == value && existing.format == format);
+
+            if value.is_empty() || is_duplicate {
+                continue;
+            }
+
+            let points = result
+                .getPoints()
+                .iter()
+                .map(|point| SnappitBarcodePoint {
+                    x: point.x,
+                    y: point.y,
+                })
+                .collect();
+
+            barcodes.push(SnappitBarcode {
+                value,
+                format,
+                points,
+            });
+        }
+
+        barcodes
+    }
+
+    fn map_format(format: &BarcodeFormat) -> SnappitBarcodeFormat {
+        match format {
+            BarcodeFormat::QR_CODE => SnappitBarcodeFormat::QrCode,
+            BarcodeFormat::MICRO_QR_CODE => SnappitBarcodeFormat::MicroQrCode,
+            BarcodeFormat::AZTEC => SnappitBarcodeFormat::Aztec,
+            BarcodeFormat::DATA_MATRIX => SnappitBarcodeFormat::DataMatrix,
+            BarcodeFormat::PDF_417 => SnappitBarcodeFormat::Pdf417,
+            BarcodeFormat::EAN_8 => SnappitBarcodeFormat::Ean8,
+            BarcodeFormat::EAN_13 => SnappitBarcodeFormat::Ean13,
+            BarcodeFormat::UPC_A => SnappitBarcodeFormat::UpcA,
+            BarcodeFormat::UPC_E => SnappitBarcodeFormat::UpcE,
+            BarcodeFormat::CODE_39 => SnappitBarcodeFormat::Code39,
+            BarcodeFormat::CODE_93 => SnappitBarcodeFormat::Code93,
+            BarcodeFormat::CODE_128 => SnappitBarcodeFormat::Code128,
+            BarcodeFormat::CODABAR => SnappitBarcodeFormat::Codabar,
+            BarcodeFormat::ITF => SnappitBarcodeFormat::Itf,
+            _ => SnappitBarcodeFormat::Other,
+        }
+    }
+}
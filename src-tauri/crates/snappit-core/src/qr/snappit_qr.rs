@@ -0,0 +1,84 @@
+//! Barcode scanning module
+//!
+//! This module provides barcode scanning functionality with platform-specific implementations:
+//! - macOS: Uses Vision Framework with rxing fallback
+//! - Other platforms: Uses rxing library (ZXing port)
+
+use image::{ImageBuffer, Rgba};
+
+use crate::errors::SnappitCoreResult;
+use crate::res::{SnappitBarcode, SnappitBarcodeFormat};
+
+use super::rxing_qr::RxingQr;
+#[cfg(target_os = "macos")]
+use super::vision_qr::VisionQr;
+
+/// Which barcodes a scan looks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnappitBarcodeScope {
+    /// QR, DataMatrix, Aztec and PDF417 codes. Stripes in text and table rules can
+    /// decode as 1D barcodes, so scans of arbitrary captures stay with these
+    TwoDimensional,
+    /// 2D codes and 1D barcodes, for scans the user asked for explicitly
+    All,
+}
+
+impl SnappitBarcodeScope {
+    pub fn includes(&self, format: SnappitBarcodeFormat) -> bool {
+        *self == SnappitBarcodeScope::All || format.is_2d()
+    }
+}
+
+/// Main barcode scanner providing platform-agnostic API
+///
+/// This struct internally chooses the appropriate platform-specific implementation:
+/// - On macOS: Uses Vision Framework with rxing fallback
+/// - On other platforms: Uses rxing library
+pub struct SnappitQr;
+
+impl SnappitQr {
+    /// Scan image for the QR codes and other barcodes of `scope`
+    ///
+    /// Returns every decoded code with its format and corner points,
+    /// an empty list if nothing was found, or an error if scanning failed.
+    pub fn scan(
+        image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+        scope: SnappitBarcodeScope,
+    ) -> SnappitCoreResult<Vec<SnappitBarcode>> {
+        #[cfg(target_os = "macos")]
+        {
+            // Vision detects every symbology, codes outside the scope are dropped
+            match VisionQr::scan(&image).map(|mut codes| {
+                codes.retain(|code| scope.includes(code.format));
+                codes
+            }) {
+                Ok(codes) if !codes.is_empty() => return Ok(codes),
+                Ok(_) => {
+                    // Vision didn't find anything, try rxing as fallback
+                    log::debug!("Vision barcode scan found nothing, trying rxing fallback");
+                }
+                Err(err) => {
+                    log::warn!("Vision barcode scan failed, falling back to rxing: {err}");
+                }
+            }
+        }
+
+        RxingQr::scan(image, scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_dimensional_scope_skips_linear_barcodes() {
+        let scope = SnappitBarcodeScope::TwoDimensional;
+
+        assert!(scope.includes(SnappitBarcodeFormat::QrCode));
+        assert!(scope.includes(SnappitBarcodeFormat::Pdf417));
+        assert!(!scope.includes(SnappitBarcodeFormat::Code128));
+        assert!(!scope.includes(SnappitBarcodeFormat::Ean13));
+        assert!(SnappitBarcodeScope::All.includes(SnappitBarcodeFormat::Code128));
+    }
+}
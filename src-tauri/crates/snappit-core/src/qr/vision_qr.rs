@@ -0,0 +1,156 @@
+//! Barcode scanning using macOS Vision Framework
+//!
+//! This module provides barcode scanning using Apple's Vision Framework
+//! with VNDetectBarcodesRequest, which uses ML models optimized for
+//! handling stylized and custom QR codes. All symbologies supported
+//! by Vision are detected.
+
+use image::{ImageBuffer, Rgba};
+
+use crate::errors::{SnappitCoreError, SnappitCoreResult};
+use crate::res::SnappitBarcode;
+#[cfg(target_os = "macos")]
+use crate::res::{SnappitBarcodeFormat, SnappitBarcodePoint};
+
+pub struct VisionQr;
+
+impl VisionQr {
+    #[cfg(target_os = "macos")]
+    pub fn scan(image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> SnappitCoreResult<Vec<SnappitBarcode>> {
+        use image::DynamicImage;
+        use objc2::rc::{autoreleasepool, Retained};
+        use objc2::runtime::AnyObject;
+        use objc2::{AnyThread, ClassType};
+        use objc2_foundation::{NSArray, NSData, NSDictionary};
+        use objc2_vision::{
+            VNDetectBarcodesRequest, VNImageOption, VNImageRequestHandler, VNRequest,
+        };
+
+        let (width, height) = (image.width() as f32, image.height() as f32);
+        let dynamic = DynamicImage::ImageRgba8(image.clone());
+        let png_bytes = encode_png(&dynamic)?;
+
+        autoreleasepool(|_| -> SnappitCoreResult<Vec<SnappitBarcode>> {
+            let data = NSData::with_bytes(&png_bytes);
+            let options: Retained<NSDictionary<VNImageOption, AnyObject>> = NSDictionary::new();
+
+            let handler = VNImageRequestHandler::initWithData_options(
+                VNImageRequestHandler::alloc(),
+                &data,
+                options.as_ref(),
+            );
+
+            // No symbologies set: Vision detects every symbology it supports
+            let request = unsafe { VNDetectBarcodesRequest::new() };
+
+            // Cast to VNRequest for the requests array
+            let request_ref: &VNRequest = request.as_super().as_super();
+            let requests: Retained<NSArray<VNRequest>> = NSArray::from_slice(&[request_ref]);
+
+            handler
+                .performRequests_error(&requests)
+                .map_err(|err| vision_error(&err))?;
+
+            // Extract results
+            let results = unsafe { request.results() };
+            let Some(observations) = results else {
+                return Ok(Vec::new());
+            };
+
+            let mut barcodes: Vec<SnappitBarcode> = Vec::new();
+
+            for observation in observations.to_vec() {
+                let Some(payload) = (unsafe { observation.payloadStringValue() }) else {
+                    continue;
+                };
+
+                let value = payload.to_string();
+                if value.is_empty() || barcodes.iter().any(|existing| existing.value == value) {
+                    continue;
+                }
+
+                let symbology = unsafe { observation.symbology() }.to_string();
+
+                // Vision points are normalized with the origin in the bottom-left corner
+                let corners = unsafe {
+                    [
+                        observation.topLeft(),
+                        observation.topRight(),
+                        observation.bottomRight(),
+                        observation.bottomLeft(),
+                    ]
+                };
+                let points = corners
+                    .iter()
+                    .map(|point| SnappitBarcodePoint {
+                        x: point.x as f32 * width,
+                        y: (1.0 - point.y as f32) * height,
+                    })
+                    .collect();
+
+                barcodes.push(SnappitBarcode {
+                    value,
+                    format: map_symbology(&symbology),
+                    points,
+                });
+            }
+
+            Ok(barcodes)
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn scan(_image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> SnappitCoreResult<Vec<SnappitBarcode>> {
+        Err(SnappitCoreError::VisionQrUnavailable(
+            "Vision barcode scanning is only available on macOS".into(),
+        ))
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn map_symbology(symbology: &str) -> SnappitBarcodeFormat {
+    let name = symbology
+        .strip_prefix("VNBarcodeSymbology")
+        .unwrap_or(symbology);
+
+    match name {
+        "QR" => SnappitBarcodeFormat::QrCode,
+        "MicroQR" => SnappitBarcodeFormat::MicroQrCode,
+        "Aztec" => SnappitBarcodeFormat::Aztec,
+        "DataMatrix" => SnappitBarcodeFormat::DataMatrix,
+        "PDF417" | "MicroPDF417" => SnappitBarcodeFormat::Pdf417,
+        "EAN8" => SnappitBarcodeFormat::Ean8,
+        "EAN13" => SnappitBarcodeFormat::Ean13,
+        "UPCE" => SnappitBarcodeFormat::UpcE,
+        "Code39" | "Code39Checksum" | "Code39FullASCII" | "Code39FullASCIIChecksum" => {
+            SnappitBarcodeFormat::Code39
+        }
+        "Code93" | "Code93i" => SnappitBarcodeFormat::Code93,
+        "Code128" => SnappitBarcodeFormat::Code128,
+        "Codabar" => SnappitBarcodeFormat::Codabar,
+        "ITF14" | "I2of5" | "I2of5Checksum" => SnappitBarcodeFormat::Itf,
+        _ => SnappitBarcodeFormat::Other,
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn encode_png(img: &image::DynamicImage) -> SnappitCoreResult<Vec<u8>> {
+    use image::ImageFormat;
+    use std::io::Cursor;
+
+    let mut buf = Vec::new();
+    img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?;
+    Ok(buf)
+}
+
+#[cfg(target_os = "macos")]
+fn vision_error(err: &objc2_foundation::NSError) -> SnappitCoreError {
+    let description = err.localizedDescription().to_string();
+    let message = if description.is_empty() {
+        "Vision barcode request failed".to_string()
+    } else {
+        description
+    };
+
+    SnappitCoreError::VisionQrUnavailable(message)
+}
@@ -0,0 +1,177 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitOcrEngine {
+    Vision,
+    Tesseract,
+}
+
+/// Pixel rectangle relative to the top-left corner of the captured image
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnappitOcrRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SnappitOcrRect {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    /// Smallest rectangle containing both `self` and `other`
+    pub fn union(&self, other: &SnappitOcrRect) -> SnappitOcrRect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+
+        SnappitOcrRect::new(x, y, right - x, bottom - y)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitOcrWord {
+    pub text: String,
+    pub rect: SnappitOcrRect,
+    /// Recognition confidence in the 0..=100 range
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitOcrLine {
+    pub rect: SnappitOcrRect,
+    pub words: Vec<SnappitOcrWord>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitOcrBlock {
+    pub rect: SnappitOcrRect,
+    pub lines: Vec<SnappitOcrLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitOcrResult {
+    pub value: String,
+    pub ocr: SnappitOcrEngine,
+    pub blocks: Vec<SnappitOcrBlock>,
+    /// Tesseract languages picked by script detection, joined with `+`
+    pub detected_language: Option<String>,
+    /// Mean confidence in the 0..=100 range, `None` when nothing was recognized
+    pub confidence: Option<f32>,
+    /// Confidence below the configured minimum, the text is likely garbled
+    pub low_confidence: bool,
+    /// Recognized a second time after a low confidence first pass
+    pub retried: bool,
+}
+
+/// How table cells are written out for the clipboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitTableFormat {
+    /// Tab separated, pastes into spreadsheets as cells
+    #[default]
+    Tsv,
+    Csv,
+    Markdown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitTableResult {
+    /// `rows` written out as `format`
+    pub value: String,
+    pub format: SnappitTableFormat,
+    /// Cell texts row by row, every row has the same number of cells
+    pub rows: Vec<Vec<String>>,
+    pub ocr: SnappitOcrEngine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitBarcodeFormat {
+    QrCode,
+    MicroQrCode,
+    Aztec,
+    DataMatrix,
+    Pdf417,
+    Ean8,
+    Ean13,
+    UpcA,
+    UpcE,
+    Code39,
+    Code93,
+    Code128,
+    Codabar,
+    Itf,
+    Other,
+}
+
+impl SnappitBarcodeFormat {
+    /// Matrix and stacked codes, which unlike 1D barcodes don't decode from stripes in text
+    pub fn is_2d(&self) -> bool {
+        matches!(
+            self,
+            SnappitBarcodeFormat::QrCode
+                | SnappitBarcodeFormat::MicroQrCode
+                | SnappitBarcodeFormat::Aztec
+                | SnappitBarcodeFormat::DataMatrix
+                | SnappitBarcodeFormat::Pdf417
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SnappitBarcodePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitBarcode {
+    pub value: String,
+    pub format: SnappitBarcodeFormat,
+    /// Corner (or, for 1D codes, end) points in pixels of the captured image
+    pub points: Vec<SnappitBarcodePoint>,
+}
+
+/// Color information in various formats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitColorInfo {
+    pub hex: String,
+    pub rgb: (u8, u8, u8),
+    pub rgba: (u8, u8, u8, u8),
+}
+
+impl fmt::Display for SnappitColorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.hex)
+    }
+}
+
+impl SnappitColorInfo {
+    pub fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
+        Self {
+            hex,
+            rgb: (r, g, b),
+            rgba: (r, g, b, a),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Settings values
+//!
+//! The settings store keeps the enum options, like the page segmentation mode or the
+//! table format, as their snake_case names. The app and the CLI both read them
+//! through [`parse_setting`], so a name means the same thing everywhere.
+
+use serde::de::{
+    value::{Error, StrDeserializer},
+    DeserializeOwned, IntoDeserializer,
+};
+
+/// Parses a setting stored by its snake_case name, `None` when it isn't a known value
+pub fn parse_setting<T: DeserializeOwned>(value: &str) -> Option<T> {
+    let deserializer: StrDeserializer<Error> = value.trim().into_deserializer();
+
+    T::deserialize(deserializer).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ocr::{SnappitPageSegMode, SnappitPreprocessStep},
+        res::SnappitTableFormat,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_parse_setting() {
+        assert_eq!(
+            parse_setting(" single_line "),
+            Some(SnappitPageSegMode::SingleLine)
+        );
+        assert_eq!(
+            parse_setting("auto_invert"),
+            Some(SnappitPreprocessStep::AutoInvert)
+        );
+        assert_eq!(
+            parse_setting("markdown"),
+            Some(SnappitTableFormat::Markdown)
+        );
+        assert_eq!(parse_setting::<SnappitTableFormat>("Markdown"), None);
+        assert_eq!(parse_setting::<SnappitPageSegMode>(""), None);
+    }
+}
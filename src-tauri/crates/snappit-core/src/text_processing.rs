@@ -0,0 +1,530 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::res::{SnappitOcrBlock, SnappitOcrLine, SnappitOcrWord};
+
+/// Проверяет, является ли символ тайским
+fn is_thai(c: char) -> bool {
+    ('\u{0E00}'..='\u{0E7F}').contains(&c)
+}
+
+/// Проверяет, является ли символ CJK (Chinese/Japanese/Korean)
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{4E00}'..='\u{9FFF}'   |  // CJK Unified Ideographs
+        '\u{3400}'..='\u{4DBF}'   |  // CJK Unified Ideographs Extension A
+        '\u{3040}'..='\u{309F}'   |  // Hiragana
+        '\u{30A0}'..='\u{30FF}'   |  // Katakana
+        '\u{AC00}'..='\u{D7AF}'   |  // Hangul Syllables
+        '\u{1100}'..='\u{11FF}'   |  // Hangul Jamo
+        '\u{3130}'..='\u{318F}'      // Hangul Compatibility Jamo
+    )
+}
+
+/// Проверяет, является ли символ частью языка без пробелов (Thai/CJK)
+fn is_non_spaced_script(c: char) -> bool {
+    is_thai(c) || is_cjk(c)
+}
+
+/// Удаляет лишние пробелы между символами Thai/CJK, сохраняя пробелы для латиницы
+pub fn remove_non_spaced_script_spaces(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == ' ' {
+            let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+            let next = chars.get(i + 1).copied();
+
+            let skip_space = match (prev, next) {
+                (Some(p), Some(n)) => is_non_spaced_script(p) && is_non_spaced_script(n),
+                _ => false,
+            };
+
+            if !skip_space {
+                result.push(c);
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Удаляет лишние переносы строк, заменяя их на пробелы
+pub fn collapse_line_breaks(text: &str) -> String {
+    let mut result = String::new();
+    let mut prev_was_newline = false;
+
+    for ch in text.chars() {
+        if ch == '\n' {
+            if !prev_was_newline && !result.is_empty() {
+                result.push(' ');
+            }
+            prev_was_newline = true;
+        } else {
+            result.push(ch);
+            prev_was_newline = false;
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// ASCII-замена типографской лигатуры или фигурной кавычки
+fn typographic_replacement(c: char) -> Option<&'static str> {
+    let replacement = match c {
+        '\u{FB00}' => "ff",
+        '\u{FB01}' => "fi",
+        '\u{FB02}' => "fl",
+        '\u{FB03}' => "ffi",
+        '\u{FB04}' => "ffl",
+        '\u{FB05}' | '\u{FB06}' => "st",
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => "\"",
+        _ => return None,
+    };
+
+    Some(replacement)
+}
+
+/// Исправляет частые ошибки OCR: типографские лигатуры, фигурные кавычки и `|` вместо `l`
+pub fn fix_ocr_confusions(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if let Some(replacement) = typographic_replacement(c) {
+            result.push_str(replacement);
+            continue;
+        }
+
+        match c {
+            '|' => {
+                let prev = if i > 0 { Some(chars[i - 1]) } else { None };
+                let next = chars.get(i + 1).copied();
+
+                // "he|lo", "|ike", но не разделители таблиц "a | b"
+                let inside_word =
+                    prev.is_some_and(char::is_alphabetic) && next.is_some_and(char::is_alphabetic);
+                let starts_word =
+                    prev.is_none_or(char::is_whitespace) && next.is_some_and(char::is_lowercase);
+
+                result.push(if inside_word || starts_word { 'l' } else { '|' });
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Заменяет лигатуры и фигурные кавычки на ASCII, не трогая остальные символы:
+/// в коде `|` — это оператор, а не ошибочно распознанная `l`
+pub fn fix_code_symbols(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match typographic_replacement(c) {
+            Some(replacement) => result.push_str(replacement),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Проверяет, начинается ли строка с маркера или номера пункта списка: "•", "-", "1.", "a)"
+fn is_list_item(line: &str) -> bool {
+    let mut chars = line.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    if matches!(first, '•' | '◦' | '▪' | '‣' | '·' | '-' | '*' | '–' | '—') {
+        return chars.next().is_some_and(char::is_whitespace);
+    }
+
+    let marker: String = line.chars().take_while(|c| c.is_alphanumeric()).collect();
+    let is_marker = marker.chars().all(|c| c.is_ascii_digit())
+        || (marker.chars().count() == 1 && marker.chars().all(|c| c.is_alphabetic()));
+
+    !marker.is_empty()
+        && is_marker
+        && line[marker.len()..].starts_with(['.', ')'])
+        && line[marker.len() + 1..].starts_with(char::is_whitespace)
+}
+
+/// Приклеивает строку `next` к абзацу, убирая перенос слова по дефису в конце строки.
+/// Перед заглавной буквой дефис остается: это составное слово, а не перенос
+fn join_wrapped_line(paragraph: &mut String, next: &str) {
+    let first = next.chars().next();
+    let mut tail = paragraph.chars().rev();
+    let last = tail.next();
+    let before_last = tail.next();
+
+    let ends_with_hyphen = matches!(last, Some('-' | '\u{00AD}' | '\u{2010}'))
+        && before_last.is_some_and(char::is_alphabetic);
+
+    let joins_without_space =
+        last.is_some_and(is_non_spaced_script) && first.is_some_and(is_non_spaced_script);
+
+    if ends_with_hyphen && first.is_some_and(char::is_lowercase) {
+        paragraph.pop();
+    } else if !ends_with_hyphen && !joins_without_space {
+        paragraph.push(' ');
+    }
+
+    paragraph.push_str(next);
+}
+
+/// Собирает абзацы из строк, перенесенных при верстке: склеивает строки внутри абзаца,
+/// убирает переносы по дефису, сохраняет пустые строки между абзацами и пункты списков
+pub fn reflow_paragraphs(text: &str) -> String {
+    let text = fix_ocr_confusions(text);
+    let mut paragraphs: Vec<String> = Vec::new();
+
+    for block in text.split("\n\n") {
+        let mut lines: Vec<String> = Vec::new();
+
+        for line in block.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            match lines.last_mut() {
+                Some(paragraph) if !is_list_item(line) => join_wrapped_line(paragraph, line),
+                _ => lines.push(line.to_string()),
+            }
+        }
+
+        if !lines.is_empty() {
+            paragraphs.push(lines.join("\n"));
+        }
+    }
+
+    paragraphs.join("\n\n")
+}
+
+/// Проверяет, является ли символ управляющим символом bidi (LRM, RLM, ALM, встраивания и изоляты),
+/// которые движки расставляют вокруг чисел в RTL тексте и которые ломают вставку
+fn is_bidi_control(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+    )
+}
+
+/// Проверяет, является ли символ буквой письменности справа налево (иврит, арабский и соседние)
+fn is_rtl(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0590}'..='\u{05FF}' |  // Hebrew
+        '\u{0600}'..='\u{065F}' |  // Arabic, без арабских цифр
+        '\u{066A}'..='\u{06EF}' |
+        '\u{06FA}'..='\u{08FF}' |  // Arabic Supplement, Syriac, Thaana, NKo, Arabic Extended
+        '\u{FB1D}'..='\u{FDFF}' |  // Hebrew и Arabic Presentation Forms-A
+        '\u{FE70}'..='\u{FEFF}'    // Arabic Presentation Forms-B
+    ) && !is_bidi_control(c)
+}
+
+/// Проверяет, написан ли текст в основном справа налево
+pub fn is_rtl_text(text: &str) -> bool {
+    let rtl = text.chars().filter(|&c| is_rtl(c)).count();
+    let ltr = text
+        .chars()
+        .filter(|&c| c.is_alphabetic() && !is_rtl(c))
+        .count();
+
+    rtl > ltr
+}
+
+/// Удаляет управляющие символы bidi
+pub fn strip_bidi_controls(text: &str) -> String {
+    text.chars().filter(|&c| !is_bidi_control(c)).collect()
+}
+
+/// Приводит текст к нормальной форме NFC, чтобы буквы с диакритикой были одним символом
+pub fn normalize_nfc(text: &str) -> String {
+    text.nfc().collect()
+}
+
+/// Слова RTL строки в логическом порядке по их расположению, в каком бы порядке
+/// их ни вернул движок. Слова идут справа налево, кроме групп LTR слов (числа,
+/// латиница), которые сохраняют свой порядок
+pub fn order_rtl_line(words: &[SnappitOcrWord]) -> Vec<&str> {
+    let mut visual: Vec<&SnappitOcrWord> = words.iter().collect();
+    visual.sort_by_key(|word| word.rect.x);
+
+    let mut ordered: Vec<&str> = Vec::with_capacity(visual.len());
+    let mut ltr_run: Vec<&str> = Vec::new();
+
+    for word in visual.into_iter().rev() {
+        if word.text.chars().any(is_rtl) {
+            ordered.extend(ltr_run.drain(..).rev());
+            ordered.push(&word.text);
+        } else {
+            ltr_run.push(&word.text);
+        }
+    }
+    ordered.extend(ltr_run.into_iter().rev());
+
+    ordered
+}
+
+/// Проверяет, является ли строка колонкой вертикального текста: она заметно выше, чем шире
+fn is_vertical_line(line: &SnappitOcrLine) -> bool {
+    line.rect.height >= line.rect.width.saturating_mul(2)
+        && line.words.iter().any(|word| word.text.chars().any(is_cjk))
+}
+
+/// Проверяет, распознан ли вертикальный CJK текст: большинство строк являются колонками
+pub fn is_vertical_layout(blocks: &[SnappitOcrBlock]) -> bool {
+    let lines = blocks.iter().flat_map(|block| &block.lines);
+    let (vertical, horizontal) = lines.fold((0, 0), |(vertical, horizontal), line| {
+        if is_vertical_line(line) {
+            (vertical + 1, horizontal)
+        } else {
+            (vertical, horizontal + 1)
+        }
+    });
+
+    vertical > horizontal
+}
+
+/// Собирает текст вертикальных колонок в порядке чтения: колонки справа налево,
+/// внутри колонки сверху вниз
+pub fn vertical_text_from_blocks(blocks: &[SnappitOcrBlock]) -> String {
+    let mut columns: Vec<&SnappitOcrLine> = blocks.iter().flat_map(|block| &block.lines).collect();
+    columns.sort_by_key(|line| std::cmp::Reverse(line.rect.x + line.rect.width / 2));
+
+    columns
+        .into_iter()
+        .map(|column| {
+            let mut words: Vec<&SnappitOcrWord> = column.words.iter().collect();
+            words.sort_by_key(|word| word.rect.y);
+
+            let text = words
+                .iter()
+                .map(|word| word.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            remove_non_spaced_script_spaces(&text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Собирает RTL текст из строк в логическом порядке слов, блоки разделяются пустой строкой
+pub fn rtl_text_from_blocks(blocks: &[SnappitOcrBlock]) -> String {
+    blocks
+        .iter()
+        .map(|block| {
+            block
+                .lines
+                .iter()
+                .map(|line| order_rtl_line(&line.words).join(" "))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Текст в порядке чтения: RTL строки и вертикальные колонки собираются заново по расположению
+/// слов, остальной текст остается как есть. Без разметки вернуть порядок нечем
+pub fn reading_order_text(text: &str, blocks: &[SnappitOcrBlock]) -> String {
+    let has_words = blocks
+        .iter()
+        .flat_map(|block| &block.lines)
+        .any(|line| !line.words.is_empty());
+
+    if !has_words {
+        text.to_string()
+    } else if is_rtl_text(text) {
+        rtl_text_from_blocks(blocks)
+    } else if is_vertical_layout(blocks) {
+        vertical_text_from_blocks(blocks)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ocr::test_fixtures::{self, block, line},
+        res::SnappitOcrRect,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_remove_thai_spaces() {
+        assert_eq!(remove_non_spaced_script_spaces("ส ว ั ส ด ี"), "สวัสดี");
+    }
+
+    #[test]
+    fn test_mixed_thai_english() {
+        assert_eq!(
+            remove_non_spaced_script_spaces("Hello ส ว ั ส ด ี World"),
+            "Hello สวัสดี World"
+        );
+    }
+
+    #[test]
+    fn test_remove_chinese_spaces() {
+        assert_eq!(remove_non_spaced_script_spaces("你 好 世 界"), "你好世界");
+    }
+
+    #[test]
+    fn test_mixed_chinese_english() {
+        assert_eq!(
+            remove_non_spaced_script_spaces("Hello 你 好 World"),
+            "Hello 你好 World"
+        );
+    }
+
+    #[test]
+    fn test_remove_japanese_spaces() {
+        assert_eq!(
+            remove_non_spaced_script_spaces("こ ん に ち は"),
+            "こんにちは"
+        );
+    }
+
+    #[test]
+    fn test_english_preserved() {
+        assert_eq!(
+            remove_non_spaced_script_spaces("Hello World"),
+            "Hello World"
+        );
+    }
+
+    #[test]
+    fn test_collapse_line_breaks() {
+        assert_eq!(collapse_line_breaks("Hello\nWorld"), "Hello World");
+        assert_eq!(collapse_line_breaks("Hello\n\nWorld"), "Hello World");
+    }
+
+    #[test]
+    fn test_fix_ocr_confusions() {
+        assert_eq!(
+            fix_ocr_confusions("\u{FB01}rst o\u{FB00}ice"),
+            "first office"
+        );
+        assert_eq!(
+            fix_ocr_confusions("\u{201C}It\u{2019}s\u{201D}"),
+            "\"It's\""
+        );
+        assert_eq!(fix_ocr_confusions("he|lo |ike"), "hello like");
+        assert_eq!(fix_ocr_confusions("a | b |"), "a | b |");
+    }
+
+    #[test]
+    fn test_fix_code_symbols() {
+        assert_eq!(
+            fix_code_symbols("print(\u{201C}o\u{FB00}\u{201D}) || |x|"),
+            "print(\"off\") || |x|"
+        );
+    }
+
+    #[test]
+    fn test_reflow_joins_wrapped_lines() {
+        assert_eq!(
+            reflow_paragraphs("Optical character recog-\nnition turns\nimages into text."),
+            "Optical character recognition turns images into text."
+        );
+        assert_eq!(reflow_paragraphs("Snappit-\nApp"), "Snappit-App");
+    }
+
+    #[test]
+    fn test_reflow_keeps_paragraphs_and_lists() {
+        let text =
+            "First paragraph\nwraps here.\n\nItems:\n• one\ncontinued\n- two\n2. three\na) four";
+
+        assert_eq!(
+            reflow_paragraphs(text),
+            "First paragraph wraps here.\n\nItems:\n• one continued\n- two\n2. three\na) four"
+        );
+    }
+
+    #[test]
+    fn test_reflow_cjk_lines() {
+        assert_eq!(reflow_paragraphs("你好\n世界"), "你好世界");
+    }
+
+    fn word(text: &str, x: u32, y: u32, width: u32, height: u32) -> SnappitOcrWord {
+        test_fixtures::word(text, SnappitOcrRect::new(x, y, width, height))
+    }
+
+    #[test]
+    fn test_strip_bidi_controls() {
+        assert_eq!(
+            strip_bidi_controls("\u{200F}שלום\u{200E} 2024\u{200F}"),
+            "שלום 2024"
+        );
+    }
+
+    #[test]
+    fn test_normalize_nfc() {
+        assert_eq!(normalize_nfc("e\u{0301}"), "\u{00E9}");
+        assert_eq!(normalize_nfc("й"), "й");
+    }
+
+    #[test]
+    fn test_is_rtl_text() {
+        assert!(is_rtl_text("مرحبا بالعالم 2024"));
+        assert!(is_rtl_text("שלום PDF עולם"));
+        assert!(!is_rtl_text("Hello עולם world"));
+    }
+
+    #[test]
+    fn test_order_rtl_line() {
+        // Visually, left to right: "2024 PDF עולם שלום"
+        let words = vec![
+            word("עולם", 200, 0, 50, 20),
+            word("2024", 0, 0, 40, 20),
+            word("שלום", 260, 0, 50, 20),
+            word("PDF", 50, 0, 40, 20),
+        ];
+
+        assert_eq!(order_rtl_line(&words), vec!["שלום", "עולם", "2024", "PDF"]);
+    }
+
+    #[test]
+    fn test_reading_order_rtl() {
+        let blocks = vec![block(vec![
+            line(vec![
+                word("بالعالم", 0, 0, 60, 20),
+                word("مرحبا", 70, 0, 50, 20),
+            ]),
+            line(vec![word("سطر", 40, 30, 40, 20)]),
+        ])];
+
+        assert_eq!(
+            reading_order_text("بالعالم مرحبا\nسطر", &blocks),
+            "مرحبا بالعالم\nسطر"
+        );
+    }
+
+    #[test]
+    fn test_reading_order_vertical() {
+        // Two columns, the right one is read first
+        let blocks = vec![block(vec![
+            line(vec![word("世", 10, 0, 20, 20), word("界", 10, 25, 20, 20)]),
+            line(vec![word("你", 50, 0, 20, 20), word("好", 50, 25, 20, 20)]),
+        ])];
+
+        assert!(is_vertical_layout(&blocks));
+        assert_eq!(reading_order_text("世界\n你好", &blocks), "你好\n世界");
+    }
+
+    #[test]
+    fn test_reading_order_keeps_horizontal_text() {
+        let blocks = vec![block(vec![line(vec![
+            word("Hello", 0, 0, 50, 20),
+            word("World", 60, 0, 50, 20),
+        ])])];
+
+        assert_eq!(reading_order_text("Hello World", &blocks), "Hello World");
+        assert_eq!(reading_order_text("Hello", &[]), "Hello");
+    }
+}
@@ -0,0 +1,67 @@
+//! Runs the engines of the crate on checked-in images, the way the app and the CLI
+//! call them. Recognition needs `eng.traineddata` where Tesseract finds it, either in
+//! `TESSDATA_PREFIX` or in the system install.
+
+use std::path::Path;
+
+use image::DynamicImage;
+use snappit_core::{
+    ocr::{OcrConfig, SnappitOcr},
+    qr::{SnappitBarcodeScope, SnappitQr},
+    res::{SnappitBarcodeFormat, SnappitOcrEngine},
+};
+
+fn fixture(name: &str) -> DynamicImage {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+
+    image::open(&path).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+fn english() -> OcrConfig {
+    OcrConfig {
+        languages: vec!["eng".to_string()],
+        ..OcrConfig::default()
+    }
+}
+
+#[test]
+fn test_tesseract_recognizes_fixture_text() {
+    let result = SnappitOcr::recognize_with_tesseract(&english(), &fixture("text.png"))
+        .expect("Tesseract failed on the fixture");
+
+    assert_eq!(result.ocr, SnappitOcrEngine::Tesseract);
+    assert_eq!(result.value.trim(), "Snappit reads text\nfrom image files.");
+    assert_eq!(
+        result.blocks.iter().map(|b| b.lines.len()).sum::<usize>(),
+        2
+    );
+    assert!(result
+        .confidence
+        .is_some_and(|confidence| confidence > 50.0));
+}
+
+#[test]
+fn test_qr_decodes_fixture_code() {
+    let image = fixture("qr_code.png").into_rgba8();
+
+    for scope in [
+        SnappitBarcodeScope::TwoDimensional,
+        SnappitBarcodeScope::All,
+    ] {
+        let codes = SnappitQr::scan(image.clone(), scope).expect("QR scan failed on the fixture");
+
+        assert_eq!(codes.len(), 1);
+        assert_eq!(codes[0].value, "https://snappit.app");
+        assert_eq!(codes[0].format, SnappitBarcodeFormat::QrCode);
+    }
+}
+
+#[test]
+fn test_qr_finds_nothing_in_text() {
+    let codes = SnappitQr::scan(fixture("text.png").into_rgba8(), SnappitBarcodeScope::All)
+        .expect("QR scan failed on the fixture");
+
+    assert!(codes.is_empty());
+}
@@ -11,8 +11,15 @@ pub struct ImageSlot {
 
 pub static IMAGE: Lazy<Mutex<Option<ImageSlot>>> = Lazy::new(|| Mutex::new(None));
 
+/// Screen frozen for a delayed capture, served as `img://frozen`
+pub static FROZEN_IMAGE: Lazy<Mutex<Option<ImageSlot>>> = Lazy::new(|| Mutex::new(None));
+
 pub fn handle_img_request(req: &Request<Vec<u8>>) -> Response<Vec<u8>> {
-    let guard = IMAGE.lock().unwrap();
+    let slot = match req.uri().host() {
+        Some("frozen") => &FROZEN_IMAGE,
+        _ => &IMAGE,
+    };
+    let guard = slot.lock().unwrap();
     let origin = req
         .headers()
         .get("Origin")
@@ -2,25 +2,31 @@ mod img_protocol;
 #[cfg(target_os = "macos")]
 mod platform;
 mod region_capture;
+mod snappit_annotation;
 mod snappit_capturer;
 mod snappit_consts;
+mod snappit_coords;
+mod snappit_delayed_capture;
 mod snappit_errors;
+mod snappit_history;
 mod snappit_license;
 mod snappit_notifications;
 mod snappit_ocr;
 mod snappit_overlay;
 mod snappit_permissions;
-mod snappit_qr;
 mod snappit_res;
+mod snappit_screenshot;
 mod snappit_settings;
 mod snappit_shortcut_manager;
 mod snappit_sounds;
 mod snappit_store;
+mod snappit_translate;
 mod snappit_tray;
-mod text_processing;
 mod traits;
 
-use region_capture::{RegionCapture, RegionCaptureParams};
+use image::{ImageBuffer, Rgba};
+use region_capture::{RegionCapture, RegionCaptureParams, SnappitCaptureMode};
+use snappit_core::qr::{SnappitBarcodeScope, SnappitQr};
 use snappit_notifications::{SnappitNotificationPayload, SnappitNotifications};
 use snappit_overlay::SnappitOverlay;
 use snappit_shortcut_manager::SnappitShortcutManager;
@@ -29,21 +35,32 @@ use tauri::{async_runtime::spawn_blocking, AppHandle};
 
 use crate::{
     img_protocol::{handle_img_request, ImageSlot, IMAGE},
+    snappit_annotation::{draw_annotations, SnappitAnnotation},
     snappit_capturer::{SnappitCapturer, SnappitColorInfo},
-    snappit_errors::{SnappitError, SnappitResult},
+    snappit_delayed_capture::SnappitDelayedCapture,
+    snappit_errors::{SnappitError, SnappitResult, SnappitResultExt},
+    snappit_history::{
+        commands::{
+            add_ruler_history, clear_history, delete_history_items, export_history,
+            get_history_thumbnail, query_history,
+        },
+        SnappitHistory, SnappitHistoryEntry,
+    },
     snappit_license::{LicenseState, SnappitLicense},
     snappit_ocr::{
         commands::{
-            delete_tess_language, download_tess_language, get_system_languages_info,
-            get_system_tess_languages, get_tess_languages, is_macos,
+            cancel_tess_download, delete_tess_language, download_tess_language,
+            get_system_languages_info, get_system_tess_languages, get_system_tessdata_dirs,
+            get_tess_languages, import_tess_languages, is_macos,
         },
-        SnappitOcr, SnappitTesseractOcr,
+        SnappitOcr, SnappitTessdata,
     },
     snappit_overlay::SnappitOverlayTarget,
     snappit_permissions::{SnappitPermissions, SnappitPermissionsState},
-    snappit_qr::SnappitQr,
     snappit_res::SnappitResponse,
+    snappit_screenshot::SnappitScreenshot,
     snappit_settings::SnappitSettings,
+    snappit_translate::SnappitTranslate,
     traits::into_dynamic::IntoPngByes,
 };
 
@@ -52,6 +69,7 @@ async fn capture_color_at_cursor(
     app: AppHandle,
     x: u32,
     y: u32,
+    record: Option<bool>,
 ) -> tauri::Result<SnappitColorInfo> {
     let app_handle = app.clone();
 
@@ -60,6 +78,15 @@ async fn capture_color_at_cursor(
     })
     .await??;
 
+    // The dropper samples on every mouse move and only asks to record the picked color
+    if record.unwrap_or(false) {
+        let entry = SnappitHistoryEntry::Dropper {
+            hex: color_info.hex.clone(),
+            rgb: color_info.rgb,
+        };
+        SnappitHistory::record(&app, entry, None).log_on_err_with("Failed to record color");
+    }
+
     Ok(color_info)
 }
 
@@ -117,26 +144,178 @@ async fn on_capture(app: AppHandle, params: RegionCaptureParams) -> tauri::Resul
 
     let app_handle = app.clone();
 
-    let captured =
-        spawn_blocking(move || -> SnappitResult<_> { RegionCapture::capture(&app_handle, params) })
-            .await??;
+    let captured = spawn_blocking(move || -> SnappitResult<_> {
+        let image = RegionCapture::capture(&app_handle, params)?;
+        RegionCapture::remember_region(&app_handle, params)
+            .log_on_err_with("Failed to remember the captured region");
+
+        Ok(image)
+    })
+    .await??;
+
+    recognize_capture(app, captured, params).await
+}
+
+#[tauri::command]
+async fn repeat_last_region(app: AppHandle) -> tauri::Result<SnappitResponse> {
+    SnappitLicense::consume_use(&app)?;
+    let _ = SnappitTray::update_license_status(&app);
+
+    let app_handle = app.clone();
+
+    let (captured, params) =
+        spawn_blocking(move || RegionCapture::capture_last_region(&app_handle)).await??;
+
+    recognize_capture(app, captured, params).await
+}
+
+#[tauri::command]
+async fn on_capture_mode(
+    app: AppHandle,
+    mode: SnappitCaptureMode,
+) -> tauri::Result<SnappitResponse> {
+    SnappitLicense::consume_use(&app)?;
+    let _ = SnappitTray::update_license_status(&app);
+
+    let app_handle = app.clone();
+
+    let captured = spawn_blocking(move || -> SnappitResult<_> {
+        RegionCapture::capture_mode(&app_handle, mode)
+    })
+    .await??;
+    let params = RegionCaptureParams::covering(&captured);
+
+    recognize_capture(app, captured, params).await
+}
 
+/// Scans a capture for 2D codes and falls back to OCR when there are none. 1D barcodes
+/// are only looked for when OCR finds no text, stripes in text can decode as one
+async fn recognize_capture(
+    app: AppHandle,
+    captured: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    params: RegionCaptureParams,
+) -> tauri::Result<SnappitResponse> {
     let img_for_qr = captured.clone();
-    let img_for_ocr = captured;
+    let img_for_ocr = captured.clone();
     let app_for_ocr = app.clone();
 
-    let qr_handle =
-        spawn_blocking(move || -> SnappitResult<_> { Ok(SnappitQr::scan(img_for_qr)?) });
+    let qr_handle = spawn_blocking(move || -> SnappitResult<_> {
+        Ok(SnappitQr::scan(
+            img_for_qr,
+            SnappitBarcodeScope::TwoDimensional,
+        )?)
+    });
     let ocr_handle = spawn_blocking(move || -> SnappitResult<_> {
-        SnappitOcr::recognize(&app_for_ocr, img_for_ocr)
+        SnappitOcr::recognize(&app_for_ocr, img_for_ocr, &params)
     });
 
-    if let Some(qr) = qr_handle.await?? {
-        return Ok(SnappitResponse::Qr(Some(qr)));
-    }
+    let codes = qr_handle.await??;
+    let response = if !codes.is_empty() {
+        SnappitResponse::Qr(codes)
+    } else {
+        let result = ocr_handle.await??;
+
+        if result.value.trim().is_empty() {
+            let img_for_barcodes = captured.clone();
+            let barcodes = spawn_blocking(move || -> SnappitResult<_> {
+                Ok(SnappitQr::scan(img_for_barcodes, SnappitBarcodeScope::All)?)
+            })
+            .await??;
+
+            if barcodes.is_empty() {
+                SnappitResponse::Ocr(result)
+            } else {
+                SnappitResponse::Qr(barcodes)
+            }
+        } else {
+            SnappitResponse::Ocr(result)
+        }
+    };
+
+    let history_response = response.clone();
+    spawn_blocking(move || {
+        SnappitHistory::record_response(&app, &history_response, Some(&captured))
+            .log_on_err_with("Failed to record capture");
+    });
+
+    Ok(response)
+}
+
+#[tauri::command]
+async fn scan_region_table(
+    app: AppHandle,
+    params: RegionCaptureParams,
+) -> tauri::Result<SnappitResponse> {
+    SnappitLicense::consume_use(&app)?;
+    let _ = SnappitTray::update_license_status(&app);
+
+    let app_handle = app.clone();
+
+    let (response, captured) = spawn_blocking(move || -> SnappitResult<_> {
+        let image = RegionCapture::capture(&app_handle, params)?;
+        let table = SnappitOcr::recognize_table(&app_handle, image.clone())?;
+
+        Ok((SnappitResponse::Table(table), image))
+    })
+    .await??;
+
+    let history_response = response.clone();
+    spawn_blocking(move || {
+        SnappitHistory::record_response(&app, &history_response, Some(&captured))
+            .log_on_err_with("Failed to record table capture");
+    });
+
+    Ok(response)
+}
+
+#[tauri::command]
+async fn translate_region(
+    app: AppHandle,
+    params: RegionCaptureParams,
+) -> tauri::Result<SnappitResponse> {
+    SnappitLicense::consume_use(&app)?;
+    let _ = SnappitTray::update_license_status(&app);
+
+    let app_handle = app.clone();
 
-    let text = ocr_handle.await??;
-    Ok(SnappitResponse::Ocr(text))
+    let (response, captured) = spawn_blocking(move || -> SnappitResult<_> {
+        let image = RegionCapture::capture(&app_handle, params)?;
+        let result = SnappitOcr::recognize(&app_handle, image.clone(), &params)?;
+        let translation = SnappitTranslate::translate(&app_handle, &result)?;
+
+        Ok((SnappitResponse::Translation(translation), image))
+    })
+    .await??;
+
+    let history_response = response.clone();
+    spawn_blocking(move || {
+        SnappitHistory::record_response(&app, &history_response, Some(&captured))
+            .log_on_err_with("Failed to record translation");
+    });
+
+    Ok(response)
+}
+
+/// Saves the region with `annotations` drawn onto it as an image file and returns its path
+#[tauri::command]
+async fn save_screenshot(
+    app: AppHandle,
+    params: RegionCaptureParams,
+    annotations: Vec<SnappitAnnotation>,
+) -> tauri::Result<String> {
+    SnappitLicense::consume_use(&app)?;
+    let _ = SnappitTray::update_license_status(&app);
+
+    let path = spawn_blocking(move || -> SnappitResult<_> {
+        let mut image = RegionCapture::capture(&app, params)?;
+        let scale = image.width() as f32 / params.width.max(1) as f32;
+        draw_annotations(&mut image, &annotations, scale);
+
+        SnappitScreenshot::save(&app, &image)
+    })
+    .await??;
+
+    Ok(path.to_string_lossy().into_owned())
 }
 
 #[tauri::command]
@@ -148,27 +327,35 @@ async fn scan_region_qr(
 
     let task = spawn_blocking(move || -> SnappitResult<_> {
         let image = RegionCapture::capture(&app_handle, params)?;
-        SnappitQr::scan(image)
+        // The QR scanner was picked on purpose, so 1D barcodes count as well
+        let codes = SnappitQr::scan(image.clone(), SnappitBarcodeScope::All)?;
+
+        if !codes.is_empty() {
+            SnappitHistory::record(&app_handle, SnappitHistory::qr_entry(&codes), Some(&image))
+                .log_on_err_with("Failed to record QR scan");
+        }
+
+        Ok(codes)
     });
 
-    let qr_result = task.await??;
+    let codes = task.await??;
 
-    if qr_result.is_some() {
+    if !codes.is_empty() {
         SnappitLicense::consume_use(&app)?;
         let _ = SnappitTray::update_license_status(&app);
     }
 
-    Ok(SnappitResponse::Qr(qr_result))
+    Ok(SnappitResponse::Qr(codes))
 }
 
 #[tauri::command]
 fn show_snap_overlay(app: AppHandle, target: SnappitOverlayTarget) -> tauri::Result<()> {
-    if !SnappitTesseractOcr::are_system_languages_installed(&app).unwrap_or(false) {
+    if !SnappitTessdata::are_system_languages_installed(&app).unwrap_or(false) {
         SnappitSettings::show(&app)?;
         return Ok(());
     }
 
-    match SnappitOverlay::show(&app, target) {
+    match SnappitDelayedCapture::show(&app, target) {
         Ok(_) => Ok(()),
         Err(SnappitError::MissingPermissions(_)) => Ok(()),
         Err(err) => Err(err.into()),
@@ -186,6 +373,11 @@ fn get_snap_overlay_target() -> Option<SnappitOverlayTarget> {
     SnappitOverlay::get_current_target()
 }
 
+#[tauri::command]
+fn set_snap_overlay_selecting(selecting: bool) {
+    SnappitOverlay::set_selecting(selecting);
+}
+
 #[tauri::command]
 fn show_notification(app: AppHandle, payload: SnappitNotificationPayload) -> tauri::Result<()> {
     SnappitNotifications::notify(&app, payload)?;
@@ -277,6 +469,7 @@ pub fn run() -> tauri::Result<()> {
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_dialog::init())
         .plugin(
             tauri_plugin_log::Builder::new()
                 .level(log::LevelFilter::Debug)
@@ -294,7 +487,8 @@ pub fn run() -> tauri::Result<()> {
             app.handle()
                 .set_activation_policy(tauri::ActivationPolicy::Accessory)?;
 
-            SnappitTesseractOcr::ensure_initialized(app.handle())?;
+            SnappitTessdata::ensure_initialized(app.handle())?;
+            SnappitHistory::init(app.handle()).log_on_err_with("Failed to open capture history");
 
             if let Err(err) = SnappitOverlay::preload(app.handle()) {
                 log::warn!(
@@ -325,13 +519,19 @@ pub fn run() -> tauri::Result<()> {
             show_snap_overlay,
             hide_snap_overlay,
             get_snap_overlay_target,
+            set_snap_overlay_selecting,
             show_notification,
             hide_notification,
             animate_out_notification,
             show_settings,
             hide_settings,
             scan_region_qr,
+            scan_region_table,
+            translate_region,
+            save_screenshot,
             on_capture,
+            on_capture_mode,
+            repeat_last_region,
             capture_color_at_cursor,
             capture_magnified_view,
             get_last_shot_dim,
@@ -341,6 +541,9 @@ pub fn run() -> tauri::Result<()> {
             sync_shortcut,
             get_tess_languages,
             download_tess_language,
+            cancel_tess_download,
+            import_tess_languages,
+            get_system_tessdata_dirs,
             delete_tess_language,
             get_system_tess_languages,
             get_system_languages_info,
@@ -353,6 +556,12 @@ pub fn run() -> tauri::Result<()> {
             update_tray_license_status,
             set_update_ready,
             is_update_ready,
+            query_history,
+            get_history_thumbnail,
+            delete_history_items,
+            clear_history,
+            export_history,
+            add_ruler_history,
         ])
         .run(tauri::generate_context!());
 }
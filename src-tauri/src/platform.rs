@@ -1,3 +1,4 @@
+use crate::snappit_coords::{Global, Physical, SnappitPoint, SnappitRect, SnappitScale, XCapUnit};
 use crate::snappit_errors::{SnappitError, SnappitResult};
 use tauri::{LogicalPosition, LogicalSize, Monitor, Wry};
 use xcap::Monitor as XCapMonitor;
@@ -63,52 +64,47 @@ impl Platform {
         Err(SnappitError::MonitorNotFound)
     }
 
-    pub fn xcap_monitor_from_cursor(app: &tauri::AppHandle<Wry>) -> SnappitResult<XCapMonitor> {
+    /// Cursor position in the coordinate space of xcap monitors and windows
+    pub fn xcap_cursor_position(
+        app: &tauri::AppHandle<Wry>,
+    ) -> SnappitResult<SnappitPoint<XCapUnit, Global>> {
         let cursor_pos = app.cursor_position()?;
-        let mut monitors = XCapMonitor::all()?;
-        let primary = app.primary_monitor()?;
+        // Tauri reports the cursor in pixels, on macOS at the scale of the primary monitor
+        let primary_scale = app
+            .primary_monitor()?
+            .map(|m| SnappitScale::new(m.scale_factor()))
+            .unwrap_or(SnappitScale::ONE);
+
+        Ok(
+            SnappitPoint::<Physical, Global>::new(cursor_pos.x, cursor_pos.y)
+                .convert::<XCapUnit>(primary_scale),
+        )
+    }
 
-        #[cfg(target_os = "macos")]
-        let primary_scale = primary.as_ref().map(|m| m.scale_factor()).unwrap_or(1.0);
-        #[cfg(target_os = "macos")]
-        let cursor_logical: LogicalPosition<f64> = cursor_pos.to_logical(primary_scale);
+    /// xcap monitor showing the same screen as the tauri `monitor`
+    pub fn xcap_monitor_of(monitor: &Monitor) -> SnappitResult<XCapMonitor> {
+        let position = monitor.position();
+        let origin = SnappitPoint::<Physical, Global>::new(position.x as f64, position.y as f64)
+            .convert::<XCapUnit>(SnappitScale::new(monitor.scale_factor()));
+
+        XCapMonitor::all()?
+            .into_iter()
+            .find(|m| {
+                SnappitRect::of_monitor(m).is_ok_and(|bounds| {
+                    (bounds.x - origin.x).abs() < 1.0 && (bounds.y - origin.y).abs() < 1.0
+                })
+            })
+            .ok_or(SnappitError::MonitorNotFound)
+    }
 
-        if let Some(index) = monitors.iter().position(|m| {
-            let width = match m.width() {
-                Ok(s) => s as f64,
-                Err(_) => return false,
-            };
-            let height = match m.height() {
-                Ok(s) => s as f64,
-                Err(_) => return false,
-            };
-            let x = match m.x() {
-                Ok(s) => s as f64,
-                Err(_) => return false,
-            };
-            let y = match m.y() {
-                Ok(s) => s as f64,
-                Err(_) => return false,
-            };
-
-            let x_max = x + width;
-            let y_max = y + height;
-
-            #[cfg(target_os = "macos")]
-            {
-                cursor_logical.x >= x
-                    && cursor_logical.x < x_max
-                    && cursor_logical.y >= y
-                    && cursor_logical.y < y_max
-            }
-            #[cfg(not(target_os = "macos"))]
-            {
-                cursor_pos.x >= x
-                    && cursor_pos.x < x_max
-                    && cursor_pos.y >= y
-                    && cursor_pos.y < y_max
-            }
-        }) {
+    pub fn xcap_monitor_from_cursor(app: &tauri::AppHandle<Wry>) -> SnappitResult<XCapMonitor> {
+        let cursor = Self::xcap_cursor_position(app)?;
+        let mut monitors = XCapMonitor::all()?;
+
+        if let Some(index) = monitors
+            .iter()
+            .position(|m| SnappitRect::of_monitor(m).is_ok_and(|bounds| bounds.contains(cursor)))
+        {
             return Ok(monitors.swap_remove(index));
         }
 
@@ -1,23 +1,105 @@
-use crate::snappit_errors::SnappitResult;
+use crate::img_protocol::{ImageSlot, FROZEN_IMAGE};
+use crate::snappit_capturer::SnappitCapturer;
+use crate::snappit_consts::SNAPPIT_CONSTS;
+use crate::snappit_coords::{
+    Global, Logical, MonitorRelative, Physical, SnappitRect, SnappitScale, XCapUnit,
+};
+use crate::snappit_errors::{SnappitError, SnappitResult};
+use crate::snappit_store::SnappitStore;
+use crate::traits::into_dynamic::IntoPngByes;
+use image::imageops::{self, FilterType};
 use image::{ImageBuffer, Rgba};
-use serde::Deserialize;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use snappit_core::ocr::SnappitPageSegMode;
+use std::sync::Mutex;
 use tauri::{AppHandle, Wry};
-use xcap::Monitor;
+use xcap::{Monitor, Window};
 
 use crate::platform::Platform;
+use crate::snappit_overlay::SnappitOverlay;
 
 fn normalized(filename: String) -> String {
     filename.replace(['|', '\\', ':', '/'], "")
 }
 
-#[derive(Debug, Deserialize)]
+/// Selection in logical points relative to the origin of the overlay's monitor, it may
+/// extend past that monitor onto its neighbours
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct RegionCaptureParams {
-    pub x: u32,
-    pub y: u32,
+    pub x: i32,
+    pub y: i32,
     pub width: u32,
     pub height: u32,
 }
 
+impl RegionCaptureParams {
+    /// Region covering all of `image`, for captures made without a selection
+    pub fn covering(image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            width: image.width(),
+            height: image.height(),
+        }
+    }
+
+    pub fn rect(&self) -> SnappitRect<Logical, MonitorRelative> {
+        SnappitRect::new(
+            self.x as f64,
+            self.y as f64,
+            self.width as f64,
+            self.height as f64,
+        )
+    }
+
+    /// Page segmentation for the selection, see [`SnappitPageSegMode::for_region`]
+    pub fn page_seg_mode(&self, configured: SnappitPageSegMode) -> SnappitPageSegMode {
+        configured.for_region(self.width, self.height)
+    }
+}
+
+/// Region of the last capture with a selection and the monitor it was on, kept in the
+/// store so it can be captured again without the overlay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitLastRegion {
+    #[serde(flatten)]
+    pub params: RegionCaptureParams,
+    pub monitor_id: u32,
+    pub monitor_name: String,
+}
+
+/// What to capture when no region is selected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitCaptureMode {
+    /// Monitor under the cursor
+    Monitor,
+    /// All monitors, stitched together as they are arranged
+    AllMonitors,
+    /// Window that had focus before the overlay was shown
+    FocusedWindow,
+    /// Topmost window under the cursor
+    HoveredWindow,
+}
+
+/// Capture of one monitor and its bounds in the desktop layout
+struct SnappitMonitorShot {
+    bounds: SnappitRect<XCapUnit, Global>,
+    image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+}
+
+/// Screen of one monitor captured ahead of the selection, see [`RegionCapture::freeze`]
+struct SnappitFrozenFrame {
+    monitor_id: u32,
+    image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+}
+
+static FROZEN_FRAME: Lazy<Mutex<Option<SnappitFrozenFrame>>> = Lazy::new(|| Mutex::new(None));
+
+#[cfg(not(target_os = "macos"))]
+static FOCUSED_WINDOW: Lazy<Mutex<Option<u32>>> = Lazy::new(|| Mutex::new(None));
+
 pub struct RegionCapture;
 
 impl RegionCapture {
@@ -25,16 +107,71 @@ impl RegionCapture {
         app: &AppHandle<Wry>,
         params: RegionCaptureParams,
     ) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
-        let monitor = Platform::xcap_monitor_from_cursor(app)?;
-        let monitor_w = monitor.width()?;
-        let monitor_h = monitor.height()?;
+        Self::capture_on(&Self::selection_monitor(app)?, params)
+    }
 
-        let x = params.x.clamp(0, monitor_w);
-        let y = params.y.clamp(0, monitor_h);
-        let width = (params.width).clamp(0, monitor_w - x);
-        let height = (params.height).clamp(0, monitor_h - y);
+    /// Monitor selections were made on, the cursor may be on another one by now
+    pub fn selection_monitor(app: &AppHandle<Wry>) -> SnappitResult<Monitor> {
+        SnappitOverlay::xcap_monitor().or_else(|_| Platform::xcap_monitor_from_cursor(app))
+    }
+
+    /// Captures the last region again on the monitor it was selected on
+    pub fn capture_last_region(
+        app: &AppHandle<Wry>,
+    ) -> SnappitResult<(ImageBuffer<Rgba<u8>, Vec<u8>>, RegionCaptureParams)> {
+        let last = Self::last_region(app).ok_or(SnappitError::NoLastRegion)?;
+        let image = Self::capture_on(&Self::monitor_of(&last)?, last.params)?;
 
-        let image = monitor.capture_region(x, y, width, height)?;
+        Ok((image, last.params))
+    }
+
+    /// Captures `params` relative to `monitor`, parts on other monitors are captured from
+    /// them and stitched in
+    fn capture_on(
+        monitor: &Monitor,
+        params: RegionCaptureParams,
+    ) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let origin = SnappitRect::of_monitor(monitor)?.origin();
+        let region = params
+            .rect()
+            .convert::<XCapUnit>(SnappitScale::of_monitor(monitor)?)
+            .to_global(origin);
+
+        let mut shots = Vec::new();
+        for monitor in Monitor::all()? {
+            if let Some(shot) = Self::capture_part(&monitor, &region)? {
+                shots.push(shot);
+            }
+        }
+
+        let image = if shots.len() == 1 {
+            shots.remove(0).image
+        } else {
+            Self::stitch(&shots).ok_or(SnappitError::RegionOutsideMonitors)?
+        };
+
+        #[cfg(debug_assertions)]
+        Self::save_image(&image);
+
+        Ok(image)
+    }
+
+    pub fn capture_mode(
+        app: &AppHandle<Wry>,
+        mode: SnappitCaptureMode,
+    ) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let image = match mode {
+            SnappitCaptureMode::Monitor => {
+                SnappitCapturer::capture_monitor(&Platform::xcap_monitor_from_cursor(app)?)?
+            }
+            SnappitCaptureMode::AllMonitors => Self::capture_all_monitors()?,
+            SnappitCaptureMode::FocusedWindow => {
+                SnappitCapturer::capture_window(&Self::focused_window()?)?
+            }
+            SnappitCaptureMode::HoveredWindow => {
+                SnappitCapturer::capture_window(&Self::hovered_window(app)?)?
+            }
+        };
 
         #[cfg(debug_assertions)]
         Self::save_image(&image);
@@ -42,6 +179,241 @@ impl RegionCapture {
         Ok(image)
     }
 
+    /// Captures the monitor under the cursor as it is now, region captures on that
+    /// monitor are cut from this frame until [`RegionCapture::unfreeze`]
+    ///
+    /// Menus and popovers close once the overlay is clicked, the frame keeps them.
+    pub fn freeze(app: &AppHandle<Wry>) -> SnappitResult<()> {
+        let monitor = Platform::xcap_monitor_from_cursor(app)?;
+        let image = SnappitCapturer::capture_monitor(&monitor)?;
+        let (width, height) = image.dimensions();
+
+        *FROZEN_IMAGE.lock().unwrap() = Some(ImageSlot {
+            bytes: image.clone().into_png_bytes()?,
+            width,
+            height,
+        });
+        *FROZEN_FRAME.lock().unwrap() = Some(SnappitFrozenFrame {
+            monitor_id: monitor.id()?,
+            image,
+        });
+
+        Ok(())
+    }
+
+    pub fn unfreeze() {
+        FROZEN_FRAME.lock().unwrap().take();
+        FROZEN_IMAGE.lock().unwrap().take();
+    }
+
+    /// Part of `region` shown on `monitor`, cut from the frozen frame when there is one
+    fn capture_part(
+        monitor: &Monitor,
+        region: &SnappitRect<XCapUnit, Global>,
+    ) -> SnappitResult<Option<SnappitMonitorShot>> {
+        let bounds = SnappitRect::of_monitor(monitor)?;
+        let Some(part) = region.intersection(&bounds) else {
+            return Ok(None);
+        };
+
+        let local = part.relative_to(bounds.origin());
+        let image = match Self::frozen_region(monitor, &local)? {
+            Some(image) => image,
+            None => {
+                let (x, y, width, height) = local.to_region();
+                monitor.capture_region(x, y, width, height)?
+            }
+        };
+
+        Ok(Some(SnappitMonitorShot {
+            bounds: part,
+            image,
+        }))
+    }
+
+    /// Part of the frozen frame of the monitor, if it was frozen
+    fn frozen_region(
+        monitor: &Monitor,
+        region: &SnappitRect<XCapUnit, MonitorRelative>,
+    ) -> SnappitResult<Option<ImageBuffer<Rgba<u8>, Vec<u8>>>> {
+        let monitor_id = monitor.id()?;
+        let frozen = FROZEN_FRAME.lock().unwrap();
+        let Some(frame) = frozen
+            .as_ref()
+            .filter(|frame| frame.monitor_id == monitor_id)
+        else {
+            return Ok(None);
+        };
+
+        // Frames are in physical pixels
+        let (x, y, width, height) = region
+            .convert::<Physical>(SnappitScale::of_monitor(monitor)?)
+            .to_region();
+
+        Ok(Some(
+            imageops::crop_imm(&frame.image, x, y, width, height).to_image(),
+        ))
+    }
+
+    /// Stores `params` as the last region, on the monitor it was selected on
+    pub fn remember_region(app: &AppHandle<Wry>, params: RegionCaptureParams) -> SnappitResult<()> {
+        let monitor = Self::selection_monitor(app)?;
+        let last = SnappitLastRegion {
+            params,
+            monitor_id: monitor.id()?,
+            monitor_name: monitor.name()?,
+        };
+
+        SnappitStore::set_value(
+            app,
+            &SNAPPIT_CONSTS.store.keys.last_region,
+            Some(serde_json::to_value(last)?),
+        )
+    }
+
+    /// Region of the last capture made with a selection
+    pub fn last_region(app: &AppHandle<Wry>) -> Option<SnappitLastRegion> {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.last_region)
+            .ok()
+            .flatten()
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// Monitor the last region was selected on, found by name when its id changed
+    /// after it was reconnected
+    fn monitor_of(last: &SnappitLastRegion) -> SnappitResult<Monitor> {
+        let monitors = Monitor::all()?;
+
+        monitors
+            .iter()
+            .find(|monitor| monitor.id().is_ok_and(|id| id == last.monitor_id))
+            .or_else(|| {
+                monitors
+                    .iter()
+                    .find(|monitor| monitor.name().is_ok_and(|name| name == last.monitor_name))
+            })
+            .cloned()
+            .ok_or(SnappitError::MonitorNotFound)
+    }
+
+    /// Remembers the focused window while it still has focus, before the overlay takes it
+    #[cfg(not(target_os = "macos"))]
+    pub fn remember_focused_window() {
+        let focused = Self::windows().ok().and_then(|windows| {
+            windows
+                .into_iter()
+                .find(|window| window.is_focused().unwrap_or(false))
+                .and_then(|window| window.id().ok())
+        });
+
+        *FOCUSED_WINDOW.lock().unwrap() = focused;
+    }
+
+    fn capture_all_monitors() -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let shots = Monitor::all()?
+            .iter()
+            .map(|monitor| {
+                Ok(SnappitMonitorShot {
+                    bounds: SnappitRect::of_monitor(monitor)?,
+                    image: SnappitCapturer::capture_monitor(monitor)?,
+                })
+            })
+            .collect::<SnappitResult<Vec<_>>>()?;
+
+        Self::stitch(&shots).ok_or(SnappitError::MonitorNotFound)
+    }
+
+    /// Places monitor captures the way the monitors are arranged, at the pixel density
+    /// of the sharpest one, gaps between monitors are left white
+    fn stitch(shots: &[SnappitMonitorShot]) -> Option<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        let bounds = shots
+            .iter()
+            .map(|shot| shot.bounds)
+            .reduce(|bounds, other| bounds.union(&other))?;
+        // Pixels per xcap unit of the sharpest shot, the others are scaled up to it
+        let scale = shots
+            .iter()
+            .map(|shot| shot.image.width() as f64 / shot.bounds.width.max(1.0))
+            .fold(1.0, f64::max);
+
+        let scaled = |value: f64| (value * scale).round();
+        let mut canvas = ImageBuffer::from_pixel(
+            scaled(bounds.width) as u32,
+            scaled(bounds.height) as u32,
+            Rgba([255, 255, 255, 255]),
+        );
+
+        for shot in shots {
+            let placed = shot.bounds.relative_to(bounds.origin());
+            let width = scaled(placed.width) as u32;
+            let height = scaled(placed.height) as u32;
+            let x = scaled(placed.x) as i64;
+            let y = scaled(placed.y) as i64;
+
+            if shot.image.dimensions() == (width, height) {
+                imageops::replace(&mut canvas, &shot.image, x, y);
+            } else {
+                let resized = imageops::resize(&shot.image, width, height, FilterType::Triangle);
+                imageops::replace(&mut canvas, &resized, x, y);
+            }
+        }
+
+        Some(canvas)
+    }
+
+    /// Windows of other apps that can be captured, front to back
+    fn windows() -> SnappitResult<Vec<Window>> {
+        let own_pid = std::process::id();
+
+        Ok(Window::all()?
+            .into_iter()
+            .filter(|window| {
+                window.pid().is_ok_and(|pid| pid != own_pid)
+                    && !window.is_minimized().unwrap_or(true)
+                    && window.width().unwrap_or(0) > 0
+                    && window.height().unwrap_or(0) > 0
+            })
+            .collect())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn focused_window() -> SnappitResult<Window> {
+        // The overlay is focused by now, the app in front before it knows the window
+        let pid = SnappitOverlay::previous_app_pid().ok_or(SnappitError::WindowNotFound)?;
+
+        Self::windows()?
+            .into_iter()
+            .find(|window| window.pid().is_ok_and(|window_pid| window_pid == pid))
+            .ok_or(SnappitError::WindowNotFound)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn focused_window() -> SnappitResult<Window> {
+        let id = FOCUSED_WINDOW
+            .lock()
+            .unwrap()
+            .ok_or(SnappitError::WindowNotFound)?;
+
+        Self::windows()?
+            .into_iter()
+            .find(|window| window.id().is_ok_and(|window_id| window_id == id))
+            .ok_or(SnappitError::WindowNotFound)
+    }
+
+    /// Name of the app whose window had focus before the overlay was shown
+    pub fn focused_app_name() -> Option<String> {
+        Self::focused_window().ok()?.app_name().ok()
+    }
+
+    fn hovered_window(app: &AppHandle<Wry>) -> SnappitResult<Window> {
+        let cursor = Platform::xcap_cursor_position(app)?;
+
+        Self::windows()?
+            .into_iter()
+            .find(|window| SnappitRect::of_window(window).is_ok_and(|rect| rect.contains(cursor)))
+            .ok_or(SnappitError::WindowNotFound)
+    }
+
     pub fn save_image(image: &ImageBuffer<Rgba<u8>, Vec<u8>>) {
         let monitors = Monitor::all().expect("asd");
 
@@ -58,3 +430,30 @@ impl RegionCapture {
             .unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shot(x: i32, y: i32, width: u32, height: u32, scale: u32, value: u8) -> SnappitMonitorShot {
+        SnappitMonitorShot {
+            bounds: SnappitRect::new(x as f64, y as f64, width as f64, height as f64),
+            image: ImageBuffer::from_pixel(width * scale, height * scale, Rgba([value, 0, 0, 255])),
+        }
+    }
+
+    #[test]
+    fn test_stitch_places_monitors_by_layout() {
+        // A Retina monitor at the origin and a regular one to its left, a bit lower
+        let shots = [shot(0, 0, 100, 50, 2, 10), shot(-50, 10, 50, 40, 1, 20)];
+        let canvas = RegionCapture::stitch(&shots).unwrap();
+
+        assert_eq!(canvas.dimensions(), (300, 100));
+        assert_eq!(canvas.get_pixel(100, 0)[0], 10);
+        assert_eq!(canvas.get_pixel(299, 99)[0], 10);
+        assert_eq!(canvas.get_pixel(0, 20)[0], 20);
+        assert_eq!(canvas.get_pixel(99, 99)[0], 20);
+        assert_eq!(*canvas.get_pixel(0, 0), Rgba([255, 255, 255, 255]));
+        assert!(RegionCapture::stitch(&[]).is_none());
+    }
+}
@@ -0,0 +1,261 @@
+//! Annotations
+//!
+//! Shapes drawn onto a screenshot before it's saved. The overlay sends them in logical
+//! points relative to the selection, they are scaled to the pixels of the capture so
+//! they look the same on every display.
+
+use image::{ImageBuffer, Rgba};
+use serde::Deserialize;
+
+/// Outline and arrow color
+const STROKE_COLOR: Rgba<u8> = Rgba([255, 59, 48, 255]);
+
+/// Highlighter color, blended over the image with `HIGHLIGHT_OPACITY`
+const HIGHLIGHT_COLOR: Rgba<u8> = Rgba([255, 214, 10, 255]);
+const HIGHLIGHT_OPACITY: f32 = 0.4;
+
+/// Line width in points
+const STROKE_WIDTH: f32 = 3.0;
+
+/// Length of the arrow head sides in points and their angle to the shaft
+const ARROW_HEAD_LENGTH: f32 = 14.0;
+const ARROW_HEAD_ANGLE: f32 = 28.0 * std::f32::consts::PI / 180.0;
+
+/// Shape drawn over the selection, coordinates are logical points from its top left
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SnappitAnnotation {
+    /// Outlined rectangle
+    Rect {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+    /// Arrow pointing from the first point to the second
+    Arrow { x1: f32, y1: f32, x2: f32, y2: f32 },
+    /// Translucent filled rectangle
+    Highlight {
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+    },
+}
+
+type Point = (f32, f32);
+
+/// Pixel range covering `from..=to`, clamped to `0..limit`
+fn pixel_range(from: f32, to: f32, limit: u32) -> std::ops::Range<u32> {
+    let start = from.floor().max(0.0) as u32;
+    let end = (to.ceil().max(0.0) as u32).min(limit);
+
+    start..end.max(start)
+}
+
+/// Distance from `point` to the segment `from`..`to`
+fn segment_distance(point: Point, from: Point, to: Point) -> f32 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = dx * dx + dy * dy;
+    let t = if length == 0.0 {
+        0.0
+    } else {
+        (((point.0 - from.0) * dx + (point.1 - from.1) * dy) / length).clamp(0.0, 1.0)
+    };
+
+    (point.0 - from.0 - t * dx).hypot(point.1 - from.1 - t * dy)
+}
+
+/// Line `width` pixels wide with round ends, in image pixels
+fn draw_segment(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, from: Point, to: Point, width: f32) {
+    let half = width / 2.0;
+    let xs = pixel_range(
+        from.0.min(to.0) - half,
+        from.0.max(to.0) + half,
+        image.width(),
+    );
+    let ys = pixel_range(
+        from.1.min(to.1) - half,
+        from.1.max(to.1) + half,
+        image.height(),
+    );
+
+    for y in ys {
+        for x in xs.clone() {
+            let center = (x as f32 + 0.5, y as f32 + 0.5);
+            if segment_distance(center, from, to) <= half {
+                image.put_pixel(x, y, STROKE_COLOR);
+            }
+        }
+    }
+}
+
+fn draw_arrow(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, from: Point, to: Point, scale: f32) {
+    let width = STROKE_WIDTH * scale;
+    draw_segment(image, from, to, width);
+
+    if from == to {
+        return;
+    }
+
+    let angle = (from.1 - to.1).atan2(from.0 - to.0);
+    let length = ARROW_HEAD_LENGTH * scale;
+    for side in [ARROW_HEAD_ANGLE, -ARROW_HEAD_ANGLE] {
+        let end = (
+            to.0 + length * (angle + side).cos(),
+            to.1 + length * (angle + side).sin(),
+        );
+        draw_segment(image, to, end, width);
+    }
+}
+
+/// Rectangle corners in image pixels, whichever way it was dragged
+fn corners(x: f32, y: f32, width: f32, height: f32, scale: f32) -> (Point, Point) {
+    let (left, right) = (x.min(x + width) * scale, x.max(x + width) * scale);
+    let (top, bottom) = (y.min(y + height) * scale, y.max(y + height) * scale);
+
+    ((left, top), (right, bottom))
+}
+
+fn draw_rect(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, corners: (Point, Point), scale: f32) {
+    let ((left, top), (right, bottom)) = corners;
+    let width = STROKE_WIDTH * scale;
+
+    draw_segment(image, (left, top), (right, top), width);
+    draw_segment(image, (right, top), (right, bottom), width);
+    draw_segment(image, (right, bottom), (left, bottom), width);
+    draw_segment(image, (left, bottom), (left, top), width);
+}
+
+fn draw_highlight(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, corners: (Point, Point)) {
+    let ((left, top), (right, bottom)) = corners;
+
+    for y in pixel_range(top, bottom, image.height()) {
+        for x in pixel_range(left, right, image.width()) {
+            let pixel = image.get_pixel_mut(x, y);
+            for channel in 0..3 {
+                let blended = f32::from(pixel[channel]) * (1.0 - HIGHLIGHT_OPACITY)
+                    + f32::from(HIGHLIGHT_COLOR[channel]) * HIGHLIGHT_OPACITY;
+                pixel[channel] = blended.round() as u8;
+            }
+        }
+    }
+}
+
+/// Draws `annotations` onto `image` in order, `scale` is the number of image pixels
+/// per logical point
+pub fn draw_annotations(
+    image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    annotations: &[SnappitAnnotation],
+    scale: f32,
+) {
+    for annotation in annotations {
+        match *annotation {
+            SnappitAnnotation::Rect {
+                x,
+                y,
+                width,
+                height,
+            } => draw_rect(image, corners(x, y, width, height, scale), scale),
+            SnappitAnnotation::Arrow { x1, y1, x2, y2 } => draw_arrow(
+                image,
+                (x1 * scale, y1 * scale),
+                (x2 * scale, y2 * scale),
+                scale,
+            ),
+            SnappitAnnotation::Highlight {
+                x,
+                y,
+                width,
+                height,
+            } => draw_highlight(image, corners(x, y, width, height, scale)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+    fn blank(width: u32, height: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+        ImageBuffer::from_pixel(width, height, WHITE)
+    }
+
+    #[test]
+    fn test_rect_draws_outline() {
+        let mut image = blank(100, 100);
+        let rect = SnappitAnnotation::Rect {
+            x: 40.0,
+            y: 40.0,
+            width: -30.0,
+            height: -30.0,
+        };
+        draw_annotations(&mut image, &[rect], 2.0);
+
+        assert_eq!(*image.get_pixel(20, 50), STROKE_COLOR);
+        assert_eq!(*image.get_pixel(80, 79), STROKE_COLOR);
+        assert_eq!(*image.get_pixel(50, 50), WHITE);
+        assert_eq!(*image.get_pixel(10, 10), WHITE);
+    }
+
+    #[test]
+    fn test_arrow_draws_shaft_and_head() {
+        let mut image = blank(100, 100);
+        let arrow = SnappitAnnotation::Arrow {
+            x1: 10.0,
+            y1: 50.0,
+            x2: 90.0,
+            y2: 50.0,
+        };
+        draw_annotations(&mut image, &[arrow], 1.0);
+
+        assert_eq!(*image.get_pixel(50, 50), STROKE_COLOR);
+        // Both sides of the head run back from the tip
+        assert_eq!(*image.get_pixel(80, 45), STROKE_COLOR);
+        assert_eq!(*image.get_pixel(80, 54), STROKE_COLOR);
+        assert_eq!(*image.get_pixel(50, 45), WHITE);
+        assert_eq!(*image.get_pixel(95, 50), WHITE);
+    }
+
+    #[test]
+    fn test_highlight_blends_over_image() {
+        let mut image = blank(20, 20);
+        let highlight = SnappitAnnotation::Highlight {
+            x: 5.0,
+            y: 5.0,
+            width: 50.0,
+            height: 5.0,
+        };
+        draw_annotations(&mut image, &[highlight], 1.0);
+
+        assert_eq!(*image.get_pixel(7, 7), Rgba([255, 239, 157, 255]));
+        assert_eq!(*image.get_pixel(19, 9), Rgba([255, 239, 157, 255]));
+        assert_eq!(*image.get_pixel(7, 10), WHITE);
+        assert_eq!(*image.get_pixel(4, 7), WHITE);
+    }
+
+    #[test]
+    fn test_annotations_are_clipped_to_image() {
+        let mut image = blank(10, 10);
+        let annotations = [
+            SnappitAnnotation::Rect {
+                x: -20.0,
+                y: -20.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            SnappitAnnotation::Arrow {
+                x1: -5.0,
+                y1: -5.0,
+                x2: 50.0,
+                y2: 50.0,
+            },
+        ];
+        draw_annotations(&mut image, &annotations, 1.0);
+
+        assert_eq!(*image.get_pixel(0, 0), STROKE_COLOR);
+        assert_eq!(*image.get_pixel(9, 0), WHITE);
+    }
+}
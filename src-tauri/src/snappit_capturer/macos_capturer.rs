@@ -25,6 +25,7 @@ use tauri::AppHandle;
 
 // CGWindowList constants
 const K_CG_WINDOW_LIST_OPTION_ON_SCREEN_ONLY: u32 = 1 << 0;
+const K_CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW: u32 = 1 << 3;
 const K_CG_NULL_WINDOW_ID: u32 = 0;
 
 // CGWindowImageOption to exclude cursor from capture
@@ -35,6 +36,7 @@ const K_CG_WINDOW_IMAGE_SHOULD_BE_OPAQUE: u32 = 1 << 3;
 // CoreGraphics functions
 #[link(name = "CoreGraphics", kind = "framework")]
 extern "C" {
+    static CGRectNull: CGRect;
     fn CGWindowListCreateImage(
         screenBounds: CGRect,
         listOption: u32,
@@ -151,16 +153,37 @@ fn capture_region_to_srgb(
     width: f64,
     height: f64,
 ) -> SnappitResult<CapturedPixels> {
-    unsafe {
-        let rect = CGRect::new(&CGPoint::new(x, y), &CGSize::new(width, height));
+    let rect = CGRect::new(&CGPoint::new(x, y), &CGSize::new(width, height));
+
+    capture_to_srgb(
+        rect,
+        K_CG_WINDOW_LIST_OPTION_ON_SCREEN_ONLY,
+        K_CG_NULL_WINDOW_ID,
+    )
+}
 
+/// Capture a single window by its CGWindowID and convert to sRGB color space
+///
+/// The window is captured on its own, windows overlapping it are left out
+fn capture_window_to_srgb(window_id: u32) -> SnappitResult<CapturedPixels> {
+    let rect = unsafe { CGRectNull };
+
+    capture_to_srgb(rect, K_CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW, window_id)
+}
+
+fn capture_to_srgb(
+    rect: CGRect,
+    list_option: u32,
+    window_id: u32,
+) -> SnappitResult<CapturedPixels> {
+    unsafe {
         // Capture the screen - image is in display's native color space
         // Use kCGWindowImageShouldBeOpaque to exclude cursor from capture
         // This ensures consistent behavior even when screen recording apps change cursor rendering
         let image = CGWindowListCreateImage(
             rect,
-            K_CG_WINDOW_LIST_OPTION_ON_SCREEN_ONLY,
-            K_CG_NULL_WINDOW_ID,
+            list_option,
+            window_id,
             kCGWindowImageBestResolution
                 | kCGWindowImageBoundsIgnoreFraming
                 | K_CG_WINDOW_IMAGE_SHOULD_BE_OPAQUE,
@@ -250,6 +273,33 @@ fn capture_region_to_srgb(
     }
 }
 
+/// Capture an area of the screen, in global logical points, as an sRGB image
+pub fn capture_area(
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+    let captured = capture_region_to_srgb(x, y, width, height)?;
+
+    Ok(captured_to_image(&captured))
+}
+
+/// Capture a window by its CGWindowID as an sRGB image
+pub fn capture_window(window_id: u32) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+    let captured = capture_window_to_srgb(window_id)?;
+
+    Ok(captured_to_image(&captured))
+}
+
+/// Convert captured BGRA pixels into an opaque RGBA image
+fn captured_to_image(captured: &CapturedPixels) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    ImageBuffer::from_fn(captured.width as u32, captured.height as u32, |x, y| {
+        let color = get_srgb_color_at(captured, x as usize, y as usize);
+        Rgba([color.r, color.g, color.b, 255])
+    })
+}
+
 /// Get sRGB color at a position in the captured pixels
 fn get_srgb_color_at(captured: &CapturedPixels, x: usize, y: usize) -> SrgbColor {
     if x >= captured.width || y >= captured.height {
@@ -4,8 +4,10 @@
 //! using the xcap library for cross-platform screen capture.
 
 use crate::platform::Platform;
-use crate::region_capture::{RegionCapture, RegionCaptureParams};
 use crate::snappit_capturer::SnappitColorInfo;
+use crate::snappit_coords::{
+    Logical, MonitorRelative, Physical, SnappitPoint, SnappitRect, SnappitScale,
+};
 use crate::snappit_errors::SnappitResult;
 use image::{ImageBuffer, Rgba};
 use tauri::AppHandle;
@@ -62,56 +64,43 @@ fn capture_logical_grid(
 ) -> SnappitResult<Vec<Rgba<u8>>> {
     let size = radius * 2 + 1;
 
-    let tauri_monitor = Platform::monitor_from_cursor(app)?;
-    let mut scale = tauri_monitor.scale_factor();
-    if scale < 1.0 {
-        scale = 1.0;
-    }
-    let scale = scale as f64;
-
-    let xcap_monitor = Platform::xcap_monitor_from_cursor(app)?;
-    let monitor_width = f64::from(xcap_monitor.width()?);
-    let monitor_height = f64::from(xcap_monitor.height()?);
-
-    let mut center_x = (x as f64) * scale;
-    let mut center_y = (y as f64) * scale;
-
-    if monitor_width >= 1.0 {
-        center_x = center_x.clamp(0.0, monitor_width - 1.0);
-    } else {
-        center_x = 0.0;
-    }
-
-    if monitor_height >= 1.0 {
-        center_y = center_y.clamp(0.0, monitor_height - 1.0);
-    } else {
-        center_y = 0.0;
-    }
-
-    let cell_span = scale;
-    let half_cell = cell_span * 0.5;
-
-    let logical_start_x = center_x - (radius as f64 * cell_span) - half_cell;
-    let logical_start_y = center_y - (radius as f64 * cell_span) - half_cell;
-
-    let mut capture_left = logical_start_x.floor() as i64;
-    let mut capture_top = logical_start_y.floor() as i64;
-
-    let max_x = monitor_width.max(1.0).ceil() as i64;
-    let max_y = monitor_height.max(1.0).ceil() as i64;
-
-    capture_left = capture_left.clamp(0, max_x.saturating_sub(1));
-    capture_top = capture_top.clamp(0, max_y.saturating_sub(1));
-
-    let image = RegionCapture::capture_around_cursor(
-        app,
-        RegionCaptureParams {
-            x,
-            y,
-            width: size,
-            height: size,
-        },
-    )?;
+    // xcap captures in physical pixels outside macOS
+    let monitor = Platform::xcap_monitor_from_cursor(app)?;
+    let scale = SnappitScale::of_monitor(&monitor)?;
+    let bounds = SnappitRect::<Physical, MonitorRelative>::new(
+        0.0,
+        0.0,
+        f64::from(monitor.width()?),
+        f64::from(monitor.height()?),
+    );
+
+    let cursor = SnappitPoint::<Logical, MonitorRelative>::new(x as f64, y as f64)
+        .convert::<Physical>(scale);
+    let center_x = cursor.x.clamp(0.0, (bounds.width - 1.0).max(0.0));
+    let center_y = cursor.y.clamp(0.0, (bounds.height - 1.0).max(0.0));
+
+    // One cell per logical pixel, centered on the cursor
+    let cells = SnappitRect::<Logical, MonitorRelative>::new(
+        -(radius as f64) - 0.5,
+        -(radius as f64) - 0.5,
+        size as f64,
+        size as f64,
+    )
+    .convert::<Physical>(scale);
+    let cell_span = cells.width / size as f64;
+    let grid_area = SnappitRect::<Physical, MonitorRelative>::new(
+        center_x + cells.x,
+        center_y + cells.y,
+        cells.width,
+        cells.height,
+    );
+
+    let logical_start_x = grid_area.x;
+    let logical_start_y = grid_area.y;
+
+    let capture_area = grid_area.intersection(&bounds).unwrap_or(bounds);
+    let (capture_left, capture_top, capture_width, capture_height) = capture_area.to_region();
+    let image = monitor.capture_region(capture_left, capture_top, capture_width, capture_height)?;
 
     let image_width = image.width() as i32;
     let image_height = image.height() as i32;
@@ -9,9 +9,9 @@
 use crate::snappit_consts::SNAPPIT_CONSTS;
 use crate::snappit_errors::SnappitResult;
 use image::{ImageBuffer, Rgba};
-use serde::{Deserialize, Serialize};
-use std::fmt;
+pub use snappit_core::res::SnappitColorInfo;
 use tauri::AppHandle;
+use xcap::{Monitor, Window};
 
 #[cfg(target_os = "macos")]
 use super::macos_capturer;
@@ -19,31 +19,6 @@ use super::macos_capturer;
 #[cfg(not(target_os = "macos"))]
 use super::multiplatform_capturer;
 
-/// Color information in various formats
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SnappitColorInfo {
-    pub hex: String,
-    pub rgb: (u8, u8, u8),
-    pub rgba: (u8, u8, u8, u8),
-}
-
-impl fmt::Display for SnappitColorInfo {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.hex)
-    }
-}
-
-impl SnappitColorInfo {
-    pub fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
-        let hex = format!("#{:02X}{:02X}{:02X}", r, g, b);
-        Self {
-            hex,
-            rgb: (r, g, b),
-            rgba: (r, g, b, a),
-        }
-    }
-}
-
 /// Main screen capturer providing platform-agnostic API
 ///
 /// This struct internally chooses the appropriate platform-specific implementation:
@@ -104,4 +79,39 @@ impl SnappitCapturer {
             multiplatform_capturer::capture_magnified(app, x, y, params)
         }
     }
+
+    /// Capture the whole of a monitor
+    ///
+    /// On macOS, colors are converted from display color space to sRGB.
+    pub fn capture_monitor(monitor: &Monitor) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        #[cfg(target_os = "macos")]
+        {
+            macos_capturer::capture_area(
+                monitor.x()? as f64,
+                monitor.y()? as f64,
+                monitor.width()? as f64,
+                monitor.height()? as f64,
+            )
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Ok(monitor.capture_image()?)
+        }
+    }
+
+    /// Capture the contents of a window, without the windows overlapping it
+    ///
+    /// On macOS, colors are converted from display color space to sRGB.
+    pub fn capture_window(window: &Window) -> SnappitResult<ImageBuffer<Rgba<u8>, Vec<u8>>> {
+        #[cfg(target_os = "macos")]
+        {
+            macos_capturer::capture_window(window.id()?)
+        }
+
+        #[cfg(not(target_os = "macos"))]
+        {
+            Ok(window.capture_image()?)
+        }
+    }
 }
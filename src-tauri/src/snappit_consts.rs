@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use once_cell::sync::Lazy;
 use serde::Deserialize;
+use snappit_core::ocr::SnappitPreprocessStep;
 
 #[derive(Debug, Deserialize)]
 pub struct SnappitConstants {
@@ -22,14 +23,47 @@ pub struct SnappitStoreKeys {
     pub hotkey_digital_ruler: String,
     pub hotkey_color_dropper: String,
     pub hotkey_qr_scanner: String,
+    pub hotkey_table_scanner: String,
+    pub hotkey_translator: String,
+    pub hotkey_repeat_region: String,
+    pub hotkey_screenshot: String,
     pub hotkey_hide: String,
     pub notifications: String,
     pub autostart: String,
+    pub capture_delay: String,
+    pub capture_delay_repeat_region: String,
+    pub last_region: String,
     pub recognition_lang: String,
     pub sound_enabled: String,
     pub ocr_keep_line_breaks: String,
+    pub ocr_line_breaks: String,
+    pub ocr_code_mode: String,
+    pub ocr_code_fence: String,
+    pub ocr_math_mode: String,
+    pub ocr_min_confidence: String,
+    pub ocr_retry_low_confidence: String,
+    pub ocr_preprocess: String,
+    pub ocr_page_seg_mode: String,
+    pub ocr_engine_mode: String,
+    pub ocr_char_whitelist: String,
+    pub ocr_char_blacklist: String,
+    pub ocr_preserve_interword_spaces: String,
+    pub table_format: String,
+    pub translate_url: String,
+    pub translate_source_lang: String,
+    pub translate_target_lang: String,
+    pub translate_api_key: String,
+    pub screenshot_folder: String,
+    pub screenshot_filename: String,
+    pub screenshot_format: String,
+    pub screenshot_quality: String,
+    pub screenshot_copy_image: String,
     pub qr_auto_open_urls: String,
     pub auto_updates: String,
+    pub capture_history: String,
+    pub history_thumbnails: String,
+    pub tessdata_mirror: String,
+    pub tessdata_variant: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -42,6 +76,20 @@ pub struct SnappitColorDropperDefaults {
 pub struct SnappitDefaults {
     pub shortcuts: HashMap<String, String>,
     pub color_dropper: SnappitColorDropperDefaults,
+    pub ocr_preprocess: Vec<SnappitPreprocessStep>,
+    /// Mean OCR confidence (0..=100) below which the result is flagged, `None` flags
+    /// nothing until a minimum is picked in settings
+    pub ocr_min_confidence: Option<f32>,
+    /// LibreTranslate-compatible server used until another one is configured
+    pub translate_url: String,
+    /// BCP 47 tag captures are translated into
+    pub translate_target_lang: String,
+    /// Name of saved screenshots, see [`crate::snappit_screenshot::render_filename`]
+    pub screenshot_filename: String,
+    /// JPEG and WebP quality (1..=100) of saved screenshots
+    pub screenshot_quality: u8,
+    /// Base URL of the traineddata repositories, `{variant}` is replaced with the repository name
+    pub tessdata_mirror: String,
 }
 
 #[derive(Debug, Deserialize)]
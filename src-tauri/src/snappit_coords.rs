@@ -0,0 +1,334 @@
+//! Screen coordinates
+//!
+//! Positions on screen come in two units. Logical points are what the overlay's web
+//! content and macOS work in, physical pixels are what captured images and xcap on
+//! Windows and Linux work in. A monitor's scale factor, 2.0 on Retina screens and
+//! 1.25 or 1.5 with fractional scaling, is the number of pixels in one point.
+//!
+//! Positions are either relative to the origin of a monitor, like selections in the
+//! overlay, or global in the desktop layout, like monitor and window bounds. Points
+//! and rectangles carry both as type parameters, so mixing them up doesn't compile
+//! and every conversion names the scale it uses.
+
+use std::marker::PhantomData;
+
+use xcap::{Monitor, Window};
+
+use crate::snappit_errors::SnappitResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Logical;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Physical;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorRelative;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Global;
+
+/// Unit of xcap monitor and window bounds and of its capture regions
+#[cfg(target_os = "macos")]
+pub type XCapUnit = Logical;
+
+/// Unit of xcap monitor and window bounds and of its capture regions
+#[cfg(not(target_os = "macos"))]
+pub type XCapUnit = Physical;
+
+/// Distance from a whole unit that still counts as on it, scaling leaves float error
+const SNAP_TOLERANCE: f64 = 1e-6;
+
+/// Physical pixels in one logical point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnappitScale(f64);
+
+impl SnappitScale {
+    pub const ONE: Self = Self(1.0);
+
+    /// Scale of `factor`, factors that aren't positive count as 1
+    pub fn new(factor: f64) -> Self {
+        if factor.is_finite() && factor > 0.0 {
+            Self(factor)
+        } else {
+            Self::ONE
+        }
+    }
+
+    pub fn of_monitor(monitor: &Monitor) -> SnappitResult<Self> {
+        Ok(Self::new(monitor.scale_factor()? as f64))
+    }
+}
+
+pub trait SnappitUnit: Copy {
+    /// Physical pixels in one unit at `scale`
+    fn pixels_per_unit(scale: SnappitScale) -> f64;
+}
+
+impl SnappitUnit for Logical {
+    fn pixels_per_unit(scale: SnappitScale) -> f64 {
+        scale.0
+    }
+}
+
+impl SnappitUnit for Physical {
+    fn pixels_per_unit(_scale: SnappitScale) -> f64 {
+        1.0
+    }
+}
+
+fn conversion_factor<U: SnappitUnit, V: SnappitUnit>(scale: SnappitScale) -> f64 {
+    U::pixels_per_unit(scale) / V::pixels_per_unit(scale)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnappitPoint<U, O> {
+    pub x: f64,
+    pub y: f64,
+    space: PhantomData<(U, O)>,
+}
+
+impl<U: SnappitUnit, O> SnappitPoint<U, O> {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self {
+            x,
+            y,
+            space: PhantomData,
+        }
+    }
+
+    /// The same point in unit `V`, `scale` is the scale of the monitor it is on
+    pub fn convert<V: SnappitUnit>(&self, scale: SnappitScale) -> SnappitPoint<V, O> {
+        let factor = conversion_factor::<U, V>(scale);
+        SnappitPoint::new(self.x * factor, self.y * factor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnappitRect<U, O> {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    space: PhantomData<(U, O)>,
+}
+
+impl<U: SnappitUnit, O> SnappitRect<U, O> {
+    pub fn new(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+            space: PhantomData,
+        }
+    }
+
+    pub fn origin(&self) -> SnappitPoint<U, O> {
+        SnappitPoint::new(self.x, self.y)
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    pub fn contains(&self, point: SnappitPoint<U, O>) -> bool {
+        point.x >= self.x && point.x < self.right() && point.y >= self.y && point.y < self.bottom()
+    }
+
+    /// Common part of both rectangles, `None` when they don't overlap
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        (right > x && bottom > y).then(|| Self::new(x, y, right - x, bottom - y))
+    }
+
+    /// Smallest rectangle containing both
+    pub fn union(&self, other: &Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Self::new(
+            x,
+            y,
+            self.right().max(other.right()) - x,
+            self.bottom().max(other.bottom()) - y,
+        )
+    }
+
+    /// The same rectangle in unit `V`, `scale` is the scale of the monitor it is on
+    pub fn convert<V: SnappitUnit>(&self, scale: SnappitScale) -> SnappitRect<V, O> {
+        let factor = conversion_factor::<U, V>(scale);
+        SnappitRect::new(
+            self.x * factor,
+            self.y * factor,
+            self.width * factor,
+            self.height * factor,
+        )
+    }
+
+    /// Edges moved outwards to whole units, so fractional rectangles lose no content
+    pub fn snap_out(&self) -> Self {
+        let x = (self.x + SNAP_TOLERANCE).floor();
+        let y = (self.y + SNAP_TOLERANCE).floor();
+
+        Self::new(
+            x,
+            y,
+            (self.right() - SNAP_TOLERANCE).ceil() - x,
+            (self.bottom() - SNAP_TOLERANCE).ceil() - y,
+        )
+    }
+}
+
+impl<U: SnappitUnit> SnappitRect<U, MonitorRelative> {
+    /// Position in the desktop layout of a monitor whose origin is at `origin`
+    pub fn to_global(&self, origin: SnappitPoint<U, Global>) -> SnappitRect<U, Global> {
+        SnappitRect::new(
+            self.x + origin.x,
+            self.y + origin.y,
+            self.width,
+            self.height,
+        )
+    }
+
+    /// `(x, y, width, height)` of a rectangle inside its monitor, for capture APIs
+    pub fn to_region(&self) -> (u32, u32, u32, u32) {
+        let snapped = self.snap_out();
+
+        (
+            snapped.x.max(0.0) as u32,
+            snapped.y.max(0.0) as u32,
+            snapped.width.max(0.0) as u32,
+            snapped.height.max(0.0) as u32,
+        )
+    }
+}
+
+impl<U: SnappitUnit> SnappitRect<U, Global> {
+    /// Position relative to a monitor whose origin is at `origin`
+    pub fn relative_to(&self, origin: SnappitPoint<U, Global>) -> SnappitRect<U, MonitorRelative> {
+        SnappitRect::new(
+            self.x - origin.x,
+            self.y - origin.y,
+            self.width,
+            self.height,
+        )
+    }
+}
+
+impl SnappitRect<XCapUnit, Global> {
+    pub fn of_monitor(monitor: &Monitor) -> SnappitResult<Self> {
+        Ok(Self::new(
+            monitor.x()? as f64,
+            monitor.y()? as f64,
+            monitor.width()? as f64,
+            monitor.height()? as f64,
+        ))
+    }
+
+    pub fn of_window(window: &Window) -> SnappitResult<Self> {
+        Ok(Self::new(
+            window.x()? as f64,
+            window.y()? as f64,
+            window.width()? as f64,
+            window.height()? as f64,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type LogicalRect = SnappitRect<Logical, MonitorRelative>;
+    type PhysicalRect = SnappitRect<Physical, MonitorRelative>;
+
+    #[test]
+    fn test_convert_with_fractional_scale() {
+        let selection = LogicalRect::new(10.0, 20.0, 101.0, 33.0);
+
+        let pixels = selection.convert::<Physical>(SnappitScale::new(1.25));
+        assert_eq!(pixels, PhysicalRect::new(12.5, 25.0, 126.25, 41.25));
+        assert_eq!(pixels.to_region(), (12, 25, 127, 42));
+
+        let pixels = selection.convert::<Physical>(SnappitScale::new(1.5));
+        assert_eq!(pixels, PhysicalRect::new(15.0, 30.0, 151.5, 49.5));
+        assert_eq!(pixels.to_region(), (15, 30, 152, 50));
+        assert_eq!(pixels.convert::<Logical>(SnappitScale::new(1.5)), selection);
+
+        // Converting to the same unit keeps the rectangle whatever the scale
+        assert_eq!(
+            selection.convert::<Logical>(SnappitScale::new(1.25)),
+            selection
+        );
+        assert_eq!(SnappitScale::new(0.0), SnappitScale::ONE);
+    }
+
+    #[test]
+    fn test_snap_out_covers_fractional_edges() {
+        assert_eq!(
+            PhysicalRect::new(0.5, 1.0, 2.0, 3.25).snap_out(),
+            PhysicalRect::new(0.0, 1.0, 3.0, 4.0)
+        );
+        // Edges already on whole units stay put despite float error
+        let rect =
+            PhysicalRect::new(3.3, 0.0, 2.2, 11.0).convert::<Logical>(SnappitScale::new(1.1));
+        assert_ne!(rect.x, 3.0);
+        assert_eq!(rect.to_region(), (3, 0, 2, 10));
+    }
+
+    #[test]
+    fn test_monitor_relative_and_global() {
+        let origin = SnappitPoint::<Physical, Global>::new(-1920.0, 0.0);
+        let selection = LogicalRect::new(100.0, 50.0, 40.0, 20.0)
+            .convert::<Physical>(SnappitScale::new(1.5))
+            .to_global(origin);
+
+        assert_eq!(selection, SnappitRect::new(-1770.0, 75.0, 60.0, 30.0));
+        assert_eq!(
+            selection.relative_to(origin),
+            PhysicalRect::new(150.0, 75.0, 60.0, 30.0)
+        );
+
+        let cursor = SnappitPoint::<Logical, Global>::new(-1000.0, 40.0);
+        assert_eq!(
+            cursor.convert::<Physical>(SnappitScale::new(1.25)),
+            SnappitPoint::new(-1250.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let left = SnappitRect::<Physical, Global>::new(-1920.0, 0.0, 1920.0, 1080.0);
+        let main = SnappitRect::<Physical, Global>::new(0.0, 0.0, 2560.0, 1440.0);
+        let selection = SnappitRect::<Physical, Global>::new(-100.0, 500.0, 300.0, 200.0);
+
+        // A selection crossing from the monitor on the left onto the one at the origin
+        assert_eq!(
+            selection.intersection(&left),
+            Some(SnappitRect::new(-100.0, 500.0, 100.0, 200.0))
+        );
+        assert_eq!(
+            selection.intersection(&main),
+            Some(SnappitRect::new(0.0, 500.0, 200.0, 200.0))
+        );
+        assert_eq!(
+            left.intersection(&SnappitRect::new(0.0, 0.0, 10.0, 10.0)),
+            None
+        );
+        assert_eq!(
+            left.union(&main),
+            SnappitRect::new(-1920.0, 0.0, 4480.0, 1440.0)
+        );
+        assert!(left.contains(SnappitPoint::new(-1.0, 1079.0)));
+        assert!(!left.contains(SnappitPoint::new(0.0, 0.0)));
+    }
+}
@@ -0,0 +1,114 @@
+//! Delayed capture
+//!
+//! With a delay configured, the capture hotkey starts a countdown instead of showing
+//! the overlay right away, leaving time to open a menu or hover a tooltip. When the
+//! countdown ends the screen is frozen and the overlay selects on the frozen frame,
+//! or the last region is captured again without any selection.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use tauri::{AppHandle, Wry};
+
+use crate::{
+    region_capture::RegionCapture,
+    snappit_consts::SNAPPIT_CONSTS,
+    snappit_errors::{SnappitResult, SnappitResultExt},
+    snappit_notifications::SnappitNotifications,
+    snappit_overlay::{SnappitOverlay, SnappitOverlayTarget},
+    snappit_permissions::SnappitPermissions,
+    snappit_store::SnappitStore,
+};
+
+/// Time for the countdown notification to leave the screen before it is captured
+const NOTIFICATION_HIDE_DELAY: Duration = Duration::from_millis(150);
+
+static COUNTDOWN_RUNNING: AtomicBool = AtomicBool::new(false);
+
+pub struct SnappitDelayedCapture;
+
+impl SnappitDelayedCapture {
+    /// Seconds to wait before capturing, 0 when captures aren't delayed
+    pub fn delay(app: &AppHandle<Wry>) -> u64 {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.capture_delay)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_u64())
+            .unwrap_or(0)
+    }
+
+    fn repeats_last_region(app: &AppHandle<Wry>) -> bool {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.capture_delay_repeat_region)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Shows the overlay for `target`, after the countdown for captures when a delay is set.
+    /// Repeating the last region captures right away, without the overlay
+    pub fn show(app: &AppHandle<Wry>, target: SnappitOverlayTarget) -> SnappitResult<()> {
+        if target == SnappitOverlayTarget::RepeatRegion {
+            return SnappitOverlay::repeat_region(app);
+        }
+
+        let delay = Self::delay(app);
+
+        if target != SnappitOverlayTarget::Capture || delay == 0 {
+            SnappitOverlay::show(app, target)?;
+            return Ok(());
+        }
+
+        // Pressing the hotkey again during the countdown doesn't start another one
+        if COUNTDOWN_RUNNING.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        Self::start_countdown(app, delay)
+            .inspect_err(|_| COUNTDOWN_RUNNING.store(false, Ordering::SeqCst))
+    }
+
+    fn start_countdown(app: &AppHandle<Wry>, delay: u64) -> SnappitResult<()> {
+        SnappitOverlay::ensure_trial(app)?;
+        SnappitPermissions::ensure_for_overlay(app)?;
+        SnappitNotifications::show_countdown(app, delay)?;
+
+        let app = app.clone();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_secs(delay));
+            Self::capture(&app).log_on_err_with("Delayed capture failed");
+            COUNTDOWN_RUNNING.store(false, Ordering::SeqCst);
+        });
+
+        Ok(())
+    }
+
+    fn capture(app: &AppHandle<Wry>) -> SnappitResult<()> {
+        let app_handle = app.clone();
+        app.run_on_main_thread(move || {
+            SnappitNotifications::hide(&app_handle).log_on_err();
+        })?;
+        thread::sleep(NOTIFICATION_HIDE_DELAY);
+
+        if Self::repeats_last_region(app) && RegionCapture::last_region(app).is_some() {
+            let app_handle = app.clone();
+            app.run_on_main_thread(move || {
+                SnappitOverlay::repeat_region(&app_handle)
+                    .log_on_err_with("Failed to repeat the last region");
+            })?;
+
+            return Ok(());
+        }
+
+        RegionCapture::freeze(app)?;
+
+        let app_handle = app.clone();
+        app.run_on_main_thread(move || {
+            SnappitOverlay::show_frozen(&app_handle, SnappitOverlayTarget::Capture)
+                .log_on_err_with("Failed to show overlay for delayed capture");
+        })?;
+
+        Ok(())
+    }
+}
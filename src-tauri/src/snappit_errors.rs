@@ -1,13 +1,16 @@
-use std::{io, str::Utf8Error};
+use std::io;
 
 use image::ImageError;
-use leptess::{leptonica::PixError, tesseract::TessInitError};
+use snappit_core::errors::SnappitCoreError;
 use tauri::Error as TauriError;
+use tauri_plugin_clipboard_manager::Error as ClipboardError;
 use tauri_plugin_global_shortcut::Error as ShortcutError;
 use tauri_plugin_store::Error as StoreError;
 use thiserror::Error;
 use xcap::XCapError;
 
+use crate::snappit_overlay::SnappitOverlayTarget;
+
 #[derive(Error, Debug)]
 pub enum SnappitError {
     #[error("TauriError error: {0}")]
@@ -19,14 +22,8 @@ pub enum SnappitError {
     #[error("Store error: {0}")]
     Store(#[from] StoreError),
 
-    #[error("PixError error: {0}")]
-    PixError(#[from] PixError),
-
-    #[error("TessInitError error: {0}")]
-    TessInitError(#[from] TessInitError),
-
-    #[error("Utf8Error error: {0}")]
-    Utf8Error(#[from] Utf8Error),
+    #[error("{0}")]
+    Core(#[from] SnappitCoreError),
 
     #[error("ImageError error: {0}")]
     ImageError(#[from] ImageError),
@@ -37,9 +34,21 @@ pub enum SnappitError {
     #[error("Shortcut error: {0}")]
     Shortcut(#[from] ShortcutError),
 
+    #[error("Clipboard error: {0}")]
+    Clipboard(#[from] ClipboardError),
+
     #[error("Monitor not found under cursor")]
     MonitorNotFound,
 
+    #[error("Window not found")]
+    WindowNotFound,
+
+    #[error("No region has been captured yet")]
+    NoLastRegion,
+
+    #[error("Region is outside of all monitors")]
+    RegionOutsideMonitors,
+
     #[error("Const undefined")]
     ConstUndefined,
 
@@ -52,9 +61,6 @@ pub enum SnappitError {
     #[error("Missing models: need det, cls, rec .onnx files")]
     PaddleModelNotFound,
 
-    #[error("macOS Vision OCR unavailable: {0}")]
-    MacOSVisionOcrUnavailable(String),
-
     #[error("Missing permissions: {0}")]
     MissingPermissions(&'static str),
 
@@ -79,11 +85,35 @@ pub enum SnappitError {
     #[error("ColorSync error: {0}")]
     ColorSync(String),
 
-    #[error("QR scan failed: {0}")]
-    QrScanFailed(String),
+    #[error("History database error: {0}")]
+    History(#[from] rusqlite::Error),
+
+    #[error("History database is not initialized")]
+    HistoryUnavailable,
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Unknown Tesseract language: {0}")]
+    UnknownTessLanguage(String),
+
+    #[error("Language {0} is already being downloaded")]
+    TessdataDownloadInProgress(String),
+
+    #[error("Download cancelled")]
+    TessdataDownloadCancelled,
+
+    #[error("Downloaded file has {1} bytes, expected {0}")]
+    TessdataSizeMismatch(u64, u64),
+
+    #[error("Downloaded file does not match its SHA-256 checksum")]
+    TessdataChecksumMismatch,
+
+    #[error("Translation failed: {0}")]
+    Translation(String),
 
-    #[error("Vision QR unavailable: {0}")]
-    VisionQrUnavailable(String),
+    #[error("{0:?} has no overlay to show")]
+    NoOverlayForTarget(SnappitOverlayTarget),
 }
 
 pub type SnappitResult<T> = Result<T, SnappitError>;
@@ -0,0 +1,52 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tauri::{async_runtime::spawn_blocking, AppHandle};
+
+use super::{
+    history_db::{SnappitHistoryExportFormat, SnappitHistoryPage, SnappitHistoryQuery},
+    SnappitHistory, SnappitHistoryEntry,
+};
+
+#[tauri::command]
+pub async fn query_history(query: SnappitHistoryQuery) -> tauri::Result<SnappitHistoryPage> {
+    let page = spawn_blocking(move || SnappitHistory::query(&query)).await??;
+    Ok(page)
+}
+
+/// Thumbnail of the captured region as a `data:` URL
+#[tauri::command]
+pub async fn get_history_thumbnail(id: i64) -> tauri::Result<Option<String>> {
+    let thumbnail = spawn_blocking(move || SnappitHistory::get_thumbnail(id)).await??;
+
+    Ok(thumbnail.map(|bytes| format!("data:image/jpeg;base64,{}", STANDARD.encode(bytes))))
+}
+
+#[tauri::command]
+pub async fn delete_history_items(app: AppHandle, ids: Vec<i64>) -> tauri::Result<()> {
+    spawn_blocking(move || SnappitHistory::delete(&app, &ids)).await??;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn clear_history(app: AppHandle) -> tauri::Result<()> {
+    spawn_blocking(move || SnappitHistory::clear(&app)).await??;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn export_history(
+    app: AppHandle,
+    format: SnappitHistoryExportFormat,
+) -> tauri::Result<String> {
+    let path = spawn_blocking(move || SnappitHistory::export(&app, format)).await??;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Ruler measurements are computed in the overlay, so the frontend reports them here
+#[tauri::command]
+pub async fn add_ruler_history(app: AppHandle, value: String) -> tauri::Result<()> {
+    spawn_blocking(move || {
+        SnappitHistory::record(&app, SnappitHistoryEntry::Ruler { value }, None)
+    })
+    .await??;
+    Ok(())
+}
@@ -0,0 +1,510 @@
+use std::path::Path;
+
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use snappit_core::res::SnappitOcrEngine;
+
+use crate::snappit_errors::SnappitResult;
+
+/// Oldest captures beyond this are dropped on insert
+const MAX_HISTORY_ITEMS: i64 = 5000;
+const MAX_PAGE_SIZE: u32 = 200;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS captures (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind TEXT NOT NULL,
+        content TEXT NOT NULL,
+        payload TEXT NOT NULL,
+        thumbnail BLOB,
+        created_at INTEGER NOT NULL
+    );
+
+    CREATE INDEX IF NOT EXISTS captures_created_at ON captures (created_at DESC);
+
+    CREATE VIRTUAL TABLE IF NOT EXISTS captures_fts USING fts5(
+        content,
+        content = 'captures',
+        content_rowid = 'id',
+        tokenize = 'unicode61 remove_diacritics 2'
+    );
+
+    CREATE TRIGGER IF NOT EXISTS captures_ai AFTER INSERT ON captures BEGIN
+        INSERT INTO captures_fts (rowid, content) VALUES (new.id, new.content);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS captures_ad AFTER DELETE ON captures BEGIN
+        INSERT INTO captures_fts (captures_fts, rowid, content) VALUES ('delete', old.id, old.content);
+    END;
+";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "snake_case")]
+pub enum SnappitHistoryEntry {
+    Ocr {
+        text: String,
+        engine: SnappitOcrEngine,
+    },
+    Qr {
+        content: String,
+    },
+    Dropper {
+        hex: String,
+        rgb: (u8, u8, u8),
+    },
+    Ruler {
+        value: String,
+    },
+}
+
+impl SnappitHistoryEntry {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SnappitHistoryEntry::Ocr { .. } => "ocr",
+            SnappitHistoryEntry::Qr { .. } => "qr",
+            SnappitHistoryEntry::Dropper { .. } => "dropper",
+            SnappitHistoryEntry::Ruler { .. } => "ruler",
+        }
+    }
+
+    /// Text indexed for full-text search and written to CSV exports
+    pub fn content(&self) -> &str {
+        match self {
+            SnappitHistoryEntry::Ocr { text, .. } => text,
+            SnappitHistoryEntry::Qr { content } => content,
+            SnappitHistoryEntry::Dropper { hex, .. } => hex,
+            SnappitHistoryEntry::Ruler { value } => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnappitHistoryItem {
+    pub id: i64,
+    #[serde(flatten)]
+    pub entry: SnappitHistoryEntry,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: i64,
+    pub has_thumbnail: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SnappitHistoryQuery {
+    /// Words matched as prefixes against the captured text
+    pub search: Option<String>,
+    /// Only these kinds (`ocr`, `qr`, `dropper`, `ruler`), all when empty
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    #[serde(default)]
+    pub offset: u32,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnappitHistoryPage {
+    pub items: Vec<SnappitHistoryItem>,
+    /// Number of matching items across all pages
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnappitHistoryExportFormat {
+    Json,
+    Csv,
+}
+
+impl SnappitHistoryExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SnappitHistoryExportFormat::Json => "json",
+            SnappitHistoryExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Item of the JSON array the frontend used to keep in the settings store
+#[derive(Deserialize)]
+struct LegacyHistoryItem {
+    #[serde(flatten)]
+    entry: SnappitHistoryEntry,
+    timestamp: i64,
+}
+
+pub struct SnappitHistoryDb {
+    conn: Connection,
+}
+
+impl SnappitHistoryDb {
+    pub fn open(path: &Path) -> SnappitResult<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> SnappitResult<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> SnappitResult<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    pub fn insert(
+        &self,
+        entry: &SnappitHistoryEntry,
+        thumbnail: Option<&[u8]>,
+        timestamp: i64,
+    ) -> SnappitResult<i64> {
+        let id = Self::insert_row(&self.conn, entry, thumbnail, timestamp)?;
+        Self::drop_oldest(&self.conn)?;
+
+        Ok(id)
+    }
+
+    fn insert_row(
+        conn: &Connection,
+        entry: &SnappitHistoryEntry,
+        thumbnail: Option<&[u8]>,
+        timestamp: i64,
+    ) -> SnappitResult<i64> {
+        conn.execute(
+            "INSERT INTO captures (kind, content, payload, thumbnail, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                entry.kind(),
+                entry.content(),
+                serde_json::to_string(entry)?,
+                thumbnail,
+                timestamp
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Deletes the oldest captures beyond `MAX_HISTORY_ITEMS`
+    fn drop_oldest(conn: &Connection) -> SnappitResult<()> {
+        conn.execute(
+            "DELETE FROM captures WHERE id NOT IN (
+                SELECT id FROM captures ORDER BY created_at DESC, id DESC LIMIT ?1
+            )",
+            params![MAX_HISTORY_ITEMS],
+        )?;
+
+        Ok(())
+    }
+
+    pub fn query(&self, query: &SnappitHistoryQuery) -> SnappitResult<SnappitHistoryPage> {
+        let mut filters: Vec<String> = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+
+        if let Some(search) = query.search.as_deref().and_then(fts_query) {
+            filters.push(
+                "id IN (SELECT rowid FROM captures_fts WHERE captures_fts MATCH ?)".to_string(),
+            );
+            values.push(Value::Text(search));
+        }
+
+        if !query.kinds.is_empty() {
+            let placeholders = vec!["?"; query.kinds.len()].join(", ");
+            filters.push(format!("kind IN ({placeholders})"));
+            values.extend(query.kinds.iter().cloned().map(Value::Text));
+        }
+
+        let where_clause = if filters.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", filters.join(" AND "))
+        };
+
+        let total: u32 = self.conn.query_row(
+            &format!("SELECT COUNT(*) FROM captures {where_clause}"),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+
+        let limit = query.limit.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        values.push(Value::Integer(limit as i64));
+        values.push(Value::Integer(query.offset as i64));
+
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, payload, created_at, thumbnail IS NOT NULL FROM captures {where_clause}
+             ORDER BY created_at DESC, id DESC LIMIT ? OFFSET ?"
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(values.iter()), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, bool>(3)?,
+            ))
+        })?;
+
+        let mut items = Vec::new();
+        for row in rows {
+            let (id, payload, timestamp, has_thumbnail) = row?;
+            items.push(SnappitHistoryItem {
+                id,
+                entry: serde_json::from_str(&payload)?,
+                timestamp,
+                has_thumbnail,
+            });
+        }
+
+        Ok(SnappitHistoryPage { items, total })
+    }
+
+    pub fn thumbnail(&self, id: i64) -> SnappitResult<Option<Vec<u8>>> {
+        let thumbnail = self
+            .conn
+            .query_row(
+                "SELECT thumbnail FROM captures WHERE id = ?1",
+                params![id],
+                |row| row.get::<_, Option<Vec<u8>>>(0),
+            )
+            .optional()?;
+
+        Ok(thumbnail.flatten())
+    }
+
+    pub fn delete(&self, ids: &[i64]) -> SnappitResult<()> {
+        let mut stmt = self.conn.prepare("DELETE FROM captures WHERE id = ?1")?;
+        for id in ids {
+            stmt.execute(params![id])?;
+        }
+
+        Ok(())
+    }
+
+    pub fn clear(&self) -> SnappitResult<()> {
+        self.conn.execute("DELETE FROM captures", [])?;
+        Ok(())
+    }
+
+    /// Serializes the whole history, newest first
+    pub fn export(&self, format: SnappitHistoryExportFormat) -> SnappitResult<String> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, kind, content, payload, created_at,
+                    strftime('%Y-%m-%dT%H:%M:%SZ', created_at / 1000, 'unixepoch')
+             FROM captures ORDER BY created_at DESC, id DESC",
+        )?;
+
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+                row.get::<_, String>(5)?,
+            ))
+        })?;
+
+        match format {
+            SnappitHistoryExportFormat::Json => {
+                let mut items = Vec::new();
+                for row in rows {
+                    let (id, _, _, payload, timestamp, _) = row?;
+                    items.push(SnappitHistoryItem {
+                        id,
+                        entry: serde_json::from_str(&payload)?,
+                        timestamp,
+                        has_thumbnail: false,
+                    });
+                }
+
+                Ok(serde_json::to_string_pretty(&items)?)
+            }
+            SnappitHistoryExportFormat::Csv => {
+                let mut csv = String::from("id,type,content,created_at\n");
+                for row in rows {
+                    let (id, kind, content, _, _, created_at) = row?;
+                    csv.push_str(&format!(
+                        "{id},{kind},{},{created_at}\n",
+                        csv_field(&content)
+                    ));
+                }
+
+                Ok(csv)
+            }
+        }
+    }
+
+    /// Imports the JSON array previously kept by the frontend, skipping items it can't parse.
+    /// Either every item is imported or, on an error, none of them
+    pub fn import_legacy(&mut self, items: &serde_json::Value) -> SnappitResult<usize> {
+        let Some(items) = items.as_array() else {
+            return Ok(0);
+        };
+
+        let tx = self.conn.transaction()?;
+        let mut imported = 0;
+
+        // Stored newest first, inserted oldest first to keep ids in chronological order
+        for item in items.iter().rev() {
+            let Ok(legacy) = serde_json::from_value::<LegacyHistoryItem>(item.clone()) else {
+                continue;
+            };
+
+            Self::insert_row(&tx, &legacy.entry, None, legacy.timestamp)?;
+            imported += 1;
+        }
+
+        Self::drop_oldest(&tx)?;
+        tx.commit()?;
+
+        Ok(imported)
+    }
+}
+
+/// Turns free text into an FTS5 query where every word is a quoted prefix
+fn fts_query(search: &str) -> Option<String> {
+    let terms: Vec<String> = search
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ocr(text: &str) -> SnappitHistoryEntry {
+        SnappitHistoryEntry::Ocr {
+            text: text.to_string(),
+            engine: SnappitOcrEngine::Tesseract,
+        }
+    }
+
+    fn search(db: &SnappitHistoryDb, text: &str) -> Vec<String> {
+        let query = SnappitHistoryQuery {
+            search: Some(text.to_string()),
+            ..Default::default()
+        };
+
+        db.query(&query)
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| item.entry.content().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_full_text_search() {
+        let db = SnappitHistoryDb::open_in_memory().unwrap();
+        db.insert(&ocr("Quarterly revenue report"), None, 1)
+            .unwrap();
+        db.insert(&ocr("Grocery list: milk, eggs"), None, 2)
+            .unwrap();
+        db.insert(
+            &SnappitHistoryEntry::Qr {
+                content: "https://example.com/report".to_string(),
+            },
+            None,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(
+            search(&db, "report"),
+            vec!["https://example.com/report", "Quarterly revenue report"]
+        );
+        assert_eq!(search(&db, "rev"), vec!["Quarterly revenue report"]);
+        assert_eq!(search(&db, "milk eggs"), vec!["Grocery list: milk, eggs"]);
+        assert!(search(&db, "\"unbalanced").is_empty());
+    }
+
+    #[test]
+    fn test_pagination_and_filters() {
+        let db = SnappitHistoryDb::open_in_memory().unwrap();
+        for i in 0..5 {
+            db.insert(&ocr(&format!("text {i}")), None, i).unwrap();
+        }
+        db.insert(
+            &SnappitHistoryEntry::Ruler {
+                value: "120px".to_string(),
+            },
+            Some(&[1, 2, 3]),
+            10,
+        )
+        .unwrap();
+
+        let page = db
+            .query(&SnappitHistoryQuery {
+                kinds: vec!["ocr".to_string()],
+                offset: 1,
+                limit: Some(2),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(page.total, 5);
+        assert_eq!(page.items[0].entry, ocr("text 3"));
+        assert_eq!(page.items[1].entry, ocr("text 2"));
+
+        let latest = &db.query(&SnappitHistoryQuery::default()).unwrap().items[0];
+        assert!(latest.has_thumbnail);
+        assert_eq!(db.thumbnail(latest.id).unwrap(), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_delete_removes_from_search() {
+        let db = SnappitHistoryDb::open_in_memory().unwrap();
+        let id = db.insert(&ocr("secret token"), None, 1).unwrap();
+
+        db.delete(&[id]).unwrap();
+
+        assert!(search(&db, "secret").is_empty());
+        assert_eq!(db.query(&SnappitHistoryQuery::default()).unwrap().total, 0);
+    }
+
+    #[test]
+    fn test_export_csv_escapes_fields() {
+        let db = SnappitHistoryDb::open_in_memory().unwrap();
+        db.insert(&ocr("a, \"b\"\nc"), None, 0).unwrap();
+
+        let csv = db.export(SnappitHistoryExportFormat::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "id,type,content,created_at\n1,ocr,\"a, \"\"b\"\"\nc\",1970-01-01T00:00:00Z\n"
+        );
+    }
+
+    #[test]
+    fn test_import_legacy() {
+        let mut db = SnappitHistoryDb::open_in_memory().unwrap();
+        let legacy = serde_json::json!([
+            {
+                "id": "b",
+                "type": "dropper",
+                "timestamp": 20,
+                "payload": { "hex": "#FF0000", "rgb": [255, 0, 0], "formattedColor": "#FF0000" }
+            },
+            { "id": "a", "type": "ocr", "timestamp": 10, "payload": { "text": "hello", "engine": "vision" } },
+            { "id": "c", "type": "unknown", "timestamp": 5, "payload": {} }
+        ]);
+
+        assert_eq!(db.import_legacy(&legacy).unwrap(), 2);
+
+        let items = db.query(&SnappitHistoryQuery::default()).unwrap().items;
+        assert_eq!(items[0].entry.content(), "#FF0000");
+        assert_eq!(items[1].timestamp, 10);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod commands;
+mod history_db;
+mod snappit_history;
+
+pub use history_db::SnappitHistoryEntry;
+pub use snappit_history::SnappitHistory;
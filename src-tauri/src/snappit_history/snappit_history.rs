@@ -0,0 +1,181 @@
+use std::{
+    path::PathBuf,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use image::{codecs::jpeg::JpegEncoder, DynamicImage, ImageBuffer, Rgba};
+use once_cell::sync::Lazy;
+use snappit_core::res::SnappitBarcode;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    snappit_consts::SNAPPIT_CONSTS,
+    snappit_errors::{SnappitError, SnappitResult},
+    snappit_res::SnappitResponse,
+    snappit_store::SnappitStore,
+};
+
+use super::history_db::{
+    SnappitHistoryDb, SnappitHistoryEntry, SnappitHistoryExportFormat, SnappitHistoryPage,
+    SnappitHistoryQuery,
+};
+
+const DB_FILE: &str = "history.sqlite3";
+const THUMBNAIL_SIZE: u32 = 320;
+const THUMBNAIL_QUALITY: u8 = 75;
+
+static HISTORY_DB: Lazy<Mutex<Option<SnappitHistoryDb>>> = Lazy::new(|| Mutex::new(None));
+
+/// Capture history stored in SQLite next to the settings store
+pub struct SnappitHistory;
+
+impl SnappitHistory {
+    pub const EVENT_NAME: &'static str = "history:updated";
+
+    pub fn init(app: &AppHandle) -> SnappitResult<()> {
+        let data_dir = app.path().app_data_dir()?;
+        std::fs::create_dir_all(&data_dir)?;
+
+        let mut db = SnappitHistoryDb::open(&data_dir.join(DB_FILE))?;
+        Self::import_legacy(app, &mut db)?;
+
+        *HISTORY_DB.lock().unwrap() = Some(db);
+        Ok(())
+    }
+
+    /// Moves items the frontend used to keep in the settings store into the database
+    fn import_legacy(app: &AppHandle, db: &mut SnappitHistoryDb) -> SnappitResult<()> {
+        let key = SNAPPIT_CONSTS.store.keys.capture_history.as_str();
+        let Some(items) = SnappitStore::get_value(app, key)? else {
+            return Ok(());
+        };
+
+        let imported = db.import_legacy(&items)?;
+        SnappitStore::delete_value(app, key)?;
+        log::info!("Imported {imported} capture history items from the settings store");
+
+        Ok(())
+    }
+
+    fn with_db<T>(f: impl FnOnce(&SnappitHistoryDb) -> SnappitResult<T>) -> SnappitResult<T> {
+        let guard = HISTORY_DB.lock().unwrap();
+        let db = guard.as_ref().ok_or(SnappitError::HistoryUnavailable)?;
+        f(db)
+    }
+
+    /// Records a capture result, with a thumbnail of the source when enabled in settings
+    pub fn record(
+        app: &AppHandle,
+        entry: SnappitHistoryEntry,
+        source: Option<&ImageBuffer<Rgba<u8>, Vec<u8>>>,
+    ) -> SnappitResult<()> {
+        if entry.content().trim().is_empty() {
+            return Ok(());
+        }
+
+        let thumbnail = match source {
+            Some(image) if Self::thumbnails_enabled(app) => Some(Self::thumbnail(image)?),
+            _ => None,
+        };
+
+        Self::with_db(|db| db.insert(&entry, thumbnail.as_deref(), Self::now()))?;
+        app.emit(Self::EVENT_NAME, ())?;
+
+        Ok(())
+    }
+
+    pub fn record_response(
+        app: &AppHandle,
+        response: &SnappitResponse,
+        source: Option<&ImageBuffer<Rgba<u8>, Vec<u8>>>,
+    ) -> SnappitResult<()> {
+        let entry = match response {
+            SnappitResponse::Qr(codes) => Self::qr_entry(codes),
+            SnappitResponse::Ocr(result) => SnappitHistoryEntry::Ocr {
+                text: result.value.clone(),
+                engine: result.ocr,
+            },
+            SnappitResponse::Table(table) => SnappitHistoryEntry::Ocr {
+                text: table.value.clone(),
+                engine: table.ocr,
+            },
+            // The translation is what ends up on the clipboard
+            SnappitResponse::Translation(translation) => SnappitHistoryEntry::Ocr {
+                text: translation.translated_text.clone(),
+                engine: translation.ocr,
+            },
+        };
+
+        Self::record(app, entry, source)
+    }
+
+    pub fn qr_entry(codes: &[SnappitBarcode]) -> SnappitHistoryEntry {
+        let content = codes
+            .iter()
+            .map(|code| code.value.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        SnappitHistoryEntry::Qr { content }
+    }
+
+    pub fn query(query: &SnappitHistoryQuery) -> SnappitResult<SnappitHistoryPage> {
+        Self::with_db(|db| db.query(query))
+    }
+
+    pub fn get_thumbnail(id: i64) -> SnappitResult<Option<Vec<u8>>> {
+        Self::with_db(|db| db.thumbnail(id))
+    }
+
+    pub fn delete(app: &AppHandle, ids: &[i64]) -> SnappitResult<()> {
+        Self::with_db(|db| db.delete(ids))?;
+        app.emit(Self::EVENT_NAME, ())?;
+        Ok(())
+    }
+
+    pub fn clear(app: &AppHandle) -> SnappitResult<()> {
+        Self::with_db(|db| db.clear())?;
+        app.emit(Self::EVENT_NAME, ())?;
+        Ok(())
+    }
+
+    /// Writes the whole history into the downloads folder and returns the file path
+    pub fn export(app: &AppHandle, format: SnappitHistoryExportFormat) -> SnappitResult<PathBuf> {
+        let content = Self::with_db(|db| db.export(format))?;
+        let path = app.path().download_dir()?.join(format!(
+            "snappit-history-{}.{}",
+            Self::now() / 1000,
+            format.extension()
+        ));
+
+        std::fs::write(&path, content)?;
+        Ok(path)
+    }
+
+    fn thumbnails_enabled(app: &AppHandle) -> bool {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.history_thumbnails)
+            .ok()
+            .flatten()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    fn thumbnail(image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> SnappitResult<Vec<u8>> {
+        let thumbnail = DynamicImage::ImageRgba8(image.clone())
+            .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+            .into_rgb8();
+
+        let mut buf = Vec::new();
+        JpegEncoder::new_with_quality(&mut buf, THUMBNAIL_QUALITY).encode_image(&thumbnail)?;
+
+        Ok(buf)
+    }
+
+    fn now() -> i64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default()
+    }
+}
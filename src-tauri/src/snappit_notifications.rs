@@ -69,6 +69,19 @@ impl SnappitNotifications {
         Ok(())
     }
 
+    /// Countdown of a delayed capture, shown even with notifications off as nothing
+    /// else tells when the capture happens
+    pub fn show_countdown(app: &AppHandle<Wry>, seconds: u64) -> SnappitResult<()> {
+        let payload = SnappitNotificationPayload {
+            target: SnappitOverlayTarget::Capture,
+            value: seconds.to_string(),
+            data: Some("countdown".to_string()),
+        };
+
+        Self::show(app, payload)?;
+        Ok(())
+    }
+
     fn show(
         app: &AppHandle<Wry>,
         payload: SnappitNotificationPayload,
@@ -1,8 +1,14 @@
+use std::path::PathBuf;
+
 use super::{
-    recognition_language::get_system_recognition_languages, tesseract_ocr::SnappitTesseractOcr,
+    SnappitTessdata, SnappitTessdataDownload, SnappitTessdataImport, SnappitTessdataImportReport,
 };
 use serde::Serialize;
-use tauri::AppHandle;
+use snappit_core::ocr::{
+    recognition_language::get_system_recognition_languages, SnappitLanguageRegistry,
+    SnappitTesseractOcr,
+};
+use tauri::{async_runtime::spawn_blocking, AppHandle};
 
 #[derive(Serialize)]
 pub struct SystemLanguageInfo {
@@ -10,25 +16,9 @@ pub struct SystemLanguageInfo {
     pub name: String,
 }
 
-fn get_language_display_name(code: &str) -> String {
-    match code {
-        "eng" => "English".to_string(),
-        "rus" => "Russian".to_string(),
-        "chi_sim" => "Chinese (Simplified)".to_string(),
-        "chi_tra" => "Chinese (Traditional)".to_string(),
-        "jpn" => "Japanese".to_string(),
-        "kor" => "Korean".to_string(),
-        "spa" => "Spanish".to_string(),
-        "fra" => "French".to_string(),
-        "deu" => "German".to_string(),
-        "tha" => "Thai".to_string(),
-        _ => code.to_string(),
-    }
-}
-
 #[tauri::command]
 pub fn get_tess_languages(app: AppHandle) -> Result<Vec<String>, String> {
-    let data_path = SnappitTesseractOcr::get_data_path(&app).map_err(|e| e.to_string())?;
+    let data_path = SnappitTessdata::get_data_path(&app).map_err(|e| e.to_string())?;
     let mut languages = Vec::new();
 
     if data_path.exists() {
@@ -52,23 +42,36 @@ pub fn get_tess_languages(app: AppHandle) -> Result<Vec<String>, String> {
 
 #[tauri::command]
 pub async fn download_tess_language(app: AppHandle, lang: String) -> Result<(), String> {
-    let url = format!(
-        "https://raw.githubusercontent.com/tesseract-ocr/tessdata/main/{}.traineddata",
-        lang
-    );
-    let data_path = SnappitTesseractOcr::get_data_path(&app).map_err(|e| e.to_string())?;
-    let file_path = data_path.join(format!("{}.traineddata", lang));
+    SnappitTessdataDownload::download(&app, &lang)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn cancel_tess_download(lang: String) -> bool {
+    SnappitTessdataDownload::cancel(&lang)
+}
+
+/// `paths` may mix `.traineddata` files and folders containing them
+#[tauri::command]
+pub async fn import_tess_languages(
+    app: AppHandle,
+    paths: Vec<String>,
+) -> Result<SnappitTessdataImportReport, String> {
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
 
-    let response = reqwest::get(&url)
+    spawn_blocking(move || SnappitTessdataImport::import(&app, &paths))
         .await
         .map_err(|e| e.to_string())?
-        .error_for_status()
-        .map_err(|e| e.to_string())?;
-
-    let content = response.bytes().await.map_err(|e| e.to_string())?;
-    std::fs::write(file_path, content).map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+}
 
-    Ok(())
+#[tauri::command]
+pub fn get_system_tessdata_dirs() -> Vec<String> {
+    SnappitTessdataImport::system_dirs()
+        .into_iter()
+        .map(|dir| dir.display().to_string())
+        .collect()
 }
 
 #[tauri::command]
@@ -81,7 +84,7 @@ pub fn get_system_languages_info() -> Vec<SystemLanguageInfo> {
     get_system_recognition_languages()
         .into_iter()
         .map(|code| SystemLanguageInfo {
-            name: get_language_display_name(&code),
+            name: SnappitLanguageRegistry::display_name(&code),
             code,
         })
         .collect()
@@ -104,8 +107,11 @@ pub fn delete_tess_language(app: AppHandle, lang: String) -> Result<(), String>
         }
     }
 
-    let data_path = SnappitTesseractOcr::get_data_path(&app).map_err(|e| e.to_string())?;
+    SnappitTessdataDownload::ensure_known_language(&lang).map_err(|e| e.to_string())?;
+
+    let data_path = SnappitTessdata::get_data_path(&app).map_err(|e| e.to_string())?;
     let file_path = data_path.join(format!("{}.traineddata", lang));
+    SnappitTessdataDownload::remove_partial(&data_path, &lang).map_err(|e| e.to_string())?;
 
     // On non-macOS platforms, prevent deleting the last language
     #[cfg(not(target_os = "macos"))]
@@ -131,6 +137,7 @@ pub fn delete_tess_language(app: AppHandle, lang: String) -> Result<(), String>
 
     if file_path.exists() {
         std::fs::remove_file(file_path).map_err(|e| e.to_string())?;
+        SnappitTesseractOcr::invalidate_cache();
     }
     Ok(())
 }
@@ -1,9 +1,10 @@
 pub mod commands;
-pub mod recognition_language;
 mod snappit_ocr;
-mod tesseract_ocr;
-mod vision_ocr;
+mod tessdata;
+mod tessdata_download;
+mod tessdata_import;
 
 pub use snappit_ocr::SnappitOcr;
-pub use tesseract_ocr::SnappitTesseractOcr;
-pub use vision_ocr::SnappitMacOSVisionOcr;
+pub use tessdata::SnappitTessdata;
+pub use tessdata_download::SnappitTessdataDownload;
+pub use tessdata_import::{SnappitTessdataImport, SnappitTessdataImportReport};
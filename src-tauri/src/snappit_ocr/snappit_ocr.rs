@@ -1,88 +1,172 @@
 use image::{ImageBuffer, Rgba};
-use log::warn;
-
-use crate::{
-    snappit_consts::SNAPPIT_CONSTS,
-    snappit_errors::SnappitResult,
-    snappit_ocr::{
+use snappit_core::{
+    ocr::{
         recognition_language::{
-            get_system_recognition_languages, languages_match_system, resolve_recognition_language,
-            split_recognition_languages,
+            is_auto_recognition_language, resolve_recognition_language, split_recognition_languages,
         },
-        SnappitMacOSVisionOcr, SnappitTesseractOcr,
+        OcrConfig, SnappitLineBreakMode, SnappitMathNotation, SnappitOcr as SnappitCoreOcr,
+        SnappitPreprocessStep, SnappitTesseractOptions,
     },
-    snappit_res::{SnappitOcrEngine, SnappitOcrResult},
-    snappit_store::SnappitStore,
-    text_processing::{collapse_line_breaks, remove_non_spaced_script_spaces},
+    res::{SnappitOcrResult, SnappitTableFormat, SnappitTableResult},
+    settings::parse_setting,
+};
+
+use crate::{
+    region_capture::RegionCaptureParams, snappit_consts::SNAPPIT_CONSTS,
+    snappit_errors::SnappitResult, snappit_ocr::SnappitTessdata, snappit_store::SnappitStore,
     traits::IntoDynamic,
 };
 
+/// Tauri adapter around the core OCR: reads settings from the store and
+/// hands them to `snappit_core` as an explicit [`OcrConfig`]
 pub struct SnappitOcr;
 
 impl SnappitOcr {
+    /// `region` is the selection the image was captured from, short and wide
+    /// selections are recognized as a single line unless a mode is configured
     pub fn recognize(
         app: &tauri::AppHandle,
         image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+        region: &RegionCaptureParams,
     ) -> SnappitResult<SnappitOcrResult> {
         let dyn_img = (image.width(), image.height(), image.into_raw()).into_dynamic()?;
-        let recognition_language = resolve_recognition_language(app)?;
-        let language_codes = split_recognition_languages(&recognition_language);
-        let keep_line_breaks = Self::get_keep_line_breaks(app);
-
-        if Self::should_use_macos_vision(&language_codes) {
-            match SnappitMacOSVisionOcr::recognize(app, &dyn_img, &language_codes) {
-                Ok(text) => {
-                    return Ok(SnappitOcrResult {
-                        value: Self::process_text(&text, keep_line_breaks, false),
-                        ocr: SnappitOcrEngine::Vision,
-                    })
-                }
-                Err(err) => {
-                    warn!("macOS Vision OCR unavailable, falling back to Tesseract: {err}");
-                }
-            }
-        }
+        let mut config = Self::config(app)?;
+        config.tesseract.page_seg_mode = region.page_seg_mode(config.tesseract.page_seg_mode);
 
-        let text = SnappitTesseractOcr::recognize(app, &dyn_img, &recognition_language)?;
-        Ok(SnappitOcrResult {
-            value: Self::process_text(&text, keep_line_breaks, true),
-            ocr: SnappitOcrEngine::Tesseract,
-        })
+        Ok(SnappitCoreOcr::recognize(&config, &dyn_img)?)
     }
 
-    fn get_keep_line_breaks(app: &tauri::AppHandle) -> bool {
-        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.ocr_keep_line_breaks)
-            .ok()
-            .flatten()
-            .and_then(|v| v.as_bool())
-            .unwrap_or(true)
+    /// Recognizes a table and formats its cells as the configured table format
+    pub fn recognize_table(
+        app: &tauri::AppHandle,
+        image: ImageBuffer<Rgba<u8>, Vec<u8>>,
+    ) -> SnappitResult<SnappitTableResult> {
+        let dyn_img = (image.width(), image.height(), image.into_raw()).into_dynamic()?;
+        let config = Self::config(app)?;
+
+        Ok(SnappitCoreOcr::recognize_table(
+            &config,
+            &dyn_img,
+            Self::get_table_format(app),
+        )?)
+    }
+
+    pub fn config(app: &tauri::AppHandle) -> SnappitResult<OcrConfig> {
+        let stored_language =
+            SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.recognition_lang)?
+                .and_then(|stored| stored.as_str().map(String::from));
+        let recognition_language = resolve_recognition_language(stored_language.as_deref());
+
+        Ok(OcrConfig {
+            languages: split_recognition_languages(&recognition_language),
+            tessdata_dir: Some(SnappitTessdata::get_data_path(app)?),
+            detect_language: is_auto_recognition_language(stored_language.as_deref()),
+            line_breaks: Self::get_line_break_mode(app),
+            code: Self::get_flag(app, &SNAPPIT_CONSTS.store.keys.ocr_code_mode),
+            code_fence: Self::get_flag(app, &SNAPPIT_CONSTS.store.keys.ocr_code_fence),
+            math: Self::get_math_notation(app),
+            min_confidence: Self::get_min_confidence(app),
+            retry_low_confidence: Self::get_flag(
+                app,
+                &SNAPPIT_CONSTS.store.keys.ocr_retry_low_confidence,
+            ),
+            preprocess: Self::get_preprocess_steps(app),
+            tesseract: Self::get_tesseract_options(app),
+        })
     }
 
-    fn process_text(text: &str, keep_line_breaks: bool, is_tesseract: bool) -> String {
-        let mut text = text.to_string();
+    /// Falls back to the older `ocr_keep_line_breaks` switch until a mode is picked
+    fn get_line_break_mode(app: &tauri::AppHandle) -> SnappitLineBreakMode {
+        let keys = &SNAPPIT_CONSTS.store.keys;
+        let get = |key: &str| SnappitStore::get_value(app, key).ok().flatten();
 
-        if is_tesseract {
-            text = remove_non_spaced_script_spaces(&text);
+        if let Some(mode) =
+            get(&keys.ocr_line_breaks).and_then(|v| v.as_str().and_then(parse_setting))
+        {
+            return mode;
         }
 
-        if keep_line_breaks {
-            return text;
+        match get(&keys.ocr_keep_line_breaks).and_then(|v| v.as_bool()) {
+            Some(false) => SnappitLineBreakMode::Collapse,
+            _ => SnappitLineBreakMode::Keep,
         }
+    }
 
-        collapse_line_breaks(&text)
+    fn get_table_format(app: &tauri::AppHandle) -> SnappitTableFormat {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.table_format)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_str().and_then(parse_setting))
+            .unwrap_or_default()
     }
 
-    fn should_use_macos_vision(languages: &[String]) -> bool {
-        #[cfg(target_os = "macos")]
-        {
-            let system_languages = get_system_recognition_languages();
-            languages_match_system(languages, &system_languages)
+    /// `off`, like any value that isn't a notation, recognizes plain text
+    fn get_math_notation(app: &tauri::AppHandle) -> Option<SnappitMathNotation> {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.ocr_math_mode)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_str().and_then(parse_setting))
+    }
+
+    /// The low confidence warning is off unless a minimum above 0 is set
+    fn get_min_confidence(app: &tauri::AppHandle) -> Option<f32> {
+        let min_confidence =
+            SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.ocr_min_confidence)
+                .ok()
+                .flatten()
+                .and_then(|value| value.as_f64())
+                .map(|value| value as f32)
+                .or(SNAPPIT_CONSTS.defaults.ocr_min_confidence);
+
+        min_confidence.filter(|min_confidence| *min_confidence > 0.0)
+    }
+
+    /// Unknown step names are skipped so a stale setting can't break recognition
+    fn get_preprocess_steps(app: &tauri::AppHandle) -> Vec<SnappitPreprocessStep> {
+        let stored = SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.ocr_preprocess)
+            .ok()
+            .flatten();
+
+        match stored {
+            Some(serde_json::Value::Array(steps)) => steps
+                .into_iter()
+                .filter_map(|step| step.as_str().and_then(parse_setting))
+                .collect(),
+            _ => SNAPPIT_CONSTS.defaults.ocr_preprocess.clone(),
         }
+    }
 
-        #[cfg(not(target_os = "macos"))]
-        {
-            let _ = languages;
-            false
+    fn get_tesseract_options(app: &tauri::AppHandle) -> SnappitTesseractOptions {
+        let keys = &SNAPPIT_CONSTS.store.keys;
+        let get = |key: &str| SnappitStore::get_value(app, key).ok().flatten();
+        let get_string = |key: &str| {
+            get(key)
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default()
+        };
+
+        // Like the preprocessing steps, unknown modes fall back to the defaults
+        SnappitTesseractOptions {
+            page_seg_mode: get(&keys.ocr_page_seg_mode)
+                .and_then(|v| v.as_str().and_then(parse_setting))
+                .unwrap_or_default(),
+            engine_mode: get(&keys.ocr_engine_mode)
+                .and_then(|v| v.as_str().and_then(parse_setting))
+                .unwrap_or_default(),
+            char_whitelist: get_string(&keys.ocr_char_whitelist),
+            char_blacklist: get_string(&keys.ocr_char_blacklist),
+            preserve_interword_spaces: get(&keys.ocr_preserve_interword_spaces)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            disable_dictionary: false,
         }
     }
+
+    fn get_flag(app: &tauri::AppHandle, key: &str) -> bool {
+        SnappitStore::get_value(app, key)
+            .ok()
+            .flatten()
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
 }
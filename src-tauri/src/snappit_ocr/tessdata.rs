@@ -1,40 +1,15 @@
-use std::{io::Cursor, path::PathBuf};
+use std::path::PathBuf;
 
-use image::{DynamicImage, ImageFormat};
-use leptess::LepTess;
+#[cfg(not(target_os = "macos"))]
+use snappit_core::ocr::recognition_language::get_system_recognition_languages;
 use tauri::Manager;
 
 use crate::snappit_errors::SnappitResult;
-use crate::snappit_ocr::recognition_language::default_recognition_language;
-#[cfg(not(target_os = "macos"))]
-use crate::snappit_ocr::recognition_language::get_system_recognition_languages;
-
-pub struct SnappitTesseractOcr;
-
-impl SnappitTesseractOcr {
-    pub fn recognize(
-        app: &tauri::AppHandle,
-        img: &DynamicImage,
-        recognition_language: &str,
-    ) -> SnappitResult<String> {
-        let mut buf: Vec<u8> = Vec::new();
-        let _ = img.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png);
-        let data_path = Self::get_data_path(app)?;
-        let recognition_language = if recognition_language.trim().is_empty() {
-            default_recognition_language()
-        } else {
-            recognition_language.to_string()
-        };
-
-        let mut lt = LepTess::new(data_path.to_str(), recognition_language.as_str())?;
-        lt.set_image_from_mem(&buf)?;
-        lt.set_source_resolution(300);
 
-        let text = lt.get_utf8_text()?;
-
-        Ok(text)
-    }
+/// App-managed tessdata directory
+pub struct SnappitTessdata;
 
+impl SnappitTessdata {
     pub fn get_data_path(app: &tauri::AppHandle) -> SnappitResult<PathBuf> {
         let app_data_dir = app.path().app_data_dir()?;
         let tess_data_path = app_data_dir.join("tessdata");
@@ -0,0 +1,553 @@
+//! Downloads of `.traineddata` files
+//!
+//! Files are streamed into `<lang>.traineddata.part` next to their final path and
+//! only renamed once complete and verified, so an interrupted download never leaves
+//! a truncated file for Tesseract to choke on. A leftover part file is resumed with
+//! a range request on the next attempt.
+//!
+//! Files are checked against the size and SHA-256 pinned in `tessdata_manifest.json`,
+//! which `tessdata-manifest.sh` generates from checkouts of the tessdata repositories.
+//! Languages without a pinned entry are checked against the mirror's `SHA256SUMS`
+//! instead, when it publishes one.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+use reqwest::{header::RANGE, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use snappit_core::ocr::{SnappitLanguageRegistry, SnappitTesseractOcr};
+use tauri::{AppHandle, Emitter};
+
+use crate::{
+    snappit_consts::SNAPPIT_CONSTS,
+    snappit_errors::{SnappitError, SnappitResult},
+    snappit_ocr::SnappitTessdata,
+    snappit_store::SnappitStore,
+};
+
+/// Placeholder in the mirror URL replaced with the variant's repository name
+const VARIANT_PLACEHOLDER: &str = "{variant}";
+/// `sha256sum` output a mirror may publish next to the files, checked on top of the
+/// pinned checksums and in their place for languages that aren't pinned
+const CHECKSUMS_FILE: &str = "SHA256SUMS";
+/// Progress is reported at most once per this many bytes
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitTessdataVariant {
+    /// `tessdata`, includes legacy models
+    #[default]
+    Standard,
+    /// `tessdata_fast`, smallest and quickest
+    Fast,
+    /// `tessdata_best`, most accurate and slowest
+    Best,
+}
+
+impl SnappitTessdataVariant {
+    pub fn repository(self) -> &'static str {
+        match self {
+            SnappitTessdataVariant::Standard => "tessdata",
+            SnappitTessdataVariant::Fast => "tessdata_fast",
+            SnappitTessdataVariant::Best => "tessdata_best",
+        }
+    }
+}
+
+/// Size and SHA-256 a traineddata file must have
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnappitTessdataChecksum {
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Pinned checksums by repository name and language
+static MANIFEST: Lazy<HashMap<String, HashMap<String, SnappitTessdataChecksum>>> =
+    Lazy::new(|| {
+        serde_json::from_str(include_str!("tessdata_manifest.json"))
+            .expect("Invalid tessdata_manifest.json")
+    });
+
+/// Cancellation flags of the downloads in flight, by language
+static ACTIVE_DOWNLOADS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnappitTessdataProgressState {
+    Downloading,
+    Verifying,
+    Done,
+    Cancelled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SnappitTessdataProgress {
+    pub lang: String,
+    pub variant: SnappitTessdataVariant,
+    pub state: SnappitTessdataProgressState,
+    pub downloaded: u64,
+    /// `None` when the server doesn't report a length
+    pub total: Option<u64>,
+    pub error: Option<String>,
+}
+
+pub struct SnappitTessdataDownload;
+
+impl SnappitTessdataDownload {
+    pub const PROGRESS_EVENT: &'static str = "tessdata:progress";
+
+    /// Whether `lang` is a traineddata name from the language registry, which
+    /// also guarantees it is safe to use as a file name
+    pub fn is_known_language(lang: &str) -> bool {
+        SnappitLanguageRegistry::all()
+            .iter()
+            .any(|known| known.tesseract == lang)
+    }
+
+    pub fn ensure_known_language(lang: &str) -> SnappitResult<()> {
+        if Self::is_known_language(lang) {
+            Ok(())
+        } else {
+            Err(SnappitError::UnknownTessLanguage(lang.to_string()))
+        }
+    }
+
+    pub async fn download(app: &AppHandle, lang: &str) -> SnappitResult<()> {
+        Self::ensure_known_language(lang)?;
+
+        let variant = Self::get_variant(app);
+        let pinned = Self::pinned_checksum(variant, lang);
+        let cancel = Self::register(lang)?;
+        let mut progress = SnappitTessdataProgress {
+            lang: lang.to_string(),
+            variant,
+            state: SnappitTessdataProgressState::Downloading,
+            downloaded: 0,
+            total: pinned.map(|pinned| pinned.size),
+            error: None,
+        };
+
+        let result = Self::run(app, &mut progress, pinned, &cancel).await;
+        ACTIVE_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(lang);
+
+        progress.state = match &result {
+            Ok(()) => SnappitTessdataProgressState::Done,
+            Err(SnappitError::TessdataDownloadCancelled) => SnappitTessdataProgressState::Cancelled,
+            Err(err) => {
+                progress.error = Some(err.to_string());
+                SnappitTessdataProgressState::Failed
+            }
+        };
+        let _ = app.emit(Self::PROGRESS_EVENT, &progress);
+
+        result
+    }
+
+    /// Stops a running download after the chunk in flight. The part file is
+    /// kept, so downloading the language again resumes where it stopped
+    pub fn cancel(lang: &str) -> bool {
+        let downloads = ACTIVE_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        match downloads.get(lang) {
+            Some(cancel) => {
+                cancel.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes an unfinished download of `lang`
+    pub fn remove_partial(data_path: &Path, lang: &str) -> SnappitResult<()> {
+        let part_path = data_path.join(Self::part_file_name(lang));
+        if part_path.exists() {
+            fs::remove_file(part_path)?;
+        }
+
+        Ok(())
+    }
+
+    /// Base URL with an optional `{variant}` placeholder. Without one, the
+    /// repository name is appended as a path segment
+    pub fn file_url(base_url: &str, variant: SnappitTessdataVariant, file_name: &str) -> String {
+        let base_url = base_url.trim().trim_end_matches('/');
+        let base_url = if base_url.contains(VARIANT_PLACEHOLDER) {
+            base_url.replace(VARIANT_PLACEHOLDER, variant.repository())
+        } else {
+            format!("{base_url}/{}", variant.repository())
+        };
+
+        format!("{base_url}/{file_name}")
+    }
+
+    /// Size and hash `lang` of `variant` is pinned to, `None` when it isn't pinned
+    pub fn pinned_checksum(
+        variant: SnappitTessdataVariant,
+        lang: &str,
+    ) -> Option<&'static SnappitTessdataChecksum> {
+        MANIFEST
+            .get(variant.repository())
+            .and_then(|checksums| checksums.get(lang))
+    }
+
+    /// Finds the hash of `file_name` in `sha256sum` style output
+    pub fn parse_checksum(checksums: &str, file_name: &str) -> Option<String> {
+        checksums.lines().find_map(|line| {
+            let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start().trim_start_matches('*');
+
+            let is_hash = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+
+            (name == file_name && is_hash).then(|| hash.to_ascii_lowercase())
+        })
+    }
+
+    fn register(lang: &str) -> SnappitResult<Arc<AtomicBool>> {
+        let mut downloads = ACTIVE_DOWNLOADS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if downloads.contains_key(lang) {
+            return Err(SnappitError::TessdataDownloadInProgress(lang.to_string()));
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        downloads.insert(lang.to_string(), cancel.clone());
+
+        Ok(cancel)
+    }
+
+    async fn run(
+        app: &AppHandle,
+        progress: &mut SnappitTessdataProgress,
+        pinned: Option<&SnappitTessdataChecksum>,
+        cancel: &AtomicBool,
+    ) -> SnappitResult<()> {
+        let lang = progress.lang.clone();
+        let file_name = format!("{lang}.traineddata");
+        let data_path = SnappitTessdata::get_data_path(app)?;
+        let final_path = data_path.join(&file_name);
+        let part_path = data_path.join(Self::part_file_name(&lang));
+
+        let base_url = Self::get_base_url(app);
+        let url = Self::file_url(&base_url, progress.variant, &file_name);
+        let client = reqwest::Client::new();
+        let mirror_hash =
+            Self::fetch_checksum(&client, &base_url, progress.variant, &file_name).await;
+
+        // Hash what is already on disk, the digest must cover the whole file
+        let mut hasher = Sha256::new();
+        let mut downloaded = 0u64;
+        if part_path.exists() {
+            downloaded = hash_file(&part_path, &mut hasher)?;
+        }
+
+        let mut response = Self::request(&client, &url, downloaded).await?;
+        if downloaded > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // The part is stale or already longer than the file, start over
+            downloaded = 0;
+            response = Self::request(&client, &url, 0).await?;
+        }
+        let mut response = response.error_for_status()?;
+
+        let resumed = downloaded > 0 && response.status() == StatusCode::PARTIAL_CONTENT;
+        if !resumed {
+            hasher = Sha256::new();
+            downloaded = 0;
+        }
+
+        progress.downloaded = downloaded;
+        if pinned.is_none() {
+            progress.total = response.content_length().map(|len| len + downloaded);
+        }
+        let _ = app.emit(Self::PROGRESS_EVENT, &*progress);
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(resumed)
+            .truncate(!resumed)
+            .open(&part_path)?;
+        let mut last_reported = downloaded;
+
+        while let Some(chunk) = response.chunk().await? {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(SnappitError::TessdataDownloadCancelled);
+            }
+
+            file.write_all(&chunk)?;
+            hasher.update(&chunk);
+            downloaded += chunk.len() as u64;
+
+            if downloaded - last_reported >= PROGRESS_STEP {
+                last_reported = downloaded;
+                progress.downloaded = downloaded;
+                let _ = app.emit(Self::PROGRESS_EVENT, &*progress);
+            }
+        }
+
+        file.sync_all()?;
+        drop(file);
+
+        progress.downloaded = downloaded;
+        progress.state = SnappitTessdataProgressState::Verifying;
+        let _ = app.emit(Self::PROGRESS_EVENT, &*progress);
+
+        let actual_hash = hex::encode(hasher.finalize());
+        if let Err(err) = Self::verify(downloaded, pinned, mirror_hash.as_deref(), &actual_hash) {
+            fs::remove_file(&part_path)?;
+            return Err(err);
+        }
+
+        fs::rename(&part_path, &final_path)?;
+        SnappitTesseractOcr::invalidate_cache();
+
+        Ok(())
+    }
+
+    async fn request(
+        client: &reqwest::Client,
+        url: &str,
+        offset: u64,
+    ) -> SnappitResult<reqwest::Response> {
+        let mut request = client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+
+        Ok(request.send().await?)
+    }
+
+    /// Checks the download against its pinned entry and the mirror's checksum,
+    /// whichever of them there are. With neither, it only must not be empty
+    fn verify(
+        size: u64,
+        pinned: Option<&SnappitTessdataChecksum>,
+        mirror_hash: Option<&str>,
+        actual_hash: &str,
+    ) -> SnappitResult<()> {
+        let expected_size = pinned.map(|pinned| pinned.size);
+        if size == 0 || expected_size.is_some_and(|expected| expected != size) {
+            return Err(SnappitError::TessdataSizeMismatch(
+                expected_size.unwrap_or(0),
+                size,
+            ));
+        }
+
+        let matches = |hash: &str| hash.eq_ignore_ascii_case(actual_hash);
+        let pinned_hash = pinned.map(|pinned| pinned.sha256.as_str());
+        if !pinned_hash.is_none_or(matches) || !mirror_hash.is_none_or(matches) {
+            return Err(SnappitError::TessdataChecksumMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Mirror checksums are optional: upstream GitHub doesn't publish any, mirrors can
+    async fn fetch_checksum(
+        client: &reqwest::Client,
+        base_url: &str,
+        variant: SnappitTessdataVariant,
+        file_name: &str,
+    ) -> Option<String> {
+        let url = Self::file_url(base_url, variant, CHECKSUMS_FILE);
+        let response = client.get(url).send().await.ok()?.error_for_status().ok()?;
+        let checksums = response.text().await.ok()?;
+
+        Self::parse_checksum(&checksums, file_name)
+    }
+
+    fn part_file_name(lang: &str) -> String {
+        format!("{lang}.traineddata.part")
+    }
+
+    fn get_base_url(app: &AppHandle) -> String {
+        SnappitStore::get_string(app, &SNAPPIT_CONSTS.store.keys.tessdata_mirror)
+            .unwrap_or_else(|| SNAPPIT_CONSTS.defaults.tessdata_mirror.clone())
+    }
+
+    fn get_variant(app: &AppHandle) -> SnappitTessdataVariant {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.tessdata_variant)
+            .ok()
+            .flatten()
+            .and_then(|v| serde_json::from_value(v).ok())
+            .unwrap_or_default()
+    }
+}
+
+fn hash_file(path: &Path, hasher: &mut Sha256) -> SnappitResult<u64> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut total = 0u64;
+
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            return Ok(total);
+        }
+
+        hasher.update(&buf[..read]);
+        total += read as u64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            SnappitTessdataDownload::file_url(
+                "https://raw.githubusercontent.com/tesseract-ocr/{variant}/main",
+                SnappitTessdataVariant::Fast,
+                "eng.traineddata"
+            ),
+            "https://raw.githubusercontent.com/tesseract-ocr/tessdata_fast/main/eng.traineddata"
+        );
+        assert_eq!(
+            SnappitTessdataDownload::file_url(
+                "https://mirror.example.com/ocr/",
+                SnappitTessdataVariant::Best,
+                "deu.traineddata"
+            ),
+            "https://mirror.example.com/ocr/tessdata_best/deu.traineddata"
+        );
+    }
+
+    #[test]
+    fn test_parse_checksum() {
+        let hash = "a".repeat(64);
+        let checksums = format!(
+            "{hash}  eng.traineddata\n{}  deu.traineddata\n",
+            "b".repeat(64)
+        );
+
+        assert_eq!(
+            SnappitTessdataDownload::parse_checksum(&checksums, "eng.traineddata"),
+            Some(hash)
+        );
+        assert_eq!(
+            SnappitTessdataDownload::parse_checksum("nothex *fra.traineddata", "fra.traineddata"),
+            None
+        );
+        assert_eq!(
+            SnappitTessdataDownload::parse_checksum(&checksums, "fra.traineddata"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_known_languages() {
+        assert!(SnappitTessdataDownload::is_known_language("chi_sim"));
+        assert!(!SnappitTessdataDownload::is_known_language("../eng"));
+        assert!(!SnappitTessdataDownload::is_known_language(""));
+    }
+
+    #[test]
+    fn test_verify() {
+        let hash = "ab".repeat(32);
+        let other = "cd".repeat(32);
+        let pinned = SnappitTessdataChecksum {
+            size: 10,
+            sha256: hash.clone(),
+        };
+        let pinned = Some(&pinned);
+
+        assert!(SnappitTessdataDownload::verify(10, pinned, None, &hash).is_ok());
+        assert!(SnappitTessdataDownload::verify(10, pinned, Some(&hash), &hash).is_ok());
+        assert!(SnappitTessdataDownload::verify(9, pinned, None, &hash).is_err());
+        assert!(SnappitTessdataDownload::verify(10, pinned, None, &other).is_err());
+        // The mirror can only add a check, never vouch for a file the pin rejects
+        assert!(SnappitTessdataDownload::verify(10, pinned, Some(&other), &hash).is_err());
+        assert!(SnappitTessdataDownload::verify(
+            10,
+            Some(&SnappitTessdataChecksum {
+                size: 10,
+                sha256: other.clone(),
+            }),
+            Some(&other),
+            &hash
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_unpinned() {
+        let hash = "ab".repeat(32);
+        let other = "cd".repeat(32);
+
+        assert!(SnappitTessdataDownload::verify(10, None, Some(&hash), &hash).is_ok());
+        assert!(SnappitTessdataDownload::verify(10, None, Some(&other), &hash).is_err());
+        assert!(SnappitTessdataDownload::verify(10, None, None, &hash).is_ok());
+        assert!(SnappitTessdataDownload::verify(0, None, None, &hash).is_err());
+    }
+
+    #[test]
+    fn test_pinned_checksum() {
+        let eng = SnappitTessdataDownload::pinned_checksum(SnappitTessdataVariant::Fast, "eng")
+            .expect("eng of tessdata_fast is pinned");
+        assert_eq!(eng.sha256.len(), 64);
+
+        assert!(
+            SnappitTessdataDownload::pinned_checksum(SnappitTessdataVariant::Fast, "xyz").is_none()
+        );
+    }
+
+    #[test]
+    fn test_manifest_entries() {
+        for (repository, checksums) in MANIFEST.iter() {
+            for (lang, checksum) in checksums {
+                assert!(
+                    SnappitTessdataDownload::is_known_language(lang),
+                    "{repository}/{lang} isn't a registry language"
+                );
+                assert!(checksum.size > 0, "{repository}/{lang} has no size");
+                assert!(
+                    checksum.sha256.len() == 64
+                        && checksum.sha256.chars().all(|c| c.is_ascii_hexdigit()),
+                    "{repository}/{lang} has no SHA-256"
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[ignore = "needs tessdata_manifest.json regenerated with tessdata-manifest.sh"]
+    fn test_every_language_pinned() {
+        let variants = [
+            SnappitTessdataVariant::Standard,
+            SnappitTessdataVariant::Fast,
+            SnappitTessdataVariant::Best,
+        ];
+
+        for variant in variants {
+            for language in SnappitLanguageRegistry::all() {
+                assert!(
+                    SnappitTessdataDownload::pinned_checksum(variant, language.tesseract).is_some(),
+                    "{}/{} isn't pinned",
+                    variant.repository(),
+                    language.tesseract
+                );
+            }
+        }
+    }
+}
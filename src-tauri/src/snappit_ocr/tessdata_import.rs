@@ -0,0 +1,164 @@
+//! Installs `.traineddata` files from disk, for machines without network access
+//!
+//! Every file is copied into a staging directory inside the tessdata directory,
+//! loaded once by Tesseract and only then renamed into place, so a broken or
+//! mismatched file never replaces a working language. Each import stages into a
+//! directory of its own, so concurrent imports never see each other's files.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use snappit_core::ocr::SnappitTesseractOcr;
+
+use crate::{
+    snappit_errors::SnappitResult,
+    snappit_ocr::{SnappitTessdata, SnappitTessdataDownload},
+};
+
+/// Prefix of the staging directories, hidden so they don't show up as languages
+const STAGING_PREFIX: &str = ".import";
+const TRAINEDDATA_EXTENSION: &str = "traineddata";
+
+/// Where package managers and installers put Tesseract's own language files
+const SYSTEM_TESSDATA_DIRS: &[&str] = &[
+    "/usr/share/tesseract-ocr/5/tessdata",
+    "/usr/share/tesseract-ocr/4.00/tessdata",
+    "/usr/share/tessdata",
+    "/usr/local/share/tessdata",
+    "/opt/homebrew/share/tessdata",
+    "/opt/local/share/tessdata",
+    "C:\\Program Files\\Tesseract-OCR\\tessdata",
+    "C:\\Program Files (x86)\\Tesseract-OCR\\tessdata",
+];
+
+#[derive(Debug, Serialize)]
+pub struct SnappitTessdataImportSkip {
+    pub path: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SnappitTessdataImportReport {
+    pub imported: Vec<String>,
+    pub skipped: Vec<SnappitTessdataImportSkip>,
+}
+
+pub struct SnappitTessdataImport;
+
+impl SnappitTessdataImport {
+    /// Imports the given `.traineddata` files and every such file directly inside the given folders
+    pub fn import(
+        app: &tauri::AppHandle,
+        paths: &[PathBuf],
+    ) -> SnappitResult<SnappitTessdataImportReport> {
+        let data_path = SnappitTessdata::get_data_path(app)?;
+        // Removed with everything left in it once the import returns
+        let staging = tempfile::Builder::new()
+            .prefix(STAGING_PREFIX)
+            .tempdir_in(&data_path)?;
+
+        let mut report = SnappitTessdataImportReport::default();
+
+        for file in Self::collect_files(paths, &mut report) {
+            match Self::import_file(&file, &data_path, staging.path()) {
+                Ok(lang) => {
+                    if !report.imported.contains(&lang) {
+                        report.imported.push(lang);
+                    }
+                }
+                Err(reason) => report.skipped.push(SnappitTessdataImportSkip {
+                    path: file.display().to_string(),
+                    reason,
+                }),
+            }
+        }
+
+        if !report.imported.is_empty() {
+            SnappitTesseractOcr::invalidate_cache();
+        }
+
+        Ok(report)
+    }
+
+    /// Existing system tessdata directories, `TESSDATA_PREFIX` first
+    pub fn system_dirs() -> Vec<PathBuf> {
+        let prefix = std::env::var_os("TESSDATA_PREFIX").map(PathBuf::from);
+
+        prefix
+            .into_iter()
+            .chain(SYSTEM_TESSDATA_DIRS.iter().map(PathBuf::from))
+            .filter(|dir| dir.is_dir() && Self::has_traineddata(dir))
+            .collect()
+    }
+
+    fn has_traineddata(dir: &Path) -> bool {
+        fs::read_dir(dir)
+            .map(|entries| entries.flatten().any(|entry| is_traineddata(&entry.path())))
+            .unwrap_or(false)
+    }
+
+    fn collect_files(paths: &[PathBuf], report: &mut SnappitTessdataImportReport) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+
+        for path in paths {
+            if path.is_dir() {
+                match fs::read_dir(path) {
+                    Ok(entries) => files.extend(
+                        entries
+                            .flatten()
+                            .map(|entry| entry.path())
+                            .filter(|path| is_traineddata(path)),
+                    ),
+                    Err(err) => report.skipped.push(SnappitTessdataImportSkip {
+                        path: path.display().to_string(),
+                        reason: err.to_string(),
+                    }),
+                }
+            } else if is_traineddata(path) {
+                files.push(path.clone());
+            } else {
+                report.skipped.push(SnappitTessdataImportSkip {
+                    path: path.display().to_string(),
+                    reason: "Not a .traineddata file".to_string(),
+                });
+            }
+        }
+
+        files.sort();
+        files
+    }
+
+    /// Returns the imported language, or why the file was skipped
+    fn import_file(file: &Path, data_path: &Path, staging_path: &Path) -> Result<String, String> {
+        let lang = file
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| "Invalid file name".to_string())?;
+
+        // Also keeps the name safe to join onto the tessdata path
+        SnappitTessdataDownload::ensure_known_language(lang).map_err(|e| e.to_string())?;
+
+        let file_name = format!("{lang}.{TRAINEDDATA_EXTENSION}");
+        let staged = staging_path.join(&file_name);
+        fs::copy(file, &staged).map_err(|e| e.to_string())?;
+
+        if let Err(err) = SnappitTesseractOcr::validate_traineddata(staging_path, lang) {
+            let _ = fs::remove_file(&staged);
+            return Err(format!("Tesseract could not load it: {err}"));
+        }
+
+        fs::rename(&staged, data_path.join(&file_name)).map_err(|e| e.to_string())?;
+
+        Ok(lang.to_string())
+    }
+}
+
+fn is_traineddata(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext == TRAINEDDATA_EXTENSION)
+}
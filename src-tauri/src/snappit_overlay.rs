@@ -3,6 +3,7 @@ use std::sync::Mutex;
 use std::thread::{self};
 use std::time::Duration;
 
+use crate::region_capture::RegionCapture;
 use crate::snappit_errors::{SnappitError, SnappitResult, SnappitResultExt};
 use crate::snappit_license::SnappitLicense;
 use crate::snappit_settings::SnappitSettings;
@@ -23,6 +24,7 @@ use tauri::{
 use tauri_nspanel::{
     tauri_panel, CollectionBehavior, ManagerExt, PanelBuilder, PanelHandle, PanelLevel, StyleMask,
 };
+use xcap::Monitor as XCapMonitor;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -31,6 +33,12 @@ pub enum SnappitOverlayTarget {
     DigitalRuler,
     ColorDropper,
     QrScanner,
+    TableScanner,
+    Translator,
+    /// Saves the selection as an image file
+    Screenshot,
+    /// Captures the last region again without showing the overlay
+    RepeatRegion,
     None,
 }
 
@@ -52,16 +60,16 @@ static OVERLAY_CURRENT_TARGET: Lazy<Mutex<Option<SnappitOverlayTarget>>> =
 
 static MONITOR_THREAD_RUNNING: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(false));
 
+static OVERLAY_SELECTING: AtomicBool = AtomicBool::new(false);
+
 #[cfg(target_os = "macos")]
 static PREVIOUS_FOREGROUND_APP: Lazy<Mutex<Option<ObjcRetained<NSRunningApplication>>>> =
     Lazy::new(|| Mutex::new(None));
 
 impl SnappitOverlay {
     pub fn hide(app: &AppHandle<Wry>) -> SnappitResult<WebviewWindow> {
-        {
-            let mut last = OVERLAY_LAST_MONITOR.lock().unwrap();
-            *last = None;
-        }
+        // The last monitor is kept, selections are captured after the overlay is hidden
+        OVERLAY_SELECTING.store(false, Ordering::SeqCst);
         {
             let mut target = OVERLAY_CURRENT_TARGET.lock().unwrap();
             *target = None;
@@ -82,10 +90,61 @@ impl SnappitOverlay {
         Ok(overlay)
     }
 
+    /// Shows the selection overlay for `target`. Repeating the last region has no
+    /// selection, it goes through [`Self::repeat_region`] instead
     pub fn show(
         app: &AppHandle<Wry>,
         target: SnappitOverlayTarget,
     ) -> SnappitResult<WebviewWindow> {
+        Self::ensure_overlay_target(target)?;
+        RegionCapture::unfreeze();
+        Self::ensure_trial(app)?;
+
+        OVERLAY_LAST_MONITOR.lock().unwrap().take();
+        Self::subscribe_monitor_changes(app);
+        Self::actual_show(app, target)
+    }
+
+    /// Shows the overlay over the screen frozen by [`RegionCapture::freeze`]
+    ///
+    /// The frozen frame belongs to one monitor, so the overlay stays there instead of
+    /// following the cursor.
+    pub fn show_frozen(
+        app: &AppHandle<Wry>,
+        target: SnappitOverlayTarget,
+    ) -> SnappitResult<WebviewWindow> {
+        Self::ensure_overlay_target(target)?;
+        Self::ensure_trial(app)?;
+        Self::actual_show(app, target)
+    }
+
+    fn ensure_overlay_target(target: SnappitOverlayTarget) -> SnappitResult<()> {
+        if target == SnappitOverlayTarget::RepeatRegion {
+            return Err(SnappitError::NoOverlayForTarget(target));
+        }
+
+        Ok(())
+    }
+
+    /// Asks the overlay to capture the last region again without showing the selection,
+    /// shows the selection instead when nothing was captured yet
+    pub fn repeat_region(app: &AppHandle<Wry>) -> SnappitResult<()> {
+        if RegionCapture::last_region(app).is_none() {
+            Self::show(app, SnappitOverlayTarget::Capture)?;
+            return Ok(());
+        }
+
+        RegionCapture::unfreeze();
+        Self::ensure_trial(app)?;
+        SnappitPermissions::ensure_for_overlay(app)?;
+
+        let (_, overlay) = Self::ensure_overlay_handles(app)?;
+        overlay.emit("snap_overlay:repeat_region", true)?;
+
+        Ok(())
+    }
+
+    pub fn ensure_trial(app: &AppHandle<Wry>) -> SnappitResult<()> {
         // Check if trial is expired - redirect to settings license tab
         if SnappitLicense::is_trial_expired()? {
             log::info!(
@@ -96,8 +155,7 @@ impl SnappitOverlay {
             return Err(SnappitError::TrialExpired);
         }
 
-        Self::subscribe_monitor_changes(app);
-        Self::actual_show(app, target)
+        Ok(())
     }
 
     fn actual_show(
@@ -124,6 +182,10 @@ impl SnappitOverlay {
         let overlay_was_visible = overlay.is_visible().unwrap_or(false);
         #[cfg(target_os = "macos")]
         Self::remember_previous_app(overlay_was_visible);
+        #[cfg(not(target_os = "macos"))]
+        if !overlay.is_visible().unwrap_or(false) {
+            RegionCapture::remember_focused_window();
+        }
 
         // Hide panel before resizing to prevent visual glitch
         panel.set_alpha_value(0.0);
@@ -158,6 +220,23 @@ impl SnappitOverlay {
         *target
     }
 
+    /// The overlay stays on its monitor while a selection is dragged, so the selection
+    /// can continue onto other monitors
+    pub fn set_selecting(selecting: bool) {
+        OVERLAY_SELECTING.store(selecting, Ordering::SeqCst);
+    }
+
+    /// Monitor the overlay was shown on last, selections are relative to its origin
+    pub fn xcap_monitor() -> SnappitResult<XCapMonitor> {
+        let monitor = OVERLAY_LAST_MONITOR
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or(SnappitError::MonitorNotFound)?;
+
+        Platform::xcap_monitor_of(&monitor)
+    }
+
     pub fn preload(app: &AppHandle<Wry>) -> SnappitResult<WebviewWindow> {
         let (_, window) = Self::ensure_overlay_handles(app)?;
 
@@ -169,6 +248,13 @@ impl SnappitOverlay {
         Ok(window)
     }
 
+    /// Process of the app that was in front before the overlay was shown
+    #[cfg(target_os = "macos")]
+    pub fn previous_app_pid() -> Option<u32> {
+        let slot = PREVIOUS_FOREGROUND_APP.lock().unwrap();
+        slot.as_ref().map(|app| app.processIdentifier() as u32)
+    }
+
     #[cfg(target_os = "macos")]
     fn remember_previous_app(overlay_was_visible: bool) {
         if overlay_was_visible {
@@ -276,6 +362,10 @@ impl SnappitOverlay {
     }
 
     fn detect_monitor_changed(app: &AppHandle<Wry>) -> SnappitResult<()> {
+        if OVERLAY_SELECTING.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
         let monitor = Platform::monitor_from_cursor(&app)?;
         let last_monitor = OVERLAY_LAST_MONITOR.lock().unwrap().clone();
 
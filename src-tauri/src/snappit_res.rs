@@ -1,21 +1,13 @@
 use serde::{Deserialize, Serialize};
+use snappit_core::res::{SnappitBarcode, SnappitOcrResult, SnappitTableResult};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SnappitOcrEngine {
-    Vision,
-    Tesseract,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SnappitOcrResult {
-    pub value: String,
-    pub ocr: SnappitOcrEngine,
-}
+use crate::snappit_translate::SnappitTranslationResult;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", content = "payload", rename_all = "snake_case")]
 pub enum SnappitResponse {
-    Qr(Option<String>),
+    Qr(Vec<SnappitBarcode>),
     Ocr(SnappitOcrResult),
+    Table(SnappitTableResult),
+    Translation(SnappitTranslationResult),
 }
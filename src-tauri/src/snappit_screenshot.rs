@@ -0,0 +1,315 @@
+//! Screenshots
+//!
+//! The screenshot tool keeps the selected region as an image file instead of
+//! recognizing it. Files go into a configurable folder and are named after a template
+//! with the date, the app that was in front and the monitor. The image can also be
+//! copied to the clipboard, so it can be pasted right away. Shapes drawn over the
+//! selection in the overlay are painted onto the image first, see
+//! [`crate::snappit_annotation`].
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, NaiveDateTime};
+use image::{
+    codecs::{jpeg::JpegEncoder, png::PngEncoder},
+    DynamicImage, ExtendedColorType, ImageBuffer, ImageEncoder, Rgba,
+};
+use serde::Deserialize;
+use tauri::{image::Image, AppHandle, Manager, Wry};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::{
+    region_capture::RegionCapture,
+    snappit_consts::SNAPPIT_CONSTS,
+    snappit_errors::{SnappitError, SnappitResult, SnappitResultExt},
+    snappit_store::SnappitStore,
+};
+
+/// Characters that aren't allowed in file names on at least one platform
+const INVALID_FILENAME_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Device names Windows won't create files for, with any extension
+const RESERVED_FILENAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Name used when the template leaves nothing behind
+const FALLBACK_FILENAME: &str = "Snappit";
+
+/// Placeholder value when the app or monitor can't be told
+const UNKNOWN: &str = "Unknown";
+
+/// Subfolder of the pictures folder screenshots go to until another one is configured
+const DEFAULT_FOLDER: &str = "Snappit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SnappitImageFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl SnappitImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SnappitImageFormat::Png => "png",
+            SnappitImageFormat::Jpeg => "jpg",
+            SnappitImageFormat::Webp => "webp",
+        }
+    }
+}
+
+/// Values the placeholders of a filename template are replaced with
+#[derive(Debug, Clone, Copy)]
+pub struct SnappitFilenameContext<'a> {
+    /// Local date and time of the capture
+    pub time: NaiveDateTime,
+    pub app: Option<&'a str>,
+    pub monitor: Option<&'a str>,
+}
+
+/// `name` without characters file systems reject, without the leading and trailing
+/// dots and whitespace some of them drop, and prefixed with `_` when Windows reserves it
+fn sanitized(name: &str) -> String {
+    let name = name
+        .chars()
+        .filter(|c| !c.is_control() && !INVALID_FILENAME_CHARS.contains(c))
+        .collect::<String>();
+    let name = name.trim_matches(|c: char| c.is_whitespace() || c == '.');
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+
+    if RESERVED_FILENAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        format!("_{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+/// File name, without extension, of a screenshot named after `template`. The
+/// placeholders are `{date}` like 2026-10-18, `{time}` like 14.03.22, `{app}` and
+/// `{monitor}`, anything else is kept as it is
+pub fn render_filename(template: &str, context: &SnappitFilenameContext) -> String {
+    let name = template
+        .replace("{date}", &context.time.format("%Y-%m-%d").to_string())
+        .replace("{time}", &context.time.format("%H.%M.%S").to_string())
+        .replace("{app}", context.app.unwrap_or(UNKNOWN))
+        .replace("{monitor}", context.monitor.unwrap_or(UNKNOWN));
+    let name = sanitized(&name);
+
+    if name.is_empty() {
+        FALLBACK_FILENAME.to_string()
+    } else {
+        name
+    }
+}
+
+/// `name` in `folder`, numbered like `name (2)` when a file of that name exists
+fn unique_path(folder: &Path, name: &str, extension: &str) -> PathBuf {
+    let mut path = folder.join(format!("{name}.{extension}"));
+    let mut copy = 1;
+
+    while path.exists() {
+        copy += 1;
+        path = folder.join(format!("{name} ({copy}).{extension}"));
+    }
+
+    path
+}
+
+fn encode(
+    image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+    format: SnappitImageFormat,
+    quality: u8,
+) -> SnappitResult<Vec<u8>> {
+    let (width, height) = image.dimensions();
+    let mut buf = Vec::new();
+
+    match format {
+        SnappitImageFormat::Png => PngEncoder::new(&mut buf).write_image(
+            image.as_raw(),
+            width,
+            height,
+            ExtendedColorType::Rgba8,
+        )?,
+        // JPEG has no alpha channel
+        SnappitImageFormat::Jpeg => JpegEncoder::new_with_quality(&mut buf, quality)
+            .encode_image(&DynamicImage::ImageRgba8(image.clone()).into_rgb8())?,
+        // The image crate only encodes lossless WebP, libwebp honors the quality
+        SnappitImageFormat::Webp => buf.extend_from_slice(
+            &webp::Encoder::from_rgba(image.as_raw(), width, height).encode(f32::from(quality)),
+        ),
+    }
+
+    Ok(buf)
+}
+
+/// Tauri adapter that reads the screenshot settings from the store and writes the files
+pub struct SnappitScreenshot;
+
+impl SnappitScreenshot {
+    /// Saves `image` into the screenshot folder and returns its path
+    pub fn save(
+        app: &AppHandle<Wry>,
+        image: &ImageBuffer<Rgba<u8>, Vec<u8>>,
+    ) -> SnappitResult<PathBuf> {
+        let folder = Self::folder(app)?;
+        fs::create_dir_all(&folder)?;
+
+        let app_name = RegionCapture::focused_app_name();
+        let monitor_name = RegionCapture::selection_monitor(app)
+            .and_then(|monitor| Ok(monitor.name()?))
+            .ok();
+        let context = SnappitFilenameContext {
+            time: Local::now().naive_local(),
+            app: app_name.as_deref(),
+            monitor: monitor_name.as_deref(),
+        };
+
+        let format = Self::format(app);
+        let name = render_filename(&Self::filename_template(app), &context);
+        let path = unique_path(&folder, &name, format.extension());
+        fs::write(&path, encode(image, format, Self::quality(app))?)?;
+
+        // The file is saved by now, a failed copy doesn't make the save fail
+        if Self::copies_image(app) {
+            let (width, height) = image.dimensions();
+            app.clipboard()
+                .write_image(&Image::new(image.as_raw(), width, height))
+                .map_err(SnappitError::from)
+                .log_on_err_with("Failed to copy the screenshot");
+        }
+
+        Ok(path)
+    }
+
+    fn folder(app: &AppHandle<Wry>) -> SnappitResult<PathBuf> {
+        match SnappitStore::get_string(app, &SNAPPIT_CONSTS.store.keys.screenshot_folder) {
+            Some(folder) => Ok(PathBuf::from(folder)),
+            None => Ok(app.path().picture_dir()?.join(DEFAULT_FOLDER)),
+        }
+    }
+
+    fn filename_template(app: &AppHandle<Wry>) -> String {
+        SnappitStore::get_string(app, &SNAPPIT_CONSTS.store.keys.screenshot_filename)
+            .unwrap_or_else(|| SNAPPIT_CONSTS.defaults.screenshot_filename.clone())
+    }
+
+    fn format(app: &AppHandle<Wry>) -> SnappitImageFormat {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.screenshot_format)
+            .ok()
+            .flatten()
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default()
+    }
+
+    fn quality(app: &AppHandle<Wry>) -> u8 {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.screenshot_quality)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_u64())
+            .map(|quality| quality.clamp(1, 100) as u8)
+            .unwrap_or(SNAPPIT_CONSTS.defaults.screenshot_quality)
+    }
+
+    fn copies_image(app: &AppHandle<Wry>) -> bool {
+        SnappitStore::get_value(app, &SNAPPIT_CONSTS.store.keys.screenshot_copy_image)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, min, sec))
+            .unwrap()
+    }
+
+    fn context(time: NaiveDateTime) -> SnappitFilenameContext<'static> {
+        SnappitFilenameContext {
+            time,
+            app: Some("Safari"),
+            monitor: Some("DELL U2720Q"),
+        }
+    }
+
+    #[test]
+    fn test_render_filename_placeholders() {
+        let leap_day = context(at(2024, 2, 29, 13, 5, 9));
+
+        assert_eq!(
+            render_filename("Snappit {date} at {time}", &leap_day),
+            "Snappit 2024-02-29 at 13.05.09"
+        );
+        assert_eq!(
+            render_filename("{app} on {monitor} {other}", &leap_day),
+            "Safari on DELL U2720Q {other}"
+        );
+        assert_eq!(
+            render_filename("{date} {time}", &context(at(1999, 12, 31, 23, 59, 59))),
+            "1999-12-31 23.59.59"
+        );
+
+        let unknown = SnappitFilenameContext {
+            app: None,
+            monitor: None,
+            ..leap_day
+        };
+        assert_eq!(render_filename("{app}", &unknown), "Unknown");
+    }
+
+    #[test]
+    fn test_render_filename_strips_invalid_characters() {
+        let context = SnappitFilenameContext {
+            time: at(2026, 1, 1, 0, 0, 0),
+            app: Some("Notes: draft/2"),
+            monitor: Some("\\\\.\\DISPLAY1"),
+        };
+
+        assert_eq!(
+            render_filename("{app} | {monitor}", &context),
+            "Notes draft2  .DISPLAY1"
+        );
+        assert_eq!(render_filename(" ../.. ", &context), "Snappit");
+        assert_eq!(render_filename("\tnotes. . ", &context), "notes");
+    }
+
+    #[test]
+    fn test_encode_webp_honors_quality() {
+        let image =
+            ImageBuffer::from_fn(64, 64, |x, y| Rgba([(x * 4) as u8, (y * 4) as u8, 90, 255]));
+
+        let low = encode(&image, SnappitImageFormat::Webp, 10).unwrap();
+        let high = encode(&image, SnappitImageFormat::Webp, 95).unwrap();
+
+        assert!(low.starts_with(b"RIFF") && &low[8..12] == b"WEBP");
+        assert!(low.len() < high.len());
+    }
+
+    #[test]
+    fn test_render_filename_avoids_reserved_names() {
+        let context = context(at(2026, 1, 1, 0, 0, 0));
+
+        assert_eq!(render_filename("con", &context), "_con");
+        assert_eq!(render_filename("NUL.tar", &context), "_NUL.tar");
+        assert_eq!(render_filename("COM1 .", &context), "_COM1");
+        assert_eq!(render_filename("LPT0", &context), "LPT0");
+        assert_eq!(render_filename("Console", &context), "Console");
+    }
+}
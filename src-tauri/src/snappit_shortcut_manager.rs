@@ -8,6 +8,7 @@ use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutEvent, ShortcutSta
 
 use crate::{
     snappit_consts::SNAPPIT_CONSTS,
+    snappit_delayed_capture::SnappitDelayedCapture,
     snappit_errors::{SnappitError, SnappitResult, SnappitResultExt},
     snappit_overlay::{SnappitOverlay, SnappitOverlayTarget},
     snappit_store::SnappitStore,
@@ -19,11 +20,15 @@ static REGISTERED_SHORTCUTS: Lazy<Mutex<HashMap<SnappitOverlayTarget, String>>>
 pub struct SnappitShortcutManager;
 
 impl SnappitShortcutManager {
-    const TARGETS: [SnappitOverlayTarget; 4] = [
+    const TARGETS: [SnappitOverlayTarget; 8] = [
         SnappitOverlayTarget::Capture,
         SnappitOverlayTarget::DigitalRuler,
         SnappitOverlayTarget::ColorDropper,
         SnappitOverlayTarget::QrScanner,
+        SnappitOverlayTarget::TableScanner,
+        SnappitOverlayTarget::Translator,
+        SnappitOverlayTarget::Screenshot,
+        SnappitOverlayTarget::RepeatRegion,
     ];
 
     pub fn sync_all(app: &AppHandle<Wry>) -> SnappitResult<()> {
@@ -152,9 +157,11 @@ impl SnappitShortcutManager {
                 accelerator_string.as_str(),
                 move |app_handle, _shortcut, event: ShortcutEvent| {
                     if event.state == ShortcutState::Pressed {
-                        SnappitOverlay::show(&app_handle, target_for_handler).log_on_err_with(
-                            &format!("Failed to show overlay for {:?}", target_for_handler),
-                        );
+                        SnappitDelayedCapture::show(&app_handle, target_for_handler)
+                            .log_on_err_with(&format!(
+                                "Failed to show overlay for {:?}",
+                                target_for_handler
+                            ));
                     }
                 },
             )?;
@@ -175,6 +182,14 @@ impl SnappitShortcutManager {
                 SNAPPIT_CONSTS.store.keys.hotkey_color_dropper.clone()
             }
             SnappitOverlayTarget::QrScanner => SNAPPIT_CONSTS.store.keys.hotkey_qr_scanner.clone(),
+            SnappitOverlayTarget::TableScanner => {
+                SNAPPIT_CONSTS.store.keys.hotkey_table_scanner.clone()
+            }
+            SnappitOverlayTarget::Translator => SNAPPIT_CONSTS.store.keys.hotkey_translator.clone(),
+            SnappitOverlayTarget::Screenshot => SNAPPIT_CONSTS.store.keys.hotkey_screenshot.clone(),
+            SnappitOverlayTarget::RepeatRegion => {
+                SNAPPIT_CONSTS.store.keys.hotkey_repeat_region.clone()
+            }
             SnappitOverlayTarget::None => {
                 unreachable!("store key requested for SnappitOverlayTarget::None")
             }
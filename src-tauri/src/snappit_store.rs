@@ -11,6 +11,15 @@ impl SnappitStore {
         Ok(store.get(key))
     }
 
+    /// Stored string without surrounding whitespace, `None` when missing or blank
+    pub fn get_string(app: &tauri::AppHandle, key: &str) -> Option<String> {
+        Self::get_value(app, key)
+            .ok()
+            .flatten()
+            .and_then(|value| value.as_str().map(|value| value.trim().to_string()))
+            .filter(|value| !value.is_empty())
+    }
+
     pub fn set_value(
         app: &tauri::AppHandle,
         key: &str,
@@ -21,4 +30,11 @@ impl SnappitStore {
         store.save()?;
         Ok(())
     }
+
+    pub fn delete_value(app: &tauri::AppHandle, key: &str) -> SnappitResult<()> {
+        let store = app.store(SNAPPIT_CONSTS.store.file.as_str())?;
+        store.delete(key);
+        store.save()?;
+        Ok(())
+    }
 }
@@ -0,0 +1,193 @@
+//! Client for servers speaking the LibreTranslate API
+//!
+//! `POST <url>/translate` with the text, the source language (`auto` to detect
+//! it) and the target language. Self-hosted servers usually run without an API
+//! key, public ones reject requests missing it with `403`.
+
+use std::time::Duration;
+
+use reqwest::{blocking::Client, header::CONTENT_TYPE, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::snappit_errors::{SnappitError, SnappitResult};
+
+use super::{SnappitTranslation, Translator};
+
+/// Translating a full screen of text on a small server takes a while
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+const AUTO_SOURCE: &str = "auto";
+
+#[derive(Serialize)]
+struct SnappitLibreTranslateRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnappitLibreTranslateResponse {
+    translated_text: String,
+    detected_language: Option<SnappitLibreTranslateDetection>,
+}
+
+#[derive(Deserialize)]
+struct SnappitLibreTranslateDetection {
+    language: String,
+}
+
+#[derive(Deserialize)]
+struct SnappitLibreTranslateError {
+    error: String,
+}
+
+pub struct SnappitLibreTranslate {
+    /// Server root, e.g. `http://localhost:5000`
+    url: String,
+    api_key: Option<String>,
+}
+
+impl SnappitLibreTranslate {
+    pub fn new(url: &str, api_key: Option<String>) -> Self {
+        Self {
+            url: url.trim().trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|key| !key.trim().is_empty()),
+        }
+    }
+
+    fn request<'a>(
+        &'a self,
+        text: &'a str,
+        source: Option<&'a str>,
+        target: &'a str,
+    ) -> SnappitLibreTranslateRequest<'a> {
+        SnappitLibreTranslateRequest {
+            q: text,
+            source: source.unwrap_or(AUTO_SOURCE),
+            target,
+            format: "text",
+            api_key: self.api_key.as_deref(),
+        }
+    }
+
+    /// The translation in a successful response, the server's explanation otherwise
+    fn parse_response(status: StatusCode, body: &[u8]) -> SnappitResult<SnappitTranslation> {
+        if !status.is_success() {
+            // The server explains rejected requests, e.g. unsupported languages
+            let message = serde_json::from_slice::<SnappitLibreTranslateError>(body)
+                .map(|body| body.error)
+                .unwrap_or_else(|_| status.to_string());
+
+            return Err(SnappitError::Translation(message));
+        }
+
+        let body: SnappitLibreTranslateResponse = serde_json::from_slice(body)?;
+
+        Ok(SnappitTranslation {
+            text: body.translated_text,
+            detected_source: body.detected_language.map(|detected| detected.language),
+        })
+    }
+}
+
+impl Translator for SnappitLibreTranslate {
+    fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> SnappitResult<SnappitTranslation> {
+        if self.url.is_empty() {
+            return Err(SnappitError::Translation(
+                "no translation server configured".into(),
+            ));
+        }
+
+        let request = self.request(text, source, target);
+
+        let response = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?
+            .post(format!("{}/translate", self.url))
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&request)?)
+            .send()?;
+
+        let status = response.status();
+        Self::parse_response(status, &response.bytes()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn request_json(translator: &SnappitLibreTranslate, source: Option<&str>) -> serde_json::Value {
+        serde_json::to_value(translator.request("こんにちは", source, "en")).unwrap()
+    }
+
+    #[test]
+    fn test_request() {
+        let translator = SnappitLibreTranslate::new(" http://localhost:5000/ ", Some(" ".into()));
+        assert_eq!(translator.url, "http://localhost:5000");
+        assert_eq!(
+            request_json(&translator, None),
+            json!({ "q": "こんにちは", "source": "auto", "target": "en", "format": "text" })
+        );
+
+        let translator =
+            SnappitLibreTranslate::new("https://libretranslate.com", Some("key".into()));
+        assert_eq!(
+            request_json(&translator, Some("ja")),
+            json!({
+                "q": "こんにちは",
+                "source": "ja",
+                "target": "en",
+                "format": "text",
+                "api_key": "key",
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let body =
+            br#"{"translatedText":"Hello","detectedLanguage":{"confidence":90,"language":"ja"}}"#;
+        let translation = SnappitLibreTranslate::parse_response(StatusCode::OK, body).unwrap();
+        assert_eq!(translation.text, "Hello");
+        assert_eq!(translation.detected_source.as_deref(), Some("ja"));
+
+        let translation =
+            SnappitLibreTranslate::parse_response(StatusCode::OK, br#"{"translatedText":"Hi"}"#)
+                .unwrap();
+        assert_eq!(translation.detected_source, None);
+
+        assert!(SnappitLibreTranslate::parse_response(StatusCode::OK, b"<html>").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_response() {
+        let error = |status, body: &[u8]| match SnappitLibreTranslate::parse_response(status, body)
+        {
+            Err(SnappitError::Translation(message)) => message,
+            other => panic!("expected a translation error, got {other:?}"),
+        };
+
+        assert_eq!(
+            error(
+                StatusCode::BAD_REQUEST,
+                br#"{"error":"xx is not supported"}"#
+            ),
+            "xx is not supported"
+        );
+        assert_eq!(
+            error(StatusCode::BAD_GATEWAY, b"Bad Gateway"),
+            "502 Bad Gateway"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+mod libre_translate;
+mod snappit_translate;
+mod translator;
+
+pub use libre_translate::SnappitLibreTranslate;
+pub use snappit_translate::{SnappitTranslate, SnappitTranslationResult};
+pub use translator::{SnappitTranslation, Translator};
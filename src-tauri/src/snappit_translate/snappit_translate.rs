@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use snappit_core::{
+    ocr::{recognition_language::split_recognition_languages, SnappitLanguageRegistry},
+    res::{SnappitOcrEngine, SnappitOcrResult},
+};
+
+use crate::{
+    snappit_consts::SNAPPIT_CONSTS, snappit_errors::SnappitResult, snappit_store::SnappitStore,
+};
+
+use super::{SnappitLibreTranslate, Translator};
+
+/// Recognized text together with its translation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnappitTranslationResult {
+    pub source_text: String,
+    pub translated_text: String,
+    /// BCP 47 tag of the recognized text, `None` when neither script detection,
+    /// the settings nor the translator tell which one it is
+    pub source_language: Option<String>,
+    pub target_language: String,
+    pub ocr: SnappitOcrEngine,
+}
+
+/// Tauri adapter around the translators: reads the server and target language
+/// from the store
+pub struct SnappitTranslate;
+
+impl SnappitTranslate {
+    /// Translates the text of an OCR `result`. The source language is the one
+    /// script detection picked, else the configured one. Without either the
+    /// translator detects it, the recognition languages are only what the text
+    /// may be in
+    pub fn translate(
+        app: &tauri::AppHandle,
+        result: &SnappitOcrResult,
+    ) -> SnappitResult<SnappitTranslationResult> {
+        let target_language = Self::get_target_language(app);
+        let source = result
+            .detected_language
+            .as_deref()
+            .and_then(|detected| source_language(&split_recognition_languages(detected)))
+            .map(String::from)
+            .or_else(|| Self::get_source_language(app));
+
+        if result.value.trim().is_empty() {
+            return Ok(SnappitTranslationResult {
+                source_text: String::new(),
+                translated_text: String::new(),
+                source_language: source,
+                target_language,
+                ocr: result.ocr,
+            });
+        }
+
+        let translation =
+            Self::translator(app).translate(&result.value, source.as_deref(), &target_language)?;
+
+        Ok(SnappitTranslationResult {
+            source_text: result.value.clone(),
+            translated_text: translation.text,
+            source_language: source.or(translation.detected_source),
+            target_language,
+            ocr: result.ocr,
+        })
+    }
+
+    /// LibreTranslate-compatible servers are the only backend so far
+    fn translator(app: &tauri::AppHandle) -> Box<dyn Translator> {
+        let keys = &SNAPPIT_CONSTS.store.keys;
+        let url = SnappitStore::get_string(app, &keys.translate_url)
+            .unwrap_or_else(|| SNAPPIT_CONSTS.defaults.translate_url.clone());
+
+        Box::new(SnappitLibreTranslate::new(
+            &url,
+            SnappitStore::get_string(app, &keys.translate_api_key),
+        ))
+    }
+
+    /// Language the user translates from, `None` leaves it to the translator
+    fn get_source_language(app: &tauri::AppHandle) -> Option<String> {
+        SnappitStore::get_string(app, &SNAPPIT_CONSTS.store.keys.translate_source_lang)
+    }
+
+    fn get_target_language(app: &tauri::AppHandle) -> String {
+        SnappitStore::get_string(app, &SNAPPIT_CONSTS.store.keys.translate_target_lang)
+            .unwrap_or_else(|| SNAPPIT_CONSTS.defaults.translate_target_lang.clone())
+    }
+}
+
+/// The language all of `languages` share, `None` for a mix like `jpn+eng`
+fn source_language(languages: &[String]) -> Option<&'static str> {
+    let mut tags = languages
+        .iter()
+        .map(|language| SnappitLanguageRegistry::find(language).map(|found| found.bcp47));
+    let first = tags.next()??;
+
+    tags.all(|tag| tag == Some(first)).then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn languages(languages: &[&str]) -> Vec<String> {
+        languages
+            .iter()
+            .map(|language| language.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_source_language() {
+        assert_eq!(source_language(&languages(&["jpn"])), Some("ja"));
+        assert_eq!(
+            source_language(&languages(&["kor", "kor_vert"])),
+            Some("ko")
+        );
+        assert_eq!(source_language(&languages(&["jpn", "eng"])), None);
+        assert_eq!(source_language(&languages(&["xyz"])), None);
+        assert_eq!(source_language(&[]), None);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::snappit_errors::SnappitResult;
+
+/// Text a [`Translator`] returned
+#[derive(Debug, Clone)]
+pub struct SnappitTranslation {
+    pub text: String,
+    /// Language the backend detected when it was asked to, as a BCP 47 tag
+    pub detected_source: Option<String>,
+}
+
+/// A machine translation backend
+pub trait Translator {
+    /// Translates `text` into `target`. Languages are BCP 47 tags like `ja` or
+    /// `zh-Hans`, a `source` of `None` leaves detecting it to the backend
+    fn translate(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> SnappitResult<SnappitTranslation>;
+}
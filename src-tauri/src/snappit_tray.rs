@@ -11,6 +11,7 @@ use tauri::{
 
 use crate::{
     snappit_consts::SNAPPIT_CONSTS,
+    snappit_delayed_capture::SnappitDelayedCapture,
     snappit_errors::{SnappitError, SnappitResult},
     snappit_license::{LicenseType, SnappitLicense},
     snappit_overlay::{SnappitOverlay, SnappitOverlayTarget},
@@ -29,6 +30,14 @@ pub enum SnappitTrayItemId {
     ColorDropper,
     #[strum(serialize = "qr")]
     Qr,
+    #[strum(serialize = "table")]
+    Table,
+    #[strum(serialize = "translate")]
+    Translate,
+    #[strum(serialize = "screenshot")]
+    Screenshot,
+    #[strum(serialize = "repeat_region")]
+    RepeatRegion,
     #[strum(serialize = "settings")]
     Settings,
     #[strum(serialize = "quit")]
@@ -114,13 +123,29 @@ fn hotkey_qr_scanner_key() -> String {
     SNAPPIT_CONSTS.store.keys.hotkey_qr_scanner.clone()
 }
 
+fn hotkey_table_scanner_key() -> String {
+    SNAPPIT_CONSTS.store.keys.hotkey_table_scanner.clone()
+}
+
+fn hotkey_translator_key() -> String {
+    SNAPPIT_CONSTS.store.keys.hotkey_translator.clone()
+}
+
+fn hotkey_screenshot_key() -> String {
+    SNAPPIT_CONSTS.store.keys.hotkey_screenshot.clone()
+}
+
+fn hotkey_repeat_region_key() -> String {
+    SNAPPIT_CONSTS.store.keys.hotkey_repeat_region.clone()
+}
+
 pub const TRAY_ITEMS: &[SnappitTrayItem] = &[
     SnappitTrayItem::item_with_accelerator(
         SnappitTrayItemId::Capture,
         "Capture",
         true,
         hotkey_capture_key,
-        |app| match SnappitOverlay::show(app, SnappitOverlayTarget::Capture) {
+        |app| match SnappitDelayedCapture::show(app, SnappitOverlayTarget::Capture) {
             Ok(_) => Ok(()),
             Err(SnappitError::MissingPermissions(_)) => Ok(()),
             Err(err) => Err(err),
@@ -159,6 +184,50 @@ pub const TRAY_ITEMS: &[SnappitTrayItem] = &[
             Err(err) => Err(err),
         },
     ),
+    SnappitTrayItem::item_with_accelerator(
+        SnappitTrayItemId::Table,
+        "Table Scanner",
+        true,
+        hotkey_table_scanner_key,
+        |app| match SnappitOverlay::show(app, SnappitOverlayTarget::TableScanner) {
+            Ok(_) => Ok(()),
+            Err(SnappitError::MissingPermissions(_)) => Ok(()),
+            Err(err) => Err(err),
+        },
+    ),
+    SnappitTrayItem::item_with_accelerator(
+        SnappitTrayItemId::Translate,
+        "Translate",
+        true,
+        hotkey_translator_key,
+        |app| match SnappitOverlay::show(app, SnappitOverlayTarget::Translator) {
+            Ok(_) => Ok(()),
+            Err(SnappitError::MissingPermissions(_)) => Ok(()),
+            Err(err) => Err(err),
+        },
+    ),
+    SnappitTrayItem::item_with_accelerator(
+        SnappitTrayItemId::Screenshot,
+        "Screenshot",
+        true,
+        hotkey_screenshot_key,
+        |app| match SnappitOverlay::show(app, SnappitOverlayTarget::Screenshot) {
+            Ok(_) => Ok(()),
+            Err(SnappitError::MissingPermissions(_)) => Ok(()),
+            Err(err) => Err(err),
+        },
+    ),
+    SnappitTrayItem::item_with_accelerator(
+        SnappitTrayItemId::RepeatRegion,
+        "Repeat Last Region",
+        true,
+        hotkey_repeat_region_key,
+        |app| match SnappitOverlay::repeat_region(app) {
+            Ok(_) => Ok(()),
+            Err(SnappitError::MissingPermissions(_)) => Ok(()),
+            Err(err) => Err(err),
+        },
+    ),
     SnappitTrayItem::separator(),
     SnappitTrayItem::item(SnappitTrayItemId::Settings, "Settings...", true, |app| {
         SnappitSettings::show(app)?;
@@ -263,6 +332,18 @@ impl SnappitTray {
                 Some((SnappitTrayItemId::ColorDropper, hotkey_color_dropper_key))
             }
             SnappitOverlayTarget::QrScanner => Some((SnappitTrayItemId::Qr, hotkey_qr_scanner_key)),
+            SnappitOverlayTarget::TableScanner => {
+                Some((SnappitTrayItemId::Table, hotkey_table_scanner_key))
+            }
+            SnappitOverlayTarget::Translator => {
+                Some((SnappitTrayItemId::Translate, hotkey_translator_key))
+            }
+            SnappitOverlayTarget::Screenshot => {
+                Some((SnappitTrayItemId::Screenshot, hotkey_screenshot_key))
+            }
+            SnappitOverlayTarget::RepeatRegion => {
+                Some((SnappitTrayItemId::RepeatRegion, hotkey_repeat_region_key))
+            }
             SnappitOverlayTarget::None => None,
         }
     }
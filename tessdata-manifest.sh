@@ -0,0 +1,48 @@
+#!/bin/sh
+# tessdata-manifest.sh (POSIX /bin/sh)
+# Prints the size and SHA-256 of every traineddata file as the JSON table the app
+# pins downloads to (src-tauri/src/snappit_ocr/tessdata_manifest.json)
+#
+# Usage: tessdata-manifest.sh TESSDATA_DIR TESSDATA_FAST_DIR TESSDATA_BEST_DIR
+# Each directory is a checkout of the tesseract-ocr repository of that name, at
+# the revision the default mirror serves.
+
+set -eu
+
+if [ $# -ne 3 ]; then
+  echo "Usage: $0 TESSDATA_DIR TESSDATA_FAST_DIR TESSDATA_BEST_DIR" >&2
+  exit 1
+fi
+
+sha256() {
+  if command -v sha256sum >/dev/null 2>&1; then
+    sum=$(sha256sum "$1")
+  else
+    sum=$(shasum -a 256 "$1")
+  fi
+  echo "${sum%% *}"
+}
+
+# -------- print one variant: REPOSITORY DIR --------
+print_variant() {
+  printf '  "%s": {' "$1"
+  sep=""
+  for file in "$2"/*.traineddata; do
+    [ -f "$file" ] || continue
+    lang=$(basename "$file" .traineddata)
+    size=$(wc -c < "$file" | tr -d ' ')
+    printf '%s\n    "%s": { "size": %s, "sha256": "%s" }' "$sep" "$lang" "$size" "$(sha256 "$file")"
+    sep=","
+  done
+  [ -z "$sep" ] || printf '\n  '
+  printf '}'
+}
+
+echo "{"
+print_variant tessdata "$1"
+echo ","
+print_variant tessdata_fast "$2"
+echo ","
+print_variant tessdata_best "$3"
+echo
+echo "}"